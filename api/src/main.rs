@@ -16,14 +16,18 @@ pub mod messenger;
 pub mod routes;
 pub mod services;
 
+use archer::FamilyVersion;
 use archer_config::get_configuration;
 use database::{establish_connection, PgPool};
+use std::convert::TryFrom;
 
 pub const AES_KEY: &str = "ffffffffffffffffffffffffffffffff";
 pub const SECRET_KEY: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890";
 
 pub struct AppData {
     pub pool: PgPool,
+    /// Version of the payload format sent to the processor
+    pub family_version: FamilyVersion,
 }
 
 #[actix_rt::main]
@@ -37,16 +41,26 @@ async fn main() -> std::io::Result<()> {
         configuration.application.host, configuration.application.port
     );
 
+    let family_version = FamilyVersion::try_from(configuration.application.family_version.as_str())
+        .expect("Failed to read family version.");
+
     let listener = TcpListener::bind(address)?;
 
-    run(listener, pool)?.await?;
+    run(listener, pool, family_version)?.await?;
 
     Ok(())
 }
 
-fn run(listener: TcpListener, pool: PgPool) -> Result<Server, std::io::Error> {
+fn run(
+    listener: TcpListener,
+    pool: PgPool,
+    family_version: FamilyVersion,
+) -> Result<Server, std::io::Error> {
     // TODO !! find solution for keys in production
-    let data = web::Data::new(AppData { pool });
+    let data = web::Data::new(AppData {
+        pool,
+        family_version,
+    });
 
     let server = HttpServer::new(move || {
         let auth = HttpAuthentication::bearer(validator);
@@ -57,10 +71,33 @@ fn run(listener: TcpListener, pool: PgPool) -> Result<Server, std::io::Error> {
             .data(data.clone())
             .route("/health-check", web::get().to(routes::health_check))
             .route("/balance", web::get().to(routes::get_balance))
+            .route("/balances", web::get().to(routes::get_balances))
             .route("/withdraw", web::put().to(routes::withdraw))
             .route("/deposit", web::put().to(routes::deposit))
+            .route("/transfer", web::put().to(routes::transfer))
+            .route("/earn", web::put().to(routes::earn))
+            .route("/earn-rule", web::post().to(routes::set_earn_rule))
+            .route("/atomic", web::put().to(routes::atomic))
+            .route("/catalog", web::get().to(routes::get_catalog))
+            .route("/catalog-item", web::post().to(routes::set_catalog_item))
+            .route("/redeem", web::put().to(routes::redeem))
+            .route("/vouchers", web::get().to(routes::get_vouchers))
+            .route("/issue-voucher", web::put().to(routes::issue_voucher))
+            .route("/consume-voucher", web::put().to(routes::consume_voucher))
+            .route("/expire", web::put().to(routes::expire))
+            .route("/expiring", web::get().to(routes::get_expiring))
+            .route("/settlements", web::get().to(routes::get_settlements))
+            .route("/account-status", web::get().to(routes::get_account_status))
+            .route("/tier", web::get().to(routes::get_tier))
+            .route("/freeze", web::put().to(routes::freeze))
+            .route("/unfreeze", web::put().to(routes::unfreeze))
+            .route("/close", web::put().to(routes::close))
             .route("/add-acount", web::post().to(routes::add_account))
             .route("/add-merchant", web::post().to(routes::add_merchant))
+            .route(
+                "/rotate-merchant-key",
+                web::put().to(routes::rotate_merchant_key),
+            )
     })
     .listen(listener)?
     .run();
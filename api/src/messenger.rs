@@ -1,25 +1,125 @@
 use super::services::{
-    make_add_account_txn, make_add_merchant_txn, make_deposit_txn, make_withdraw_txn,
+    make_add_account_txn, make_add_merchant_txn, make_atomic_txn, make_close_txn,
+    make_consume_voucher_txn, make_deposit_txn, make_earn_txn, make_expire_txn, make_freeze_txn,
+    make_issue_voucher_txn, make_redeem_txn, make_rotate_merchant_key_txn,
+    make_set_catalog_item_txn, make_set_earn_rule_txn, make_transfer_txn, make_unfreeze_txn,
+    make_withdraw_txn, Operation, OwnerSignature,
 };
+use archer::FamilyVersion;
+use archer_protobuf::earn_rule::EarnRule_Rounding;
+use archer_protobuf::receipt::Receipt as ReceiptPB;
 use protobuf::parse_from_bytes;
 use reqwest::Client;
-use sawtooth_sdk::messages::client_batch_submit::{
-    ClientBatchStatusResponse, ClientBatchStatus_InvalidTransaction, ClientBatchStatus_Status,
-};
+use sawtooth_sdk::messages::batch::BatchList;
 use sawtooth_sdk::signing::{
     create_context, secp256k1, Context, CryptoFactory, PrivateKey, Signer,
 };
+use serde::Deserialize;
 use std::sync::Arc;
 use tracing::{error, info};
 
+/// Body of the REST API `/receipts` endpoint
+#[derive(Deserialize)]
+struct ReceiptsResponse {
+    data: Vec<TransactionReceiptJson>,
+}
+
+#[derive(Deserialize)]
+struct TransactionReceiptJson {
+    // Base64, one entry per `add_receipt_data` call
+    data: Vec<String>,
+}
+
+fn parse_receipts(receipts: ReceiptsResponse) -> Vec<ReceiptPB> {
+    receipts
+        .data
+        .iter()
+        .flat_map(|receipt| receipt.data.iter())
+        .filter_map(|data| base64::decode(data).ok())
+        .filter_map(|data| parse_from_bytes(&data).ok())
+        .collect()
+}
+
+// Seconds the REST API holds a status request open while the batch is pending
+const STATUS_WAIT: u32 = 10;
+// Status requests made before a batch still pending is given up on
+const MAX_STATUS_POLLS: u32 = 6;
+
+/// Body of the REST API `/batch_statuses` endpoint
+#[derive(Deserialize)]
+struct BatchStatusesResponse {
+    data: Vec<BatchStatusJson>,
+}
+
+#[derive(Deserialize)]
+struct BatchStatusJson {
+    id: String,
+    status: String,
+    #[serde(default)]
+    invalid_transactions: Vec<InvalidTransactionJson>,
+}
+
+#[derive(Deserialize)]
+struct InvalidTransactionJson {
+    #[serde(default)]
+    message: String,
+}
+
+/// Where a submitted batch stands. Pending batches may still commit, unknown ones
+/// never reached the validator or were dropped by it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BatchStatus {
+    Committed,
+    Invalid(String),
+    Pending,
+    Unknown,
+}
+
+/// Why submitted transactions did not commit, either the status the batch was left in
+/// or the request that failed along the way
+#[derive(Debug, PartialEq)]
+pub enum SubmitError {
+    Status(BatchStatus),
+    Request(String),
+}
+
+fn parse_batch_status(
+    statuses: BatchStatusesResponse,
+    batch_id: &str,
+) -> Result<BatchStatus, String> {
+    let status = statuses
+        .data
+        .into_iter()
+        .find(|status| status.id == batch_id)
+        .ok_or_else(|| format!("No status returned for batch {}", batch_id))?;
+    match status.status.as_str() {
+        "COMMITTED" => Ok(BatchStatus::Committed),
+        "INVALID" => Ok(BatchStatus::Invalid(
+            status
+                .invalid_transactions
+                .into_iter()
+                .map(|transaction| transaction.message)
+                .collect::<Vec<String>>()
+                .join("; "),
+        )),
+        "PENDING" => Ok(BatchStatus::Pending),
+        "UNKNOWN" => Ok(BatchStatus::Unknown),
+        other => Err(format!(
+            "Unexpected status {} for batch {}",
+            other, batch_id
+        )),
+    }
+}
+
 pub struct Messenger<'a> {
     client: Client,
     context: Arc<Box<dyn Context + 'a>>,
     batch_private_key: Arc<Box<dyn PrivateKey + 'a>>,
+    family_version: FamilyVersion,
 }
 
 impl Messenger<'_> {
-    pub fn new<'a>(algo: &'a str) -> Self {
+    pub fn new<'a>(algo: &'a str, family_version: FamilyVersion) -> Self {
         let client: Client = Client::new();
         let context = create_context(algo).expect("Error creating the right context");
         let batch_private_key: Box<dyn PrivateKey> = context
@@ -31,6 +131,7 @@ impl Messenger<'_> {
             client,
             context: Arc::new(context),
             batch_private_key: Arc::new(batch_private_key),
+            family_version,
         }
     }
 
@@ -49,13 +150,17 @@ impl Messenger<'_> {
         (public_key.as_hex(), private_key.as_hex())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn send_deposit_txn(
         &self,
         private_key: &str,
         name: String,
         number: u32,
-        amount: i32,
-    ) {
+        currency: String,
+        amount: u64,
+        timestamp: i64,
+        expires_at: Option<i64>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
         let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
 
         let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
@@ -64,22 +169,35 @@ impl Messenger<'_> {
         let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
 
         let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
-        let (encoded_batches, batch_header_signature): (Vec<u8>, String) =
-            make_deposit_txn(&transaction_signer, &batch_signer, name, number, amount);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_deposit_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            currency,
+            amount,
+            timestamp,
+            expires_at,
+        );
 
         info!("Sending encoded batches");
 
         self.send_and_wait(encoded_batches, batch_header_signature)
-            .await;
+            .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn send_withdraw_txn(
         &self,
         private_key: &str,
         name: String,
         number: u32,
-        amount: i32,
-    ) {
+        currency: String,
+        amount: u64,
+        timestamp: i64,
+        owner_signature: Option<OwnerSignature>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
         let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
 
         let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
@@ -88,16 +206,71 @@ impl Messenger<'_> {
         let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
 
         let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
-        let (encoded_batches, batch_header_signature): (Vec<u8>, String) =
-            make_withdraw_txn(&transaction_signer, &batch_signer, name, number, amount);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_withdraw_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            currency,
+            amount,
+            timestamp,
+            owner_signature,
+        );
 
         info!("Sending encoded batches");
 
         self.send_and_wait(encoded_batches, batch_header_signature)
-            .await;
+            .await
     }
 
-    pub async fn send_add_account_txn(&self, private_key: &str, name: String, number: u32) {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_transfer_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+        currency: String,
+        recipient_name: String,
+        recipient_number: u32,
+        amount: u64,
+        timestamp: i64,
+        owner_signature: Option<OwnerSignature>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_transfer_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            currency,
+            recipient_name,
+            recipient_number,
+            amount,
+            timestamp,
+            owner_signature,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    pub async fn send_add_account_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+        owner_public_key: Option<String>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
         let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
 
         let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
@@ -106,16 +279,27 @@ impl Messenger<'_> {
         let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
 
         let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
-        let (encoded_batches, batch_header_signature): (Vec<u8>, String) =
-            make_add_account_txn(&transaction_signer, &batch_signer, name, number);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_add_account_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            owner_public_key,
+        );
 
         info!("Sending encoded batches");
 
         self.send_and_wait(encoded_batches, batch_header_signature)
-            .await;
+            .await
     }
 
-    pub async fn send_add_merchant_txn(&self, private_key: &str, name: String, timestamp: i64) {
+    pub async fn send_add_merchant_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        timestamp: i64,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
         let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
 
         let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
@@ -123,71 +307,567 @@ impl Messenger<'_> {
             .expect("Error generating secp256k1 private key from hex");
         let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
 
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_add_merchant_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            timestamp,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    /// Batches and batch id of the rotation, built ahead of `send_and_commit` so the
+    /// caller can record the rotation before submitting it
+    pub fn make_rotate_merchant_key_batches(
+        &self,
+        private_key: &str,
+        new_public_key: String,
+    ) -> (Vec<u8>, String) {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .ok()
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        make_rotate_merchant_key_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            new_public_key,
+        )
+    }
+
+    pub async fn send_set_earn_rule_txn(
+        &self,
+        private_key: &str,
+        points_per_unit: u32,
+        rounding: EarnRule_Rounding,
+        minimum_purchase: u64,
+        multipliers: Vec<(String, u32)>,
+        points_lifetime: Option<i64>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_set_earn_rule_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            points_per_unit,
+            rounding,
+            minimum_purchase,
+            multipliers,
+            points_lifetime,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_earn_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+        currency: String,
+        purchase_amount: u64,
+        category: String,
+        timestamp: i64,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_earn_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            currency,
+            purchase_amount,
+            category,
+            timestamp,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    pub async fn send_expire_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+        currency: String,
+        timestamp: i64,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_expire_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            currency,
+            timestamp,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    pub async fn send_set_catalog_item_txn(
+        &self,
+        private_key: &str,
+        item_id: String,
+        description: String,
+        point_cost: u64,
+        stock: u64,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
         let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
         let (encoded_batches, batch_header_signature): (Vec<u8>, String) =
-            make_add_merchant_txn(&transaction_signer, &batch_signer, name, timestamp);
+            make_set_catalog_item_txn(
+                &transaction_signer,
+                &batch_signer,
+                self.family_version,
+                item_id,
+                description,
+                point_cost,
+                stock,
+            );
 
         info!("Sending encoded batches");
 
         self.send_and_wait(encoded_batches, batch_header_signature)
-            .await;
+            .await
     }
 
-    pub async fn send_and_wait<'a>(&self, batches: Vec<u8>, batch_id: String) {
-        self.client
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_redeem_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+        currency: String,
+        item_id: String,
+        quantity: u64,
+        points: u64,
+        timestamp: i64,
+        owner_signature: Option<OwnerSignature>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_redeem_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            currency,
+            item_id,
+            quantity,
+            points,
+            timestamp,
+            owner_signature,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_issue_voucher_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+        voucher_id: String,
+        discount: u64,
+        timestamp: i64,
+        expires_at: Option<i64>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_issue_voucher_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            voucher_id,
+            discount,
+            timestamp,
+            expires_at,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    pub async fn send_consume_voucher_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+        voucher_id: String,
+        timestamp: i64,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_consume_voucher_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            voucher_id,
+            timestamp,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    pub async fn send_freeze_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_freeze_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    pub async fn send_unfreeze_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_unfreeze_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    pub async fn send_close_txn(
+        &self,
+        private_key: &str,
+        name: String,
+        number: u32,
+        closure_reason: Option<String>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) = make_close_txn(
+            &transaction_signer,
+            &batch_signer,
+            self.family_version,
+            name,
+            number,
+            closure_reason,
+        );
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    pub async fn send_atomic_txn(
+        &self,
+        private_key: &str,
+        operations: Vec<Operation>,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let crypto_factory: CryptoFactory = CryptoFactory::new(&**self.context);
+
+        let secp_private_key = secp256k1::Secp256k1PrivateKey::from_hex(private_key)
+            .expect("Error generating secp256k1 private key from hex");
+        let transaction_signer: Signer = crypto_factory.new_signer(&secp_private_key);
+
+        let batch_signer: Signer = crypto_factory.new_signer(&**self.batch_private_key);
+        let (encoded_batches, batch_header_signature): (Vec<u8>, String) =
+            make_atomic_txn(&transaction_signer, &batch_signer, operations);
+
+        info!("Sending encoded batches");
+
+        self.send_and_wait(encoded_batches, batch_header_signature)
+            .await
+    }
+
+    /// Receipts of the batch transactions once committed
+    pub async fn send_and_wait<'a>(
+        &self,
+        batches: Vec<u8>,
+        batch_id: String,
+    ) -> Result<Vec<ReceiptPB>, SubmitError> {
+        let transaction_ids: Vec<String> = parse_from_bytes::<BatchList>(&batches)
+            .map_err(|err| SubmitError::Request(format!("Error parsing batches: {}", err)))?
+            .get_batches()
+            .iter()
+            .flat_map(|batch| batch.get_transactions())
+            .map(|transaction| String::from(transaction.get_header_signature()))
+            .collect();
+
+        match self.send_and_commit(batches, batch_id).await {
+            Ok(BatchStatus::Committed) => Ok(self.fetch_receipts(&transaction_ids).await),
+            Ok(status) => Err(SubmitError::Status(status)),
+            Err(err) => {
+                error!("{}", err);
+                Err(SubmitError::Request(err))
+            }
+        }
+    }
+
+    /// Submits the batches and waits for them to commit or be found invalid, for
+    /// transactions that attach no receipts
+    pub async fn send_and_commit(
+        &self,
+        batches: Vec<u8>,
+        batch_id: String,
+    ) -> Result<BatchStatus, String> {
+        let response = self
+            .client
             .post("http://localhost:8008/batches")
             .header("Content-Type", "application/octet-stream")
             .body(batches)
             .send()
             .await
-            .ok();
+            .map_err(|err| format!("Error submitting batches: {}", err))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Batches were rejected with status {}",
+                response.status()
+            ));
+        }
 
-        // let batch_id_copy: String = batch_id.clone();
+        self.wait_for_batch(&batch_id).await
+    }
 
+    /// Polls the status of a submitted batch until it leaves the pending state, a batch
+    /// still pending after the last poll is reported as such
+    pub async fn wait_for_batch(&self, batch_id: &str) -> Result<BatchStatus, String> {
         info!("Obtaining validator response");
 
-        let validator_response: Result<reqwest::Response, reqwest::Error> = self
+        for _ in 0..MAX_STATUS_POLLS {
+            let status = self.fetch_batch_status(batch_id).await?;
+            match &status {
+                BatchStatus::Committed => info!("Batch committed"),
+                BatchStatus::Invalid(message) => error!("{}", message),
+                BatchStatus::Pending => {
+                    info!("Batch is still pending");
+                    continue;
+                }
+                BatchStatus::Unknown => error!("Batch {} is unknown to the validator", batch_id),
+            }
+            return Ok(status);
+        }
+        Ok(BatchStatus::Pending)
+    }
+
+    async fn fetch_batch_status(&self, batch_id: &str) -> Result<BatchStatus, String> {
+        let response = self
             .client
-            .post("http://localhost:8008/batch_statuses")
-            .header("Content-Type", "application/octet-stream")
-            .body(batch_id)
+            .get("http://localhost:8008/batch_statuses")
+            .query(&[("id", batch_id), ("wait", &STATUS_WAIT.to_string())])
+            .send()
+            .await
+            .map_err(|err| format!("Error fetching batch status: {}", err))?;
+        let statuses: BatchStatusesResponse = response
+            .json()
+            .await
+            .map_err(|err| format!("Error reading batch status: {}", err))?;
+        parse_batch_status(statuses, batch_id)
+    }
+
+    /// Receipt data the processor attached to the transactions, in transaction order
+    pub async fn fetch_receipts(&self, transaction_ids: &[String]) -> Vec<ReceiptPB> {
+        let response = self
+            .client
+            .get("http://localhost:8008/receipts")
+            .query(&[("id", transaction_ids.join(","))])
             .send()
             .await;
 
-        let validator_response: String = validator_response
-            .ok()
-            .unwrap()
-            .text()
-            .await
-            .ok()
-            .expect("Error getting validator response content");
-
-        let status_response: ClientBatchStatusResponse =
-            parse_from_bytes(&validator_response.into_bytes())
-                .ok()
-                .expect("Error converting validator response into bytes");
-
-        let status: ClientBatchStatus_Status = status_response.batch_statuses[0].status;
-
-        match status {
-            ClientBatchStatus_Status::STATUS_UNSET => info!("Status has not yet been set"),
-            ClientBatchStatus_Status::COMMITTED => info!("Batch committed"),
-            ClientBatchStatus_Status::INVALID => {
-                let error: &ClientBatchStatus_InvalidTransaction =
-                    &status_response.batch_statuses[0].invalid_transactions[0];
-                error!("{:?}", error.message)
+        let receipts: ReceiptsResponse = match response {
+            Ok(response) => match response.json().await {
+                Ok(receipts) => receipts,
+                Err(err) => {
+                    error!("Error reading transaction receipts: {}", err);
+                    return Vec::new();
+                }
+            },
+            Err(err) => {
+                error!("Error fetching transaction receipts: {}", err);
+                return Vec::new();
             }
-            ClientBatchStatus_Status::PENDING => info!("Transaction submitted but timed out"),
-            ClientBatchStatus_Status::UNKNOWN => error!("Something went wrong, try again later"),
-        }
+        };
+
+        parse_receipts(receipts)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::*;
+    use protobuf::Message;
+
     #[test]
     fn test_key_pair_generation() {}
 
+    #[test]
+    fn test_parse_receipts() {
+        let mut receipt = ReceiptPB::new();
+        receipt.set_name(String::from("John Doe"));
+        receipt.set_number(12345);
+        receipt.set_balance(50);
+        receipt.set_lot_ids(vec![0]);
+        let data = base64::encode(receipt.write_to_bytes().unwrap());
+
+        let receipts = parse_receipts(ReceiptsResponse {
+            data: vec![TransactionReceiptJson {
+                data: vec![data, String::from("not base64")],
+            }],
+        });
+        assert_eq!(receipts, vec![receipt]);
+    }
+
+    fn make_batch_status(id: &str, status: &str, messages: &[&str]) -> BatchStatusJson {
+        BatchStatusJson {
+            id: String::from(id),
+            status: String::from(status),
+            invalid_transactions: messages
+                .iter()
+                .map(|message| InvalidTransactionJson {
+                    message: String::from(*message),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_batch_status() {
+        let parse = |status: BatchStatusJson| {
+            parse_batch_status(BatchStatusesResponse { data: vec![status] }, "batch")
+        };
+        assert_eq!(
+            parse(make_batch_status("batch", "COMMITTED", &[])),
+            Ok(BatchStatus::Committed)
+        );
+        assert_eq!(
+            parse(make_batch_status(
+                "batch",
+                "INVALID",
+                &["Insufficient balance"]
+            )),
+            Ok(BatchStatus::Invalid(String::from("Insufficient balance")))
+        );
+        assert_eq!(
+            parse(make_batch_status("batch", "PENDING", &[])),
+            Ok(BatchStatus::Pending)
+        );
+        assert_eq!(
+            parse(make_batch_status("batch", "UNKNOWN", &[])),
+            Ok(BatchStatus::Unknown)
+        );
+        assert!(parse(make_batch_status("batch", "LOST", &[])).is_err());
+        assert!(parse(make_batch_status("other", "COMMITTED", &[])).is_err());
+        assert!(parse_batch_status(BatchStatusesResponse { data: Vec::new() }, "batch").is_err());
+    }
+
     #[test]
     fn test_send_transaction() {}
 
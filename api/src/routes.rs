@@ -1,12 +1,21 @@
 use actix_web::{web, Error, HttpRequest, HttpResponse, Responder};
-use serde::Deserialize;
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
 use tracing::{error};
 
-use super::auth::{encrypt_private_key, hash_password, verify_password};
-use super::messenger::Messenger;
+use super::auth::{decrypt_private_key, encrypt_private_key, hash_password, verify_password};
+use super::messenger::{BatchStatus, Messenger, SubmitError};
+use super::services::{Operation, OwnerSignature};
 use super::AppData;
+use archer_protobuf::earn_rule::EarnRule_Rounding;
+use archer_protobuf::receipt::Receipt as ReceiptPB;
 use database::models::NewCredentials;
-use database::{fetch_auth, fetch_balance, insert_auth};
+use database::{
+    clear_pending_rotation, fetch_account_status, fetch_account_tier, fetch_auth, fetch_balance,
+    fetch_balances, fetch_catalog_items, fetch_expiring_lots, fetch_settlements, fetch_vouchers,
+    insert_auth, rotate_auth, set_pending_rotation, PgPooledConnection,
+};
 
 #[derive(Deserialize)]
 pub struct AccountData {
@@ -14,6 +23,22 @@ pub struct AccountData {
     number: u32,
 }
 
+#[derive(Deserialize)]
+pub struct CurrencyData {
+    name: String,
+    number: u32,
+    // Issuing merchant's public key, the shared pool when omitted
+    #[serde(default)]
+    currency: String,
+}
+
+#[derive(Deserialize)]
+pub struct AddAccountJson {
+    name: String,
+    number: u32,
+    owner_public_key: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct MerchantData {
     name: String,
@@ -26,11 +51,550 @@ pub struct AuthData {
     password: String,
 }
 
+/// Request signed with the stored key of the merchant or administrator it names
+#[derive(Deserialize)]
+pub struct SignedJson<T> {
+    #[serde(flatten)]
+    auth: AuthData,
+    #[serde(flatten)]
+    data: T,
+}
+
+impl<T> Deref for SignedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
 #[derive(Deserialize)]
 pub struct UpdateBalanceJson {
     name: String,
     number: u32,
-    amount: i32,
+    // Issuing merchant's public key, the shared pool when omitted
+    #[serde(default)]
+    currency: String,
+    amount: u64,
+    // Unix timestamp after which deposited points lapse
+    expires_at: Option<i64>,
+    owner_signature: Option<OwnerSignatureJson>,
+}
+
+#[derive(Deserialize)]
+pub struct OwnerSignatureJson {
+    timestamp: i64,
+    signature: String,
+}
+
+#[derive(Deserialize)]
+pub struct TransferJson {
+    name: String,
+    number: u32,
+    // Issuing merchant's public key, the shared pool when omitted
+    #[serde(default)]
+    currency: String,
+    recipient_name: String,
+    recipient_number: u32,
+    amount: u64,
+    owner_signature: Option<OwnerSignatureJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingJson {
+    Floor,
+    Ceil,
+    Nearest,
+}
+
+#[derive(Deserialize)]
+pub struct CategoryMultiplierJson {
+    category: String,
+    multiplier: u32,
+}
+
+#[derive(Deserialize)]
+pub struct EarnRuleJson {
+    points_per_unit: u32,
+    rounding: RoundingJson,
+    minimum_purchase: u64,
+    #[serde(default)]
+    multipliers: Vec<CategoryMultiplierJson>,
+    // Seconds before earned points lapse, never when omitted
+    points_lifetime: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct EarnJson {
+    name: String,
+    number: u32,
+    // Issuing merchant's public key, the shared pool when omitted
+    #[serde(default)]
+    currency: String,
+    purchase_amount: u64,
+    #[serde(default)]
+    category: String,
+}
+
+#[derive(Deserialize)]
+pub struct SetCatalogItemJson {
+    id: String,
+    description: String,
+    point_cost: u64,
+    stock: u64,
+}
+
+#[derive(Deserialize)]
+pub struct RedeemJson {
+    name: String,
+    number: u32,
+    // Issuing merchant's public key, the shared pool when omitted
+    #[serde(default)]
+    currency: String,
+    item_id: String,
+    #[serde(default = "default_quantity")]
+    quantity: u64,
+    // Total cost the owner agreed to, the redemption fails if the item was repriced
+    points: u64,
+    owner_signature: Option<OwnerSignatureJson>,
+}
+
+#[derive(Deserialize)]
+pub struct CatalogJson {
+    // Catalog of a single merchant, all of them when omitted
+    public_key: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CatalogItemJson {
+    public_key: String,
+    id: String,
+    description: String,
+    point_cost: i64,
+    stock: i64,
+}
+
+#[derive(Deserialize)]
+pub struct IssueVoucherJson {
+    name: String,
+    number: u32,
+    id: String,
+    // In cents
+    discount: u64,
+    expires_at: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct ConsumeVoucherJson {
+    name: String,
+    number: u32,
+    id: String,
+}
+
+#[derive(Deserialize)]
+pub struct CloseJson {
+    name: String,
+    number: u32,
+    // Required when the account still holds points, which are forfeited
+    closure_reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CurrencyBalanceJson {
+    currency: String,
+    balance: i64,
+}
+
+#[derive(Serialize)]
+pub struct AccountStatusJson {
+    status: String,
+    closure_reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct VoucherJson {
+    public_key: String,
+    id: String,
+    discount: i64,
+    issued_at: i64,
+    expires_at: Option<i64>,
+    consumed_at: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct SettlementReportJson {
+    // Unix timestamps, days starting in between are included, `until` excluded
+    from: i64,
+    until: i64,
+}
+
+/// Tier reached on `changed_at`, none when the earnings fell below every tier
+#[derive(Serialize)]
+pub struct TierChangeJson {
+    tier: Option<String>,
+    changed_at: i64,
+}
+
+#[derive(Serialize)]
+pub struct TierJson {
+    tier: Option<String>,
+    lifetime_earned: i64,
+    history: Vec<TierChangeJson>,
+}
+
+/// What the issuer owes the redeemer over the reported period
+#[derive(Serialize)]
+pub struct SettlementJson {
+    issuer: String,
+    redeemer: String,
+    amount: i64,
+}
+
+/// Receipts are only there once the transaction is committed
+#[derive(Serialize)]
+pub struct TransactionJson {
+    message: String,
+    receipts: Vec<ReceiptJson>,
+}
+
+#[derive(Serialize)]
+pub struct ReceiptJson {
+    name: String,
+    number: u32,
+    balance: u64,
+    lot_ids: Vec<u64>,
+    points: Option<u64>,
+}
+
+impl TransactionJson {
+    fn new(message: &str, receipts: Vec<ReceiptPB>) -> Self {
+        TransactionJson {
+            message: String::from(message),
+            receipts: receipts
+                .into_iter()
+                .map(|mut receipt| ReceiptJson {
+                    name: receipt.take_name(),
+                    number: receipt.get_number(),
+                    balance: receipt.get_balance(),
+                    lot_ids: receipt.take_lot_ids(),
+                    points: if receipt.has_points() {
+                        Some(receipt.get_points())
+                    } else {
+                        None
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Private key the caller signs with, once its password matches the stored credentials
+fn signer_private_key(app_data: &AppData, auth_data: &AuthData) -> Result<String, HttpResponse> {
+    let connection = app_data
+        .pool
+        .get()
+        .expect("Could not get connection from pool");
+
+    let public_key = auth_data.public_key.to_owned();
+    let auth = fetch_auth(public_key.clone(), &connection).map_err(|err| {
+        error!("{}", err);
+        HttpResponse::Unauthorized().body("No credentials for that public key")
+    })?;
+    let is_match = verify_password(auth_data.password.to_owned(), auth.hashed_password)
+        .expect("Error verifying password");
+    if !is_match {
+        return Err(HttpResponse::Unauthorized().body("Passwords did not match"));
+    }
+
+    Ok(decrypt_private_key(public_key, auth.encrypted_private_key))
+}
+
+/// Response to a submission, carrying the receipts once the transactions committed
+fn transaction_response(
+    message: &str,
+    result: Result<Vec<ReceiptPB>, SubmitError>,
+) -> HttpResponse {
+    match result {
+        Ok(receipts) => HttpResponse::Ok().json(TransactionJson::new(message, receipts)),
+        Err(err) => submit_error_response(err),
+    }
+}
+
+/// Invalid transactions are the caller's to fix, anything else is on the validator
+fn submit_error_response(err: SubmitError) -> HttpResponse {
+    match err {
+        SubmitError::Status(BatchStatus::Invalid(message)) => {
+            HttpResponse::BadRequest().json(message)
+        }
+        SubmitError::Status(BatchStatus::Pending) => {
+            HttpResponse::GatewayTimeout().json("Transaction is still pending")
+        }
+        SubmitError::Status(_) => {
+            HttpResponse::ServiceUnavailable().json("Transaction was dropped by the validator")
+        }
+        SubmitError::Request(_) => {
+            HttpResponse::ServiceUnavailable().json("Validator could not be reached")
+        }
+    }
+}
+
+fn default_quantity() -> u64 {
+    1
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum OperationJson {
+    Deposit {
+        name: String,
+        number: u32,
+        #[serde(default)]
+        currency: String,
+        amount: u64,
+        expires_at: Option<i64>,
+    },
+    Withdraw {
+        name: String,
+        number: u32,
+        #[serde(default)]
+        currency: String,
+        amount: u64,
+        owner_signature: Option<OwnerSignatureJson>,
+    },
+    Transfer {
+        name: String,
+        number: u32,
+        #[serde(default)]
+        currency: String,
+        recipient_name: String,
+        recipient_number: u32,
+        amount: u64,
+        owner_signature: Option<OwnerSignatureJson>,
+    },
+    Earn {
+        name: String,
+        number: u32,
+        #[serde(default)]
+        currency: String,
+        purchase_amount: u64,
+        #[serde(default)]
+        category: String,
+    },
+    Expire {
+        name: String,
+        number: u32,
+        #[serde(default)]
+        currency: String,
+    },
+    Redeem {
+        name: String,
+        number: u32,
+        #[serde(default)]
+        currency: String,
+        item_id: String,
+        #[serde(default = "default_quantity")]
+        quantity: u64,
+        points: u64,
+        owner_signature: Option<OwnerSignatureJson>,
+    },
+    #[serde(rename = "issue_voucher")]
+    IssueVoucher {
+        name: String,
+        number: u32,
+        id: String,
+        discount: u64,
+        expires_at: Option<i64>,
+    },
+    #[serde(rename = "consume_voucher")]
+    ConsumeVoucher {
+        name: String,
+        number: u32,
+        id: String,
+    },
+    Freeze {
+        name: String,
+        number: u32,
+    },
+    Unfreeze {
+        name: String,
+        number: u32,
+    },
+    Close {
+        name: String,
+        number: u32,
+        closure_reason: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct AtomicJson {
+    operations: Vec<OperationJson>,
+}
+
+#[derive(Deserialize)]
+pub struct ExpiringJson {
+    name: String,
+    number: u32,
+    // Window from now, up to MAX_EXPIRING_DAYS
+    days: i64,
+}
+
+const MAX_EXPIRING_DAYS: i64 = 3650;
+
+#[derive(Serialize)]
+pub struct ExpiringLotJson {
+    lot_id: i64,
+    currency: String,
+    amount: i64,
+    expires_at: i64,
+}
+
+impl OwnerSignatureJson {
+    fn to_owner_signature(&self) -> OwnerSignature {
+        OwnerSignature {
+            timestamp: self.timestamp,
+            signature: self.signature.to_owned(),
+        }
+    }
+}
+
+impl OperationJson {
+    fn to_operation(&self, timestamp: i64) -> Operation {
+        match self {
+            OperationJson::Deposit {
+                name,
+                number,
+                currency,
+                amount,
+                expires_at,
+            } => Operation::Deposit {
+                name: name.to_owned(),
+                number: *number,
+                currency: currency.to_owned(),
+                amount: *amount,
+                timestamp,
+                expires_at: *expires_at,
+            },
+            OperationJson::Withdraw {
+                name,
+                number,
+                currency,
+                amount,
+                owner_signature,
+            } => Operation::Withdraw {
+                name: name.to_owned(),
+                number: *number,
+                currency: currency.to_owned(),
+                amount: *amount,
+                timestamp,
+                owner_signature: owner_signature
+                    .as_ref()
+                    .map(OwnerSignatureJson::to_owner_signature),
+            },
+            OperationJson::Transfer {
+                name,
+                number,
+                currency,
+                recipient_name,
+                recipient_number,
+                amount,
+                owner_signature,
+            } => Operation::Transfer {
+                name: name.to_owned(),
+                number: *number,
+                currency: currency.to_owned(),
+                recipient_name: recipient_name.to_owned(),
+                recipient_number: *recipient_number,
+                amount: *amount,
+                timestamp,
+                owner_signature: owner_signature
+                    .as_ref()
+                    .map(OwnerSignatureJson::to_owner_signature),
+            },
+            OperationJson::Earn {
+                name,
+                number,
+                currency,
+                purchase_amount,
+                category,
+            } => Operation::Earn {
+                name: name.to_owned(),
+                number: *number,
+                currency: currency.to_owned(),
+                purchase_amount: *purchase_amount,
+                category: category.to_owned(),
+                timestamp,
+            },
+            OperationJson::Expire {
+                name,
+                number,
+                currency,
+            } => Operation::Expire {
+                name: name.to_owned(),
+                number: *number,
+                currency: currency.to_owned(),
+                timestamp,
+            },
+            OperationJson::Redeem {
+                name,
+                number,
+                currency,
+                item_id,
+                quantity,
+                points,
+                owner_signature,
+            } => Operation::Redeem {
+                name: name.to_owned(),
+                number: *number,
+                currency: currency.to_owned(),
+                item_id: item_id.to_owned(),
+                quantity: *quantity,
+                points: *points,
+                timestamp,
+                owner_signature: owner_signature
+                    .as_ref()
+                    .map(OwnerSignatureJson::to_owner_signature),
+            },
+            OperationJson::IssueVoucher {
+                name,
+                number,
+                id,
+                discount,
+                expires_at,
+            } => Operation::IssueVoucher {
+                name: name.to_owned(),
+                number: *number,
+                voucher_id: id.to_owned(),
+                discount: *discount,
+                timestamp,
+                expires_at: *expires_at,
+            },
+            OperationJson::ConsumeVoucher { name, number, id } => Operation::ConsumeVoucher {
+                name: name.to_owned(),
+                number: *number,
+                voucher_id: id.to_owned(),
+                timestamp,
+            },
+            OperationJson::Freeze { name, number } => Operation::Freeze {
+                name: name.to_owned(),
+                number: *number,
+            },
+            OperationJson::Unfreeze { name, number } => Operation::Unfreeze {
+                name: name.to_owned(),
+                number: *number,
+            },
+            OperationJson::Close {
+                name,
+                number,
+                closure_reason,
+            } => Operation::Close {
+                name: name.to_owned(),
+                number: *number,
+                closure_reason: closure_reason.to_owned(),
+            },
+        }
+    }
 }
 
 // TODO attribute ID to every request
@@ -63,72 +627,420 @@ pub async fn authenticate(
 }
 
 pub async fn deposit(
-    _app_data: web::Data<AppData>,
-    account_data: web::Json<UpdateBalanceJson>,
+    app_data: web::Data<AppData>,
+    account_data: web::Json<SignedJson<UpdateBalanceJson>>,
 ) -> impl Responder {
-    let messenger: Messenger = Messenger::new("secp256k1");
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
 
-    // TODO change to auth
-    let (_public_key, private_key): (String, String) = messenger.get_new_key_pair();
+    let private_key = match signer_private_key(&app_data, &account_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
 
-    messenger
+    let result = messenger
         .send_deposit_txn(
             &private_key,
             account_data.name.to_owned(),
             account_data.number,
+            account_data.currency.to_owned(),
             account_data.amount,
+            chrono::offset::Utc::now().timestamp(),
+            account_data.expires_at,
         )
         .await;
 
-    HttpResponse::Ok().json("Deposit transaction submitted to validator")
+    transaction_response("Deposit transaction submitted to validator", result)
 }
 
 pub async fn withdraw(
-    _app_data: web::Data<AppData>,
-    account_data: web::Json<UpdateBalanceJson>,
+    app_data: web::Data<AppData>,
+    account_data: web::Json<SignedJson<UpdateBalanceJson>>,
 ) -> impl Responder {
-    let messenger: Messenger = Messenger::new("secp256k1");
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
 
-    // TODO change to auth
-    let (_public_key, private_key): (String, String) = messenger.get_new_key_pair();
+    let private_key = match signer_private_key(&app_data, &account_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
 
-    messenger
+    let result = messenger
         .send_withdraw_txn(
             &private_key,
             account_data.name.to_owned(),
             account_data.number,
+            account_data.currency.to_owned(),
             account_data.amount,
+            chrono::offset::Utc::now().timestamp(),
+            account_data
+                .owner_signature
+                .as_ref()
+                .map(OwnerSignatureJson::to_owner_signature),
         )
         .await;
 
-    HttpResponse::Ok().json("Withdraw transaction submitted to validator")
+    transaction_response("Withdraw transaction submitted to validator", result)
 }
 
-pub async fn add_account(
-    _app_data: web::Data<AppData>,
-    account_data: web::Json<AccountData>,
+pub async fn transfer(
+    app_data: web::Data<AppData>,
+    transfer_data: web::Json<SignedJson<TransferJson>>,
 ) -> impl Responder {
-    let messenger: Messenger = Messenger::new("secp256k1");
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
 
-    // TODO change to auth
-    let (_public_key, private_key): (String, String) = messenger.get_new_key_pair();
+    let private_key = match signer_private_key(&app_data, &transfer_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
 
-    messenger
-        .send_add_account_txn(
+    let result = messenger
+        .send_transfer_txn(
+            &private_key,
+            transfer_data.name.to_owned(),
+            transfer_data.number,
+            transfer_data.currency.to_owned(),
+            transfer_data.recipient_name.to_owned(),
+            transfer_data.recipient_number,
+            transfer_data.amount,
+            chrono::offset::Utc::now().timestamp(),
+            transfer_data
+                .owner_signature
+                .as_ref()
+                .map(OwnerSignatureJson::to_owner_signature),
+        )
+        .await;
+
+    transaction_response("Transfer transaction submitted to validator", result)
+}
+
+pub async fn set_earn_rule(
+    app_data: web::Data<AppData>,
+    rule_data: web::Json<SignedJson<EarnRuleJson>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &rule_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    let rounding = match rule_data.rounding {
+        RoundingJson::Floor => EarnRule_Rounding::FLOOR,
+        RoundingJson::Ceil => EarnRule_Rounding::CEIL,
+        RoundingJson::Nearest => EarnRule_Rounding::NEAREST,
+    };
+    let multipliers = rule_data
+        .multipliers
+        .iter()
+        .map(|entry| (entry.category.to_owned(), entry.multiplier))
+        .collect();
+
+    match messenger
+        .send_set_earn_rule_txn(
+            &private_key,
+            rule_data.points_per_unit,
+            rounding,
+            rule_data.minimum_purchase,
+            multipliers,
+            rule_data.points_lifetime,
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json("Earn rule transaction submitted to validator"),
+        Err(err) => submit_error_response(err),
+    }
+}
+
+pub async fn earn(
+    app_data: web::Data<AppData>,
+    earn_data: web::Json<SignedJson<EarnJson>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &earn_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    let result = messenger
+        .send_earn_txn(
+            &private_key,
+            earn_data.name.to_owned(),
+            earn_data.number,
+            earn_data.currency.to_owned(),
+            earn_data.purchase_amount,
+            earn_data.category.to_owned(),
+            chrono::offset::Utc::now().timestamp(),
+        )
+        .await;
+
+    transaction_response("Earn transaction submitted to validator", result)
+}
+
+pub async fn set_catalog_item(
+    app_data: web::Data<AppData>,
+    item_data: web::Json<SignedJson<SetCatalogItemJson>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &item_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    match messenger
+        .send_set_catalog_item_txn(
+            &private_key,
+            item_data.id.to_owned(),
+            item_data.description.to_owned(),
+            item_data.point_cost,
+            item_data.stock,
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json("Catalog item transaction submitted to validator"),
+        Err(err) => submit_error_response(err),
+    }
+}
+
+pub async fn redeem(
+    app_data: web::Data<AppData>,
+    redeem_data: web::Json<SignedJson<RedeemJson>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &redeem_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    let result = messenger
+        .send_redeem_txn(
+            &private_key,
+            redeem_data.name.to_owned(),
+            redeem_data.number,
+            redeem_data.currency.to_owned(),
+            redeem_data.item_id.to_owned(),
+            redeem_data.quantity,
+            redeem_data.points,
+            chrono::offset::Utc::now().timestamp(),
+            redeem_data
+                .owner_signature
+                .as_ref()
+                .map(OwnerSignatureJson::to_owner_signature),
+        )
+        .await;
+
+    transaction_response("Redeem transaction submitted to validator", result)
+}
+
+pub async fn issue_voucher(
+    app_data: web::Data<AppData>,
+    voucher_data: web::Json<SignedJson<IssueVoucherJson>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &voucher_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    match messenger
+        .send_issue_voucher_txn(
+            &private_key,
+            voucher_data.name.to_owned(),
+            voucher_data.number,
+            voucher_data.id.to_owned(),
+            voucher_data.discount,
+            chrono::offset::Utc::now().timestamp(),
+            voucher_data.expires_at,
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json("Issue voucher transaction submitted to validator"),
+        Err(err) => submit_error_response(err),
+    }
+}
+
+pub async fn consume_voucher(
+    app_data: web::Data<AppData>,
+    voucher_data: web::Json<SignedJson<ConsumeVoucherJson>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &voucher_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    match messenger
+        .send_consume_voucher_txn(
+            &private_key,
+            voucher_data.name.to_owned(),
+            voucher_data.number,
+            voucher_data.id.to_owned(),
+            chrono::offset::Utc::now().timestamp(),
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json("Consume voucher transaction submitted to validator"),
+        Err(err) => submit_error_response(err),
+    }
+}
+
+pub async fn atomic(
+    app_data: web::Data<AppData>,
+    atomic_data: web::Json<SignedJson<AtomicJson>>,
+) -> impl Responder {
+    if atomic_data.operations.is_empty() {
+        return HttpResponse::BadRequest().json("At least one operation is required");
+    }
+
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &atomic_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    let timestamp = chrono::offset::Utc::now().timestamp();
+    let operations = atomic_data
+        .operations
+        .iter()
+        .map(|operation| operation.to_operation(timestamp))
+        .collect();
+
+    let result = messenger.send_atomic_txn(&private_key, operations).await;
+
+    transaction_response("Atomic transaction submitted to validator", result)
+}
+
+pub async fn expire(
+    app_data: web::Data<AppData>,
+    account_data: web::Json<SignedJson<CurrencyData>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &account_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    let result = messenger
+        .send_expire_txn(
             &private_key,
             account_data.name.to_owned(),
             account_data.number,
+            account_data.currency.to_owned(),
+            chrono::offset::Utc::now().timestamp(),
         )
         .await;
 
-    HttpResponse::Ok().json("Add account transaction submitted to validator")
+    transaction_response("Expire transaction submitted to validator", result)
+}
+
+pub async fn freeze(
+    app_data: web::Data<AppData>,
+    account_data: web::Json<SignedJson<AccountData>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &account_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    match messenger
+        .send_freeze_txn(
+            &private_key,
+            account_data.name.to_owned(),
+            account_data.number,
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json("Freeze transaction submitted to validator"),
+        Err(err) => submit_error_response(err),
+    }
+}
+
+pub async fn unfreeze(
+    app_data: web::Data<AppData>,
+    account_data: web::Json<SignedJson<AccountData>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &account_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    match messenger
+        .send_unfreeze_txn(
+            &private_key,
+            account_data.name.to_owned(),
+            account_data.number,
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json("Unfreeze transaction submitted to validator"),
+        Err(err) => submit_error_response(err),
+    }
+}
+
+pub async fn close(
+    app_data: web::Data<AppData>,
+    close_data: web::Json<SignedJson<CloseJson>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &close_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    match messenger
+        .send_close_txn(
+            &private_key,
+            close_data.name.to_owned(),
+            close_data.number,
+            close_data.closure_reason.to_owned(),
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json("Close transaction submitted to validator"),
+        Err(err) => submit_error_response(err),
+    }
+}
+
+pub async fn add_account(
+    app_data: web::Data<AppData>,
+    account_data: web::Json<SignedJson<AddAccountJson>>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let private_key = match signer_private_key(&app_data, &account_data.auth) {
+        Ok(private_key) => private_key,
+        Err(response) => return response,
+    };
+
+    match messenger
+        .send_add_account_txn(
+            &private_key,
+            account_data.name.to_owned(),
+            account_data.number,
+            account_data.owner_public_key.to_owned(),
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json("Add account transaction submitted to validator"),
+        Err(err) => submit_error_response(err),
+    }
 }
 
 pub async fn add_merchant(
     app_data: web::Data<AppData>,
     merchant_data: web::Json<MerchantData>,
 ) -> impl Responder {
-    let messenger: Messenger = Messenger::new("secp256k1");
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
 
     let pool = &app_data.pool;
 
@@ -137,13 +1049,16 @@ pub async fn add_merchant(
     let (public_key, private_key): (String, String) = messenger.get_new_key_pair();
 
     let date_time = chrono::offset::Utc::now();
-    messenger
+    if let Err(err) = messenger
         .send_add_merchant_txn(
             &private_key,
             merchant_data.name.to_owned(),
             date_time.timestamp(),
         )
-        .await;
+        .await
+    {
+        return submit_error_response(err);
+    }
 
     let hashed_password =
         hash_password(merchant_data.password.to_owned()).expect("Could not hash password");
@@ -162,9 +1077,131 @@ pub async fn add_merchant(
     HttpResponse::Ok().json("Add merchant transaction submitted to validator")
 }
 
+/// Rotates the merchant onto a fresh key pair, the transaction is signed with the old
+/// key and the stored private key is replaced once it commits. The new key pair is
+/// stored before the rotation is submitted, so a rotation whose outcome was not known
+/// yet is finished or dropped by calling again.
+pub async fn rotate_merchant_key(
+    app_data: web::Data<AppData>,
+    auth_data: web::Json<AuthData>,
+) -> impl Responder {
+    let messenger: Messenger = Messenger::new("secp256k1", app_data.family_version);
+
+    let pool = &app_data.pool;
+
+    let connection = pool.get().expect("Could not get connection from pool");
+
+    let public_key = auth_data.public_key.to_owned();
+    let auth = match fetch_auth(public_key.clone(), &*connection) {
+        Ok(auth) => auth,
+        Err(err) => {
+            error!("{}", err);
+            return HttpResponse::Unauthorized().body("No credentials for that public key");
+        }
+    };
+    let is_match = verify_password(auth_data.password.to_owned(), auth.hashed_password.clone())
+        .expect("Error verifying password");
+    if !is_match {
+        return HttpResponse::Unauthorized().body("Passwords did not match");
+    }
+
+    if let (Some(pending_public_key), Some(pending_private_key), Some(batch_id)) = (
+        auth.pending_public_key.clone(),
+        auth.pending_private_key.clone(),
+        auth.pending_batch_id.clone(),
+    ) {
+        match messenger.wait_for_batch(&batch_id).await {
+            Ok(BatchStatus::Committed) => {
+                return finish_rotation(
+                    public_key,
+                    &pending_public_key,
+                    &auth.hashed_password,
+                    &pending_private_key,
+                    &connection,
+                )
+            }
+            Ok(BatchStatus::Pending) => {
+                return HttpResponse::Accepted().json("Key rotation is still pending");
+            }
+            // The earlier batch will never commit, so a new rotation takes its place
+            Ok(BatchStatus::Invalid(_)) | Ok(BatchStatus::Unknown) => {
+                if let Err(err) = clear_pending_rotation(public_key.clone(), &*connection) {
+                    error!("{}", err);
+                    return HttpResponse::InternalServerError().finish();
+                }
+            }
+            Err(err) => {
+                error!("{}", err);
+                return HttpResponse::ServiceUnavailable().json("Key rotation status is unknown");
+            }
+        }
+    }
+
+    let private_key = decrypt_private_key(public_key.clone(), auth.encrypted_private_key);
+    let (new_public_key, new_private_key): (String, String) = messenger.get_new_key_pair();
+    let (batches, batch_id) =
+        messenger.make_rotate_merchant_key_batches(&private_key, new_public_key.clone());
+
+    let encrypted_private_key = encrypt_private_key(new_public_key.clone(), new_private_key);
+    if let Err(err) = set_pending_rotation(
+        public_key.clone(),
+        &new_public_key,
+        &encrypted_private_key,
+        &batch_id,
+        &*connection,
+    ) {
+        error!("{}", err);
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    // The old key keeps signing for the merchant until the rotation commits
+    match messenger.send_and_commit(batches, batch_id).await {
+        Ok(BatchStatus::Committed) => finish_rotation(
+            public_key,
+            &new_public_key,
+            &auth.hashed_password,
+            &encrypted_private_key,
+            &connection,
+        ),
+        Ok(BatchStatus::Invalid(_)) => {
+            if let Err(err) = clear_pending_rotation(public_key, &*connection) {
+                error!("{}", err);
+            }
+            HttpResponse::BadRequest().json("Key rotation was not committed")
+        }
+        Ok(_) => HttpResponse::Accepted().json("Key rotation is still pending"),
+        Err(err) => {
+            error!("{}", err);
+            HttpResponse::Accepted().json("Key rotation is still pending")
+        }
+    }
+}
+
+/// Moves the credentials over to the key the merchant was rotated to
+fn finish_rotation(
+    public_key: String,
+    new_public_key: &str,
+    hashed_password: &str,
+    encrypted_private_key: &str,
+    connection: &PgPooledConnection,
+) -> HttpResponse {
+    let credentials = NewCredentials {
+        public_key: new_public_key,
+        hashed_password,
+        encrypted_private_key,
+    };
+    match rotate_auth(public_key, credentials, connection) {
+        Ok(_) => HttpResponse::Ok().json(new_public_key),
+        Err(err) => {
+            error!("{}", err);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
 pub async fn get_balance(
     app_data: web::Data<AppData>,
-    account_data: web::Json<AccountData>,
+    account_data: web::Json<CurrencyData>,
 ) -> Result<HttpResponse, Error> {
     // TODO add authorization GUARD
     // (don't allow just anyone to reach this route, but the private key does not need to be used)
@@ -174,8 +1211,9 @@ pub async fn get_balance(
 
     let name = account_data.name.clone();
     let number = account_data.number;
+    let currency = account_data.currency.clone();
 
-    let balance = web::block(move || fetch_balance(name, number, &*connection))
+    let balance = web::block(move || fetch_balance(name, number, currency, &*connection))
         .await
         .map_err(|err| {
             error!("{}", err);
@@ -194,6 +1232,295 @@ pub async fn get_balance(
     }
 }
 
+pub async fn get_balances(
+    app_data: web::Data<AppData>,
+    account_data: web::Json<AccountData>,
+) -> Result<HttpResponse, Error> {
+    let pool = &app_data.pool;
+
+    let connection = pool.get().expect("Could not get connection from pool");
+
+    let name = account_data.name.clone();
+    let number = account_data.number;
+
+    let balances = web::block(move || fetch_balances(name, number, &*connection))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+            HttpResponse::InternalServerError().finish()
+        });
+
+    match balances {
+        Ok(balances) if !balances.is_empty() => {
+            let balances: Vec<CurrencyBalanceJson> = balances
+                .into_iter()
+                .map(|(currency, balance)| CurrencyBalanceJson { currency, balance })
+                .collect();
+            Ok(HttpResponse::Ok().json(balances))
+        }
+        _ => {
+            let res = HttpResponse::NotFound().body(format!(
+                "No account found with name and number: {}, {}",
+                account_data.name, account_data.number
+            ));
+            Ok(res)
+        }
+    }
+}
+
+pub async fn get_account_status(
+    app_data: web::Data<AppData>,
+    account_data: web::Json<AccountData>,
+) -> Result<HttpResponse, Error> {
+    let pool = &app_data.pool;
+
+    let connection = pool.get().expect("Could not get connection from pool");
+
+    let name = account_data.name.clone();
+    let number = account_data.number;
+
+    let status = web::block(move || fetch_account_status(name, number, &*connection))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+            HttpResponse::InternalServerError().finish()
+        });
+
+    match status {
+        Ok((status, closure_reason)) => Ok(HttpResponse::Ok().json(AccountStatusJson {
+            status,
+            closure_reason,
+        })),
+        Err(_) => {
+            let res = HttpResponse::NotFound().body(format!(
+                "No account found with name and number: {}, {}",
+                account_data.name, account_data.number
+            ));
+            Ok(res)
+        }
+    }
+}
+
+pub async fn get_tier(
+    app_data: web::Data<AppData>,
+    account_data: web::Json<AccountData>,
+) -> Result<HttpResponse, Error> {
+    let pool = &app_data.pool;
+
+    let connection = pool.get().expect("Could not get connection from pool");
+
+    let name = account_data.name.clone();
+    let number = account_data.number;
+
+    let tier = web::block(move || fetch_account_tier(name, number, &*connection))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+            HttpResponse::InternalServerError().finish()
+        });
+
+    match tier {
+        Ok((tier, lifetime_earned, history)) => Ok(HttpResponse::Ok().json(TierJson {
+            tier,
+            lifetime_earned,
+            history: history
+                .into_iter()
+                .map(|change| TierChangeJson {
+                    tier: change.tier,
+                    changed_at: Utc.from_utc_datetime(&change.changed_at).timestamp(),
+                })
+                .collect(),
+        })),
+        Err(_) => {
+            let res = HttpResponse::NotFound().body(format!(
+                "No account found with name and number: {}, {}",
+                account_data.name, account_data.number
+            ));
+            Ok(res)
+        }
+    }
+}
+
+pub async fn get_expiring(
+    app_data: web::Data<AppData>,
+    expiring_data: web::Json<ExpiringJson>,
+) -> Result<HttpResponse, Error> {
+    let pool = &app_data.pool;
+
+    let connection = pool.get().expect("Could not get connection from pool");
+
+    let name = expiring_data.name.clone();
+    let number = expiring_data.number;
+    if !(0..=MAX_EXPIRING_DAYS).contains(&expiring_data.days) {
+        return Ok(HttpResponse::BadRequest()
+            .json(format!("Days must be between 0 and {}", MAX_EXPIRING_DAYS)));
+    }
+    let from = chrono::offset::Utc::now().naive_utc();
+    let until = match from.checked_add_signed(chrono::Duration::days(expiring_data.days)) {
+        Some(until) => until,
+        None => return Ok(HttpResponse::BadRequest().json("Invalid expiry window")),
+    };
+
+    let lots = web::block(move || fetch_expiring_lots(name, number, from, until, &*connection))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+            HttpResponse::InternalServerError().finish()
+        });
+
+    match lots {
+        Ok(lots) => {
+            let lots: Vec<ExpiringLotJson> = lots
+                .into_iter()
+                .filter_map(|lot| {
+                    lot.expires_at.map(|expires_at| ExpiringLotJson {
+                        lot_id: lot.lot_id,
+                        currency: lot.currency,
+                        amount: lot.amount,
+                        expires_at: Utc.from_utc_datetime(&expires_at).timestamp(),
+                    })
+                })
+                .collect();
+            Ok(HttpResponse::Ok().json(lots))
+        }
+        Err(_) => Ok(HttpResponse::InternalServerError().finish()),
+    }
+}
+
+pub async fn get_catalog(
+    app_data: web::Data<AppData>,
+    catalog_data: web::Json<CatalogJson>,
+) -> Result<HttpResponse, Error> {
+    let pool = &app_data.pool;
+
+    let connection = pool.get().expect("Could not get connection from pool");
+
+    let public_key = catalog_data.public_key.clone();
+
+    let items = web::block(move || fetch_catalog_items(public_key, &*connection))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+            HttpResponse::InternalServerError().finish()
+        });
+
+    match items {
+        Ok(items) => {
+            let items: Vec<CatalogItemJson> = items
+                .into_iter()
+                .map(|item| CatalogItemJson {
+                    public_key: item.public_key,
+                    id: item.item_id,
+                    description: item.description,
+                    point_cost: item.point_cost,
+                    stock: item.stock,
+                })
+                .collect();
+            Ok(HttpResponse::Ok().json(items))
+        }
+        Err(_) => Ok(HttpResponse::InternalServerError().finish()),
+    }
+}
+
+pub async fn get_vouchers(
+    app_data: web::Data<AppData>,
+    account_data: web::Json<AccountData>,
+) -> Result<HttpResponse, Error> {
+    let pool = &app_data.pool;
+
+    let connection = pool.get().expect("Could not get connection from pool");
+
+    let name = account_data.name.clone();
+    let number = account_data.number;
+
+    let vouchers = web::block(move || fetch_vouchers(name, number, &*connection))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+            HttpResponse::InternalServerError().finish()
+        });
+
+    match vouchers {
+        Ok(vouchers) => {
+            let vouchers: Vec<VoucherJson> = vouchers
+                .into_iter()
+                .map(|voucher| VoucherJson {
+                    public_key: voucher.public_key,
+                    id: voucher.voucher_id,
+                    discount: voucher.discount,
+                    issued_at: Utc.from_utc_datetime(&voucher.issued_at).timestamp(),
+                    expires_at: voucher
+                        .expires_at
+                        .map(|expires_at| Utc.from_utc_datetime(&expires_at).timestamp()),
+                    consumed_at: voucher
+                        .consumed_at
+                        .map(|consumed_at| Utc.from_utc_datetime(&consumed_at).timestamp()),
+                })
+                .collect();
+            Ok(HttpResponse::Ok().json(vouchers))
+        }
+        Err(_) => Ok(HttpResponse::InternalServerError().finish()),
+    }
+}
+
+pub async fn get_settlements(
+    app_data: web::Data<AppData>,
+    report_data: web::Json<SettlementReportJson>,
+) -> Result<HttpResponse, Error> {
+    let pool = &app_data.pool;
+
+    let connection = pool.get().expect("Could not get connection from pool");
+
+    let (from, until) = match (
+        Utc.timestamp_opt(report_data.from, 0).single(),
+        Utc.timestamp_opt(report_data.until, 0).single(),
+    ) {
+        (Some(from), Some(until)) => (from.naive_utc(), until.naive_utc()),
+        _ => return Ok(HttpResponse::BadRequest().json("Invalid settlement period")),
+    };
+
+    let settlements = web::block(move || fetch_settlements(from, until, &*connection))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+            HttpResponse::InternalServerError().finish()
+        });
+
+    match settlements {
+        Ok(settlements) => {
+            // Rows come ordered by issuer and redeemer, one per day
+            let mut report: Vec<SettlementJson> = Vec::new();
+            for settlement in settlements {
+                match report.last_mut() {
+                    Some(last)
+                        if last.issuer == settlement.issuer
+                            && last.redeemer == settlement.redeemer =>
+                    {
+                        last.amount = match last.amount.checked_add(settlement.amount) {
+                            Some(amount) => amount,
+                            None => {
+                                error!(
+                                    "Settlement total of {} to {} overflowed",
+                                    last.issuer, last.redeemer
+                                );
+                                return Ok(HttpResponse::InternalServerError()
+                                    .json("Settlement total overflowed"));
+                            }
+                        };
+                    }
+                    _ => report.push(SettlementJson {
+                        issuer: settlement.issuer,
+                        redeemer: settlement.redeemer,
+                        amount: settlement.amount,
+                    }),
+                }
+            }
+            Ok(HttpResponse::Ok().json(report))
+        }
+        Err(_) => Ok(HttpResponse::InternalServerError().finish()),
+    }
+}
+
 pub async fn health_check(_request: HttpRequest) -> impl Responder {
     HttpResponse::Ok()
 }
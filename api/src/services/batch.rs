@@ -3,6 +3,8 @@ use sawtooth_sdk::messages::batch::{Batch, BatchHeader};
 use sawtooth_sdk::messages::transaction::Transaction;
 use sawtooth_sdk::signing::Signer;
 
+use archer::FamilyVersion;
+
 use super::transaction;
 
 pub fn make_batch<'a>(
@@ -11,10 +13,17 @@ pub fn make_batch<'a>(
     outputs: Vec<String>,
     txn_signer: &'a Signer,
     batch_signer: &'a Signer,
+    family_version: FamilyVersion,
 ) -> Batch {
     // create txn header
-    let txn_header =
-        transaction::make_header(&payload_bytes, inputs, outputs, txn_signer, batch_signer);
+    let txn_header = transaction::make_header(
+        &payload_bytes,
+        inputs,
+        outputs,
+        txn_signer,
+        batch_signer,
+        family_version,
+    );
     let txn_header_bytes = txn_header
         .write_to_bytes()
         .expect("Error converting transaction header to bytes");
@@ -3,34 +3,423 @@ use protobuf::{Message, RepeatedField};
 use sawtooth_sdk::messages::batch::{Batch, BatchList};
 use sawtooth_sdk::signing::Signer;
 
-use archer::{calculate_account_address, calculate_merchant_address};
-use archer_protobuf::payload::{Payload as PayloadPB, Payload_Action};
+use archer::addresses::{
+    add_account_addresses, add_merchant_addresses, consume_voucher_addresses, deposit_addresses,
+    earn_addresses, expire_addresses, issue_voucher_addresses, redeem_addresses,
+    rotate_merchant_key_addresses, set_catalog_item_addresses, set_earn_rule_addresses,
+    status_addresses, transaction_addresses, transfer_addresses, withdraw_addresses, Addresses,
+};
+use archer::FamilyVersion;
+use archer_protobuf::catalog::CatalogItem as CatalogItemPB;
+use archer_protobuf::earn_rule::{
+    EarnRule as EarnRulePB, EarnRule_CategoryMultiplier, EarnRule_Rounding,
+};
+use archer_protobuf::payload::{Payload as PayloadPB, PayloadEnvelope, Payload_Action};
 
 pub mod batch;
 pub mod transaction;
 
+/// Owner co-signature over `archer::make_owner_authorization`, needed when the
+/// account owner is not the one signing the transaction
+pub struct OwnerSignature {
+    pub timestamp: i64,
+    pub signature: String,
+}
+
+/// Operation on an account, several can be combined with `make_atomic_txn`. Debits are
+/// dated so points of other merchants settle in the right period, an owner signature
+/// brings its own timestamp. Balance operations name the currency they move, the
+/// issuing merchant's public key, or an empty string for the shared pool.
+pub enum Operation {
+    Deposit {
+        name: String,
+        number: u32,
+        currency: String,
+        amount: u64,
+        timestamp: i64,
+        expires_at: Option<i64>,
+    },
+    Withdraw {
+        name: String,
+        number: u32,
+        currency: String,
+        amount: u64,
+        timestamp: i64,
+        owner_signature: Option<OwnerSignature>,
+    },
+    Transfer {
+        name: String,
+        number: u32,
+        currency: String,
+        recipient_name: String,
+        recipient_number: u32,
+        amount: u64,
+        timestamp: i64,
+        owner_signature: Option<OwnerSignature>,
+    },
+    Earn {
+        name: String,
+        number: u32,
+        currency: String,
+        purchase_amount: u64,
+        category: String,
+        timestamp: i64,
+    },
+    Expire {
+        name: String,
+        number: u32,
+        currency: String,
+        timestamp: i64,
+    },
+    /// Spends `points`, the total cost the owner agreed to, on a catalog item of the signer
+    Redeem {
+        name: String,
+        number: u32,
+        currency: String,
+        item_id: String,
+        quantity: u64,
+        points: u64,
+        timestamp: i64,
+        owner_signature: Option<OwnerSignature>,
+    },
+    /// Single-use voucher of the signer, bound to the account
+    IssueVoucher {
+        name: String,
+        number: u32,
+        voucher_id: String,
+        discount: u64,
+        timestamp: i64,
+        expires_at: Option<i64>,
+    },
+    ConsumeVoucher {
+        name: String,
+        number: u32,
+        voucher_id: String,
+        timestamp: i64,
+    },
+    Freeze {
+        name: String,
+        number: u32,
+    },
+    Unfreeze {
+        name: String,
+        number: u32,
+    },
+    /// Any points left are forfeited, which requires a reason
+    Close {
+        name: String,
+        number: u32,
+        closure_reason: Option<String>,
+    },
+}
+
+impl Operation {
+    /// Payload for the operation along with its input and output addresses
+    fn into_payload(self, signer_public_key: &str) -> (PayloadPB, Addresses) {
+        let mut payload_pb = PayloadPB::new();
+        match self {
+            Operation::Deposit {
+                name,
+                number,
+                currency,
+                amount,
+                timestamp,
+                expires_at,
+            } => {
+                let addresses = deposit_addresses(signer_public_key, &name);
+                payload_pb.set_name(name);
+                payload_pb.set_number(number);
+                set_currency(&mut payload_pb, currency);
+                payload_pb.set_amount(amount);
+                payload_pb.set_timestamp(timestamp);
+                if let Some(expires_at) = expires_at {
+                    payload_pb.set_expires_at(expires_at);
+                }
+                payload_pb.set_action(Payload_Action::DEPOSIT);
+                (payload_pb, addresses)
+            }
+            Operation::Withdraw {
+                name,
+                number,
+                currency,
+                amount,
+                timestamp,
+                owner_signature,
+            } => {
+                let addresses = withdraw_addresses(signer_public_key, &name);
+                payload_pb.set_name(name);
+                payload_pb.set_number(number);
+                set_currency(&mut payload_pb, currency);
+                payload_pb.set_amount(amount);
+                payload_pb.set_timestamp(timestamp);
+                set_owner_signature(&mut payload_pb, owner_signature);
+                payload_pb.set_action(Payload_Action::WITHDRAW);
+                (payload_pb, addresses)
+            }
+            Operation::Transfer {
+                name,
+                number,
+                currency,
+                recipient_name,
+                recipient_number,
+                amount,
+                timestamp,
+                owner_signature,
+            } => {
+                let addresses = transfer_addresses(signer_public_key, &name, &recipient_name);
+                payload_pb.set_name(name);
+                payload_pb.set_number(number);
+                set_currency(&mut payload_pb, currency);
+                payload_pb.set_recipient_name(recipient_name);
+                payload_pb.set_recipient_number(recipient_number);
+                payload_pb.set_amount(amount);
+                payload_pb.set_timestamp(timestamp);
+                set_owner_signature(&mut payload_pb, owner_signature);
+                payload_pb.set_action(Payload_Action::TRANSFER);
+                (payload_pb, addresses)
+            }
+            Operation::Earn {
+                name,
+                number,
+                currency,
+                purchase_amount,
+                category,
+                timestamp,
+            } => {
+                let addresses = earn_addresses(signer_public_key, &name);
+                payload_pb.set_name(name);
+                payload_pb.set_number(number);
+                set_currency(&mut payload_pb, currency);
+                payload_pb.set_purchase_amount(purchase_amount);
+                payload_pb.set_category(category);
+                payload_pb.set_timestamp(timestamp);
+                payload_pb.set_action(Payload_Action::EARN);
+                (payload_pb, addresses)
+            }
+            Operation::Expire {
+                name,
+                number,
+                currency,
+                timestamp,
+            } => {
+                let addresses = expire_addresses(signer_public_key, &name);
+                payload_pb.set_name(name);
+                payload_pb.set_number(number);
+                set_currency(&mut payload_pb, currency);
+                payload_pb.set_timestamp(timestamp);
+                payload_pb.set_action(Payload_Action::EXPIRE);
+                (payload_pb, addresses)
+            }
+            Operation::Redeem {
+                name,
+                number,
+                currency,
+                item_id,
+                quantity,
+                points,
+                timestamp,
+                owner_signature,
+            } => {
+                let addresses = redeem_addresses(signer_public_key, &name);
+                payload_pb.set_name(name);
+                payload_pb.set_number(number);
+                set_currency(&mut payload_pb, currency);
+                payload_pb.set_item_id(item_id);
+                payload_pb.set_quantity(quantity);
+                payload_pb.set_amount(points);
+                payload_pb.set_timestamp(timestamp);
+                set_owner_signature(&mut payload_pb, owner_signature);
+                payload_pb.set_action(Payload_Action::REDEEM);
+                (payload_pb, addresses)
+            }
+            Operation::IssueVoucher {
+                name,
+                number,
+                voucher_id,
+                discount,
+                timestamp,
+                expires_at,
+            } => {
+                let addresses = issue_voucher_addresses(signer_public_key, &name, &voucher_id);
+                payload_pb.set_name(name);
+                payload_pb.set_number(number);
+                payload_pb.set_voucher_id(voucher_id);
+                payload_pb.set_discount(discount);
+                payload_pb.set_timestamp(timestamp);
+                if let Some(expires_at) = expires_at {
+                    payload_pb.set_expires_at(expires_at);
+                }
+                payload_pb.set_action(Payload_Action::ISSUE_VOUCHER);
+                (payload_pb, addresses)
+            }
+            Operation::ConsumeVoucher {
+                name,
+                number,
+                voucher_id,
+                timestamp,
+            } => {
+                let addresses = consume_voucher_addresses(signer_public_key, &name);
+                payload_pb.set_name(name);
+                payload_pb.set_number(number);
+                payload_pb.set_voucher_id(voucher_id);
+                payload_pb.set_timestamp(timestamp);
+                payload_pb.set_action(Payload_Action::CONSUME_VOUCHER);
+                (payload_pb, addresses)
+            }
+            Operation::Freeze { name, number } => {
+                make_status_payload(payload_pb, name, number, Payload_Action::FREEZE)
+            }
+            Operation::Unfreeze { name, number } => {
+                make_status_payload(payload_pb, name, number, Payload_Action::UNFREEZE)
+            }
+            Operation::Close {
+                name,
+                number,
+                closure_reason,
+            } => {
+                if let Some(closure_reason) = closure_reason {
+                    payload_pb.set_closure_reason(closure_reason);
+                }
+                make_status_payload(payload_pb, name, number, Payload_Action::CLOSE)
+            }
+        }
+    }
+}
+
+fn make_status_payload(
+    mut payload_pb: PayloadPB,
+    name: String,
+    number: u32,
+    action: Payload_Action,
+) -> (PayloadPB, Addresses) {
+    let addresses = status_addresses(&name);
+    payload_pb.set_name(name);
+    payload_pb.set_number(number);
+    payload_pb.set_action(action);
+    (payload_pb, addresses)
+}
+
+/// Points of the shared pool carry no currency
+fn set_currency(payload_pb: &mut PayloadPB, currency: String) {
+    if !currency.is_empty() {
+        payload_pb.set_currency(currency);
+    }
+}
+
+fn set_owner_signature(payload_pb: &mut PayloadPB, owner_signature: Option<OwnerSignature>) {
+    if let Some(owner_signature) = owner_signature {
+        payload_pb.set_timestamp(owner_signature.timestamp);
+        payload_pb.set_owner_signature(owner_signature.signature);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn make_deposit_txn<'a>(
     txn_signer: &'a Signer,
     batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+    currency: String,
+    amount: u64,
+    timestamp: i64,
+    expires_at: Option<i64>,
+) -> (Vec<u8>, String) {
+    let operation = Operation::Deposit {
+        name,
+        number,
+        currency,
+        amount,
+        timestamp,
+        expires_at,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn make_withdraw_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+    currency: String,
+    amount: u64,
+    timestamp: i64,
+    owner_signature: Option<OwnerSignature>,
+) -> (Vec<u8>, String) {
+    let operation = Operation::Withdraw {
+        name,
+        number,
+        currency,
+        amount,
+        timestamp,
+        owner_signature,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn make_transfer_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
     name: String,
     number: u32,
-    amount: i32,
+    currency: String,
+    recipient_name: String,
+    recipient_number: u32,
+    amount: u64,
+    timestamp: i64,
+    owner_signature: Option<OwnerSignature>,
 ) -> (Vec<u8>, String) {
-    let address = calculate_account_address(&name);
+    let operation = Operation::Transfer {
+        name,
+        number,
+        currency,
+        recipient_name,
+        recipient_number,
+        amount,
+        timestamp,
+        owner_signature,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+pub fn make_add_account_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+    owner_public_key: Option<String>,
+) -> (Vec<u8>, String) {
+    // The processor only accepts these from signers registered as merchants
+    let public_key = txn_signer
+        .get_public_key()
+        .expect("Could not get public key from transaction signer")
+        .as_hex();
+    let (inputs, outputs) =
+        transaction_addresses(&public_key, vec![add_account_addresses(&public_key, &name)]);
 
     let mut payload_pb = PayloadPB::new();
     payload_pb.set_name(name);
     payload_pb.set_number(number);
-    payload_pb.set_amount(amount);
-    payload_pb.set_action(Payload_Action::DEPOSIT);
+    if let Some(owner_public_key) = owner_public_key {
+        payload_pb.set_public_key(owner_public_key);
+    }
+    payload_pb.set_action(Payload_Action::ADD_ACCOUNT);
 
-    let payload_bytes = payload_pb
-        .write_to_bytes()
-        .expect("Error converting protobuf payload to bytes");
-    let inputs = vec![String::from(&address)];
-    let outputs = vec![String::from(&address)];
+    let payload_bytes = encode_payloads(vec![payload_pb], family_version);
 
-    let batch = batch::make_batch(&payload_bytes, inputs, outputs, txn_signer, batch_signer);
+    let batch = batch::make_batch(
+        &payload_bytes,
+        inputs,
+        outputs,
+        txn_signer,
+        batch_signer,
+        family_version,
+    );
 
     let batch_header_signature = batch.get_header_signature();
 
@@ -39,28 +428,35 @@ pub fn make_deposit_txn<'a>(
     (encoded_batches, batch_header_signature.to_string())
 }
 
-pub fn make_withdraw_txn<'a>(
+pub fn make_add_merchant_txn<'a>(
     txn_signer: &'a Signer,
     batch_signer: &'a Signer,
+    family_version: FamilyVersion,
     name: String,
-    number: u32,
-    amount: i32,
+    timestamp: i64,
 ) -> (Vec<u8>, String) {
-    let address = calculate_account_address(&name);
+    let public_key = txn_signer
+        .get_public_key()
+        .expect("Could not get public key from transaction signer")
+        .as_hex();
+    let (inputs, outputs) =
+        transaction_addresses(&public_key, vec![add_merchant_addresses(&public_key)]);
 
     let mut payload_pb = PayloadPB::new();
     payload_pb.set_name(name);
-    payload_pb.set_number(number);
-    payload_pb.set_amount(amount);
-    payload_pb.set_action(Payload_Action::WITHDRAW);
+    payload_pb.set_timestamp(timestamp);
+    payload_pb.set_action(Payload_Action::ADD_MERCHANT);
 
-    let payload_bytes = payload_pb
-        .write_to_bytes()
-        .expect("Error converting protobuf payload to bytes");
-    let inputs = vec![String::from(&address)];
-    let outputs = vec![String::from(&address)];
+    let payload_bytes = encode_payloads(vec![payload_pb], family_version);
 
-    let batch = batch::make_batch(&payload_bytes, inputs, outputs, txn_signer, batch_signer);
+    let batch = batch::make_batch(
+        &payload_bytes,
+        inputs,
+        outputs,
+        txn_signer,
+        batch_signer,
+        family_version,
+    );
 
     let batch_header_signature = batch.get_header_signature();
 
@@ -69,26 +465,38 @@ pub fn make_withdraw_txn<'a>(
     (encoded_batches, batch_header_signature.to_string())
 }
 
-pub fn make_add_account_txn<'a>(
+/// Signed by the merchant's current key, the merchant and its earn rule move to the
+/// addresses of the new one
+pub fn make_rotate_merchant_key_txn<'a>(
     txn_signer: &'a Signer,
     batch_signer: &'a Signer,
-    name: String,
-    number: u32,
+    family_version: FamilyVersion,
+    new_public_key: String,
 ) -> (Vec<u8>, String) {
-    let address = calculate_account_address(&name);
+    let public_key = txn_signer
+        .get_public_key()
+        .expect("Could not get public key from transaction signer")
+        .as_hex();
+    let (inputs, outputs) = transaction_addresses(
+        &public_key,
+        vec![rotate_merchant_key_addresses(&public_key, &new_public_key)],
+    );
 
     let mut payload_pb = PayloadPB::new();
-    payload_pb.set_name(name);
-    payload_pb.set_number(number);
-    payload_pb.set_action(Payload_Action::ADD_ACCOUNT);
+    payload_pb.set_name(String::new());
+    payload_pb.set_public_key(new_public_key);
+    payload_pb.set_action(Payload_Action::ROTATE_MERCHANT_KEY);
 
-    let payload_bytes = payload_pb
-        .write_to_bytes()
-        .expect("Error converting protobuf payload to bytes");
-    let inputs = vec![String::from(&address)];
-    let outputs = vec![String::from(&address)];
+    let payload_bytes = encode_payloads(vec![payload_pb], family_version);
 
-    let batch = batch::make_batch(&payload_bytes, inputs, outputs, txn_signer, batch_signer);
+    let batch = batch::make_batch(
+        &payload_bytes,
+        inputs,
+        outputs,
+        txn_signer,
+        batch_signer,
+        family_version,
+    );
 
     let batch_header_signature = batch.get_header_signature();
 
@@ -97,31 +505,297 @@ pub fn make_add_account_txn<'a>(
     (encoded_batches, batch_header_signature.to_string())
 }
 
-pub fn make_add_merchant_txn<'a>(
+#[allow(clippy::too_many_arguments)]
+pub fn make_set_earn_rule_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    points_per_unit: u32,
+    rounding: EarnRule_Rounding,
+    minimum_purchase: u64,
+    multipliers: Vec<(String, u32)>,
+    points_lifetime: Option<i64>,
+) -> (Vec<u8>, String) {
+    let public_key = txn_signer
+        .get_public_key()
+        .expect("Could not get public key from transaction signer")
+        .as_hex();
+    // The processor only accepts these from signers registered as merchants
+    let (inputs, outputs) =
+        transaction_addresses(&public_key, vec![set_earn_rule_addresses(&public_key)]);
+
+    let mut earn_rule = EarnRulePB::new();
+    earn_rule.set_public_key(String::from(&public_key));
+    earn_rule.set_points_per_unit(points_per_unit);
+    earn_rule.set_rounding(rounding);
+    earn_rule.set_minimum_purchase(minimum_purchase);
+    for (category, multiplier) in multipliers {
+        let mut category_multiplier = EarnRule_CategoryMultiplier::new();
+        category_multiplier.set_category(category);
+        category_multiplier.set_multiplier(multiplier);
+        earn_rule.mut_multipliers().push(category_multiplier);
+    }
+    if let Some(points_lifetime) = points_lifetime {
+        earn_rule.set_points_lifetime(points_lifetime);
+    }
+
+    let mut payload_pb = PayloadPB::new();
+    payload_pb.set_name(String::new());
+    payload_pb.set_earn_rule(earn_rule);
+    payload_pb.set_action(Payload_Action::SET_EARN_RULE);
+
+    let payload_bytes = encode_payloads(vec![payload_pb], family_version);
+
+    let batch = batch::make_batch(
+        &payload_bytes,
+        inputs,
+        outputs,
+        txn_signer,
+        batch_signer,
+        family_version,
+    );
+
+    let batch_header_signature = batch.get_header_signature();
+
+    let encoded_batches: Vec<u8> = encode_batches(batch.clone());
+
+    (encoded_batches, batch_header_signature.to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn make_earn_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+    currency: String,
+    purchase_amount: u64,
+    category: String,
+    timestamp: i64,
+) -> (Vec<u8>, String) {
+    let operation = Operation::Earn {
+        name,
+        number,
+        currency,
+        purchase_amount,
+        category,
+        timestamp,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+pub fn make_expire_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+    currency: String,
+    timestamp: i64,
+) -> (Vec<u8>, String) {
+    let operation = Operation::Expire {
+        name,
+        number,
+        currency,
+        timestamp,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn make_redeem_txn<'a>(
     txn_signer: &'a Signer,
     batch_signer: &'a Signer,
+    family_version: FamilyVersion,
     name: String,
+    number: u32,
+    currency: String,
+    item_id: String,
+    quantity: u64,
+    points: u64,
     timestamp: i64,
+    owner_signature: Option<OwnerSignature>,
+) -> (Vec<u8>, String) {
+    let operation = Operation::Redeem {
+        name,
+        number,
+        currency,
+        item_id,
+        quantity,
+        points,
+        timestamp,
+        owner_signature,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn make_issue_voucher_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+    voucher_id: String,
+    discount: u64,
+    timestamp: i64,
+    expires_at: Option<i64>,
+) -> (Vec<u8>, String) {
+    let operation = Operation::IssueVoucher {
+        name,
+        number,
+        voucher_id,
+        discount,
+        timestamp,
+        expires_at,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+pub fn make_consume_voucher_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+    voucher_id: String,
+    timestamp: i64,
+) -> (Vec<u8>, String) {
+    let operation = Operation::ConsumeVoucher {
+        name,
+        number,
+        voucher_id,
+        timestamp,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+pub fn make_freeze_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+) -> (Vec<u8>, String) {
+    let operation = Operation::Freeze { name, number };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+pub fn make_unfreeze_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+) -> (Vec<u8>, String) {
+    let operation = Operation::Unfreeze { name, number };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+pub fn make_close_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    name: String,
+    number: u32,
+    closure_reason: Option<String>,
+) -> (Vec<u8>, String) {
+    let operation = Operation::Close {
+        name,
+        number,
+        closure_reason,
+    };
+    make_operations_txn(txn_signer, batch_signer, family_version, vec![operation])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn make_set_catalog_item_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    item_id: String,
+    description: String,
+    point_cost: u64,
+    stock: u64,
 ) -> (Vec<u8>, String) {
-    let address = calculate_merchant_address(
-        &txn_signer
-            .get_public_key()
-            .expect("Could not get public key from transaction signer")
-            .as_hex(),
+    let public_key = txn_signer
+        .get_public_key()
+        .expect("Could not get public key from transaction signer")
+        .as_hex();
+    // The processor only accepts these from signers registered as merchants
+    let (inputs, outputs) = transaction_addresses(
+        &public_key,
+        vec![set_catalog_item_addresses(&public_key, &item_id)],
     );
 
+    let mut catalog_item = CatalogItemPB::new();
+    catalog_item.set_public_key(String::from(&public_key));
+    catalog_item.set_id(item_id);
+    catalog_item.set_description(description);
+    catalog_item.set_point_cost(point_cost);
+    catalog_item.set_stock(stock);
+
     let mut payload_pb = PayloadPB::new();
-    payload_pb.set_name(name);
-    payload_pb.set_timestamp(timestamp);
-    payload_pb.set_action(Payload_Action::ADD_MERCHANT);
+    payload_pb.set_name(String::new());
+    payload_pb.set_catalog_item(catalog_item);
+    payload_pb.set_action(Payload_Action::SET_CATALOG_ITEM);
 
-    let payload_bytes = payload_pb
-        .write_to_bytes()
-        .expect("Error converting protobuf payload to bytes");
-    let inputs = vec![String::from(&address)];
-    let outputs = vec![String::from(&address)];
+    let payload_bytes = encode_payloads(vec![payload_pb], family_version);
+
+    let batch = batch::make_batch(
+        &payload_bytes,
+        inputs,
+        outputs,
+        txn_signer,
+        batch_signer,
+        family_version,
+    );
+
+    let batch_header_signature = batch.get_header_signature();
+
+    let encoded_batches: Vec<u8> = encode_batches(batch.clone());
+
+    (encoded_batches, batch_header_signature.to_string())
+}
+
+/// Applies the operations in order within a single transaction, if one fails
+/// none of them take effect. Only family version 2.0 can carry several.
+pub fn make_atomic_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    operations: Vec<Operation>,
+) -> (Vec<u8>, String) {
+    make_operations_txn(txn_signer, batch_signer, FamilyVersion::V2, operations)
+}
+
+fn make_operations_txn<'a>(
+    txn_signer: &'a Signer,
+    batch_signer: &'a Signer,
+    family_version: FamilyVersion,
+    operations: Vec<Operation>,
+) -> (Vec<u8>, String) {
+    let signer_public_key = txn_signer
+        .get_public_key()
+        .expect("Could not get public key from transaction signer")
+        .as_hex();
+
+    let (payloads, addresses): (Vec<PayloadPB>, Vec<Addresses>) = operations
+        .into_iter()
+        .map(|operation| operation.into_payload(&signer_public_key))
+        .unzip();
+    let (inputs, outputs) = transaction_addresses(&signer_public_key, addresses);
 
-    let batch = batch::make_batch(&payload_bytes, inputs, outputs, txn_signer, batch_signer);
+    let payload_bytes = encode_payloads(payloads, family_version);
+
+    let batch = batch::make_batch(
+        &payload_bytes,
+        inputs,
+        outputs,
+        txn_signer,
+        batch_signer,
+        family_version,
+    );
 
     let batch_header_signature = batch.get_header_signature();
 
@@ -130,6 +804,21 @@ pub fn make_add_merchant_txn<'a>(
     (encoded_batches, batch_header_signature.to_string())
 }
 
+fn encode_payloads(payloads: Vec<PayloadPB>, family_version: FamilyVersion) -> Vec<u8> {
+    match family_version {
+        FamilyVersion::V1 => match payloads.as_slice() {
+            [payload_pb] => payload_pb.write_to_bytes(),
+            _ => panic!("Family version 1.0 carries a single operation"),
+        },
+        FamilyVersion::V2 => {
+            let mut envelope = PayloadEnvelope::new();
+            envelope.set_payloads(RepeatedField::from_vec(payloads));
+            envelope.write_to_bytes()
+        }
+    }
+    .expect("Error converting protobuf payload to bytes")
+}
+
 fn encode_batches<'a>(batch: Batch) -> Vec<u8> {
     let mut batch_list = BatchList::new();
     batch_list.set_batches(RepeatedField::from_vec(vec![batch]));
@@ -137,3 +826,185 @@ fn encode_batches<'a>(batch: Batch) -> Vec<u8> {
         .write_to_bytes()
         .expect("Error converting batch list to bytes")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archer::addresses::{settlement_namespace, type_namespace};
+    use archer::{
+        calculate_account_address, calculate_earn_rule_address, calculate_merchant_address,
+        calculate_setting_address, ArcherTypes, TIERS_SETTING, VELOCITY_LIMITS_SETTING,
+    };
+    use protobuf::parse_from_bytes;
+    use sawtooth_sdk::messages::transaction::TransactionHeader;
+    use sawtooth_sdk::signing::{create_context, CryptoFactory};
+
+    #[test]
+    fn transfer_txn_declares_both_accounts_and_the_merchant() {
+        let context = create_context("secp256k1").unwrap();
+        let private_key = context.new_random_private_key().unwrap();
+        let crypto_factory = CryptoFactory::new(&*context);
+        let signer = crypto_factory.new_signer(&*private_key);
+
+        let (encoded_batches, _) = make_transfer_txn(
+            &signer,
+            &signer,
+            FamilyVersion::V2,
+            String::from("John Doe"),
+            1,
+            String::new(),
+            String::from("Jane Doe"),
+            2,
+            100,
+            10000,
+            None,
+        );
+
+        let batch_list: BatchList = parse_from_bytes(&encoded_batches).unwrap();
+        let transaction = &batch_list.get_batches()[0].get_transactions()[0];
+        let header: TransactionHeader = parse_from_bytes(transaction.get_header()).unwrap();
+        let expected = vec![
+            calculate_account_address("John Doe"),
+            calculate_account_address("Jane Doe"),
+            calculate_merchant_address(&signer.get_public_key().unwrap().as_hex()),
+        ];
+        assert_eq!(header.get_outputs().to_vec(), expected);
+        let mut expected = expected;
+        expected.push(calculate_setting_address(VELOCITY_LIMITS_SETTING));
+        assert_eq!(header.get_inputs().to_vec(), expected);
+    }
+
+    #[test]
+    fn rotation_txn_declares_both_merchants_and_earn_rules() {
+        let context = create_context("secp256k1").unwrap();
+        let private_key = context.new_random_private_key().unwrap();
+        let crypto_factory = CryptoFactory::new(&*context);
+        let signer = crypto_factory.new_signer(&*private_key);
+        let new_private_key = context.new_random_private_key().unwrap();
+        let new_public_key = context.get_public_key(&*new_private_key).unwrap().as_hex();
+
+        let (encoded_batches, _) = make_rotate_merchant_key_txn(
+            &signer,
+            &signer,
+            FamilyVersion::V2,
+            new_public_key.clone(),
+        );
+
+        let batch_list: BatchList = parse_from_bytes(&encoded_batches).unwrap();
+        let transaction = &batch_list.get_batches()[0].get_transactions()[0];
+        let header: TransactionHeader = parse_from_bytes(transaction.get_header()).unwrap();
+        let public_key = signer.get_public_key().unwrap().as_hex();
+        let expected = vec![
+            calculate_merchant_address(&public_key),
+            calculate_merchant_address(&new_public_key),
+            calculate_earn_rule_address(&public_key),
+            calculate_earn_rule_address(&new_public_key),
+        ];
+        assert_eq!(header.get_inputs().to_vec(), expected);
+        assert_eq!(header.get_outputs().to_vec(), expected);
+    }
+
+    #[test]
+    fn txn_uses_configured_family_version() {
+        let context = create_context("secp256k1").unwrap();
+        let private_key = context.new_random_private_key().unwrap();
+        let crypto_factory = CryptoFactory::new(&*context);
+        let signer = crypto_factory.new_signer(&*private_key);
+
+        for family_version in FamilyVersion::ALL.iter() {
+            let (encoded_batches, _) = make_expire_txn(
+                &signer,
+                &signer,
+                *family_version,
+                String::from("John Doe"),
+                1,
+                String::new(),
+                10000,
+            );
+
+            let batch_list: BatchList = parse_from_bytes(&encoded_batches).unwrap();
+            let transaction = &batch_list.get_batches()[0].get_transactions()[0];
+            let header: TransactionHeader = parse_from_bytes(transaction.get_header()).unwrap();
+            assert_eq!(header.get_family_version(), family_version.as_str());
+
+            let payload: PayloadPB = match family_version {
+                FamilyVersion::V1 => parse_from_bytes(transaction.get_payload()).unwrap(),
+                FamilyVersion::V2 => {
+                    let mut envelope: PayloadEnvelope =
+                        parse_from_bytes(transaction.get_payload()).unwrap();
+                    envelope.take_payloads().pop().unwrap()
+                }
+            };
+            assert_eq!(payload.get_action(), Payload_Action::EXPIRE);
+            assert_eq!(payload.get_name(), "John Doe");
+        }
+    }
+
+    #[test]
+    fn atomic_txn_declares_union_of_addresses() {
+        let context = create_context("secp256k1").unwrap();
+        let private_key = context.new_random_private_key().unwrap();
+        let crypto_factory = CryptoFactory::new(&*context);
+        let signer = crypto_factory.new_signer(&*private_key);
+        let public_key = signer.get_public_key().unwrap().as_hex();
+
+        let (encoded_batches, _) = make_atomic_txn(
+            &signer,
+            &signer,
+            vec![
+                Operation::Withdraw {
+                    name: String::from("John Doe"),
+                    number: 1,
+                    currency: String::new(),
+                    amount: 500,
+                    timestamp: 10000,
+                    owner_signature: None,
+                },
+                Operation::Earn {
+                    name: String::from("John Doe"),
+                    number: 1,
+                    currency: String::new(),
+                    purchase_amount: 2500,
+                    category: String::from("food"),
+                    timestamp: 10000,
+                },
+            ],
+        );
+
+        let batch_list: BatchList = parse_from_bytes(&encoded_batches).unwrap();
+        let transaction = &batch_list.get_batches()[0].get_transactions()[0];
+        let header: TransactionHeader = parse_from_bytes(transaction.get_header()).unwrap();
+        assert_eq!(header.get_family_version(), "2.0");
+        assert_eq!(
+            header.get_inputs().to_vec(),
+            vec![
+                calculate_account_address("John Doe"),
+                calculate_merchant_address(&public_key),
+                settlement_namespace(),
+                calculate_setting_address(VELOCITY_LIMITS_SETTING),
+                type_namespace(&ArcherTypes::Merchant),
+                calculate_earn_rule_address(&public_key),
+                calculate_setting_address(TIERS_SETTING),
+            ]
+        );
+        assert_eq!(
+            header.get_outputs().to_vec(),
+            vec![
+                calculate_account_address("John Doe"),
+                calculate_merchant_address(&public_key),
+                settlement_namespace()
+            ]
+        );
+
+        let envelope: PayloadEnvelope = parse_from_bytes(transaction.get_payload()).unwrap();
+        let actions: Vec<Payload_Action> = envelope
+            .get_payloads()
+            .iter()
+            .map(|payload| payload.get_action())
+            .collect();
+        assert_eq!(
+            actions,
+            vec![Payload_Action::WITHDRAW, Payload_Action::EARN]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use rand::{thread_rng, Rng};
 use sawtooth_sdk::messages::transaction::{Transaction, TransactionHeader};
 use sawtooth_sdk::signing::Signer;
 
-use archer::to_hex_string;
+use archer::{to_hex_string, FamilyVersion};
 
 pub fn make_transaction(
     payload_bytes: &Vec<u8>,
@@ -24,6 +24,7 @@ pub fn make_header(
     outputs: Vec<String>,
     txn_signer: &Signer,
     batch_signer: &Signer,
+    family_version: FamilyVersion,
 ) -> TransactionHeader {
     let mut header = TransactionHeader::new();
     let mut nonce = [0u8; 16];
@@ -33,7 +34,7 @@ pub fn make_header(
         .expect("Error generating random nonce");
 
     header.set_family_name(String::from("archer"));
-    header.set_family_version(String::from("1.0"));
+    header.set_family_version(String::from(family_version.as_str()));
 
     header.set_nonce(to_hex_string(&nonce.to_vec()));
     header.set_inputs(RepeatedField::from_vec(inputs));
@@ -24,6 +24,7 @@ pub struct DatabaseSettings {
 pub struct ApplicationSettings {
     pub port: u16,
     pub host: String,
+    pub family_version: String,
 }
 
 #[derive(Deserialize)]
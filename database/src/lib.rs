@@ -11,6 +11,7 @@ use diesel::r2d2::{ConnectionManager, Pool, PoolError, PooledConnection};
 use diesel::result::QueryResult;
 use dotenv::dotenv;
 use archer_config::get_configuration;
+use chrono::NaiveDateTime;
 
 pub mod models;
 pub mod schema;
@@ -61,6 +62,60 @@ pub fn drop_fork(eval_block_num: i64, connection: &PgConnection) -> QueryResult<
     diesel::update(accounts.filter(end_block_num.nullable().eq(eval_block_num)))
         .set(end_block_num.eq(None::<i64>))
         .execute(connection)?;
+    {
+        use schema::account_lots::dsl::*;
+
+        diesel::delete(account_lots.filter(start_block_num.nullable().eq(eval_block_num)))
+            .execute(connection)?;
+        diesel::update(account_lots.filter(end_block_num.nullable().eq(eval_block_num)))
+            .set(end_block_num.eq(None::<i64>))
+            .execute(connection)?;
+    }
+    {
+        use schema::merchants::dsl::*;
+
+        diesel::delete(merchants.filter(start_block_num.nullable().eq(eval_block_num)))
+            .execute(connection)?;
+        diesel::update(merchants.filter(end_block_num.nullable().eq(eval_block_num)))
+            .set(end_block_num.eq(None::<i64>))
+            .execute(connection)?;
+    }
+    {
+        use schema::catalog_items::dsl::*;
+
+        diesel::delete(catalog_items.filter(start_block_num.nullable().eq(eval_block_num)))
+            .execute(connection)?;
+        diesel::update(catalog_items.filter(end_block_num.nullable().eq(eval_block_num)))
+            .set(end_block_num.eq(None::<i64>))
+            .execute(connection)?;
+    }
+    {
+        use schema::vouchers::dsl::*;
+
+        diesel::delete(vouchers.filter(start_block_num.nullable().eq(eval_block_num)))
+            .execute(connection)?;
+        diesel::update(vouchers.filter(end_block_num.nullable().eq(eval_block_num)))
+            .set(end_block_num.eq(None::<i64>))
+            .execute(connection)?;
+    }
+    {
+        use schema::settlements::dsl::*;
+
+        diesel::delete(settlements.filter(start_block_num.nullable().eq(eval_block_num)))
+            .execute(connection)?;
+        diesel::update(settlements.filter(end_block_num.nullable().eq(eval_block_num)))
+            .set(end_block_num.eq(None::<i64>))
+            .execute(connection)?;
+    }
+    {
+        use schema::account_tiers::dsl::*;
+
+        diesel::delete(account_tiers.filter(start_block_num.nullable().eq(eval_block_num)))
+            .execute(connection)?;
+        diesel::update(account_tiers.filter(end_block_num.nullable().eq(eval_block_num)))
+            .set(end_block_num.eq(None::<i64>))
+            .execute(connection)?;
+    }
     diesel::delete(blocks.filter(block_num.ge(eval_block_num))).execute(connection)
 }
 
@@ -106,50 +161,377 @@ pub fn fetch_block(
         .optional()
 }
 
+/// Balance in a currency, the empty currency being the shared pool
 pub fn fetch_balance(
     account_name: String,
     account_number: u32,
+    account_currency: String,
     connection: &PgConnection,
-) -> QueryResult<i32> {
+) -> QueryResult<i64> {
     use schema::accounts::dsl::*;
 
     let account_number =
         i32::try_from(account_number).expect("Error converting account number from u32 to i32");
 
     accounts
-        .filter(name.eq(account_name).and(number.eq(account_number)))
+        .filter(
+            name.eq(account_name)
+                .and(number.eq(account_number))
+                .and(currency.eq(account_currency))
+                .and(end_block_num.eq(i64::MAX)),
+        )
         .select(balance)
         .first(connection)
 }
 
+/// Balance in each currency the account holds, the shared pool first
+pub fn fetch_balances(
+    account_name: String,
+    account_number: u32,
+    connection: &PgConnection,
+) -> QueryResult<Vec<(String, i64)>> {
+    use schema::accounts::dsl::*;
+
+    let account_number =
+        i32::try_from(account_number).expect("Error converting account number from u32 to i32");
+
+    accounts
+        .filter(
+            name.eq(account_name)
+                .and(number.eq(account_number))
+                .and(end_block_num.eq(i64::MAX)),
+        )
+        .select((currency, balance))
+        .order_by(currency.asc())
+        .load(connection)
+}
+
+/// Status of the account along with the reason its points were forfeited, if closed
+pub fn fetch_account_status(
+    account_name: String,
+    account_number: u32,
+    connection: &PgConnection,
+) -> QueryResult<(String, Option<String>)> {
+    use schema::accounts::dsl::*;
+
+    let account_number =
+        i32::try_from(account_number).expect("Error converting account number from u32 to i32");
+
+    accounts
+        .filter(
+            name.eq(account_name)
+                .and(number.eq(account_number))
+                .and(currency.eq(""))
+                .and(end_block_num.eq(i64::MAX)),
+        )
+        .select((status, closure_reason))
+        .first(connection)
+}
+
+/// Current tier of the account and the points it ever earned, along with the tiers
+/// it went through, oldest first
+pub fn fetch_account_tier(
+    account_name: String,
+    account_number: u32,
+    connection: &PgConnection,
+) -> QueryResult<(Option<String>, i64, Vec<models::AccountTier>)> {
+    use schema::accounts::dsl::*;
+
+    let account_number =
+        i32::try_from(account_number).expect("Error converting account number from u32 to i32");
+
+    let (current_tier, earned) = accounts
+        .filter(
+            name.eq(&account_name)
+                .and(number.eq(account_number))
+                .and(currency.eq(""))
+                .and(end_block_num.eq(i64::MAX)),
+        )
+        .select((tier, lifetime_earned))
+        .first::<(Option<String>, i64)>(connection)?;
+
+    let history = {
+        use schema::account_tiers::dsl::*;
+
+        account_tiers
+            .filter(name.eq(account_name).and(number.eq(account_number)))
+            .order_by((changed_at.asc(), id.asc()))
+            .load::<models::AccountTier>(connection)?
+    };
+
+    Ok((current_tier, earned, history))
+}
+
 pub fn insert_account(
     account: models::NewAccount,
     connection: &PgConnection,
 ) -> QueryResult<usize> {
     use schema::accounts::dsl::*;
 
-    diesel::update(accounts.filter(end_block_num.eq(account.end_block_num)))
-        .set(end_block_num.eq(account.start_block_num))
-        .execute(connection)?;
+    diesel::update(
+        accounts.filter(
+            name.eq(account.name)
+                .and(number.eq(account.number))
+                .and(currency.eq(account.currency))
+                .and(end_block_num.eq(account.end_block_num)),
+        ),
+    )
+    .set(end_block_num.eq(account.start_block_num))
+    .execute(connection)?;
     diesel::insert_into(accounts)
         .values(&account)
         .execute(connection)
 }
 
+/// Records a tier change, the account's state is written on every change to its
+/// balance so a tier already recorded is left as is
+pub fn insert_account_tier(
+    account_tier: models::NewAccountTier,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::account_tiers::dsl::*;
+
+    let current = account_tiers
+        .filter(
+            name.eq(account_tier.name)
+                .and(number.eq(account_tier.number))
+                .and(end_block_num.eq(account_tier.end_block_num)),
+        )
+        .select((tier, changed_at))
+        .first::<(Option<String>, NaiveDateTime)>(connection)
+        .optional()?;
+    if current == Some((account_tier.tier.map(String::from), account_tier.changed_at)) {
+        return Ok(0);
+    }
+
+    diesel::update(
+        account_tiers.filter(
+            name.eq(account_tier.name)
+                .and(number.eq(account_tier.number))
+                .and(end_block_num.eq(account_tier.end_block_num)),
+        ),
+    )
+    .set(end_block_num.eq(account_tier.start_block_num))
+    .execute(connection)?;
+    diesel::insert_into(account_tiers)
+        .values(&account_tier)
+        .execute(connection)
+}
+
+/// Ends the current rows of an account that no longer holds this number
+pub fn close_account(
+    account_name: &str,
+    account_number: i32,
+    block_num: i64,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::accounts::dsl::*;
+
+    diesel::update(
+        accounts.filter(
+            name.eq(account_name)
+                .and(number.eq(account_number))
+                .and(end_block_num.eq(i64::MAX)),
+        ),
+    )
+    .set(end_block_num.eq(block_num))
+    .execute(connection)?;
+    {
+        use schema::account_lots::dsl::*;
+
+        diesel::update(
+            account_lots.filter(
+                name.eq(account_name)
+                    .and(number.eq(account_number))
+                    .and(end_block_num.eq(i64::MAX)),
+            ),
+        )
+        .set(end_block_num.eq(block_num))
+        .execute(connection)
+    }
+}
+
+pub fn insert_lots(
+    account_name: &str,
+    account_number: i32,
+    lots: Vec<models::NewLot>,
+    block_num: i64,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::account_lots::dsl::*;
+
+    diesel::update(
+        account_lots.filter(
+            name.eq(account_name)
+                .and(number.eq(account_number))
+                .and(end_block_num.eq(i64::MAX)),
+        ),
+    )
+    .set(end_block_num.eq(block_num))
+    .execute(connection)?;
+    diesel::insert_into(account_lots)
+        .values(&lots)
+        .execute(connection)
+}
+
+pub fn fetch_expiring_lots(
+    account_name: String,
+    account_number: u32,
+    from: NaiveDateTime,
+    until: NaiveDateTime,
+    connection: &PgConnection,
+) -> QueryResult<Vec<models::Lot>> {
+    use schema::account_lots::dsl::*;
+
+    let account_number =
+        i32::try_from(account_number).expect("Error converting account number from u32 to i32");
+
+    account_lots
+        .filter(
+            name.eq(account_name)
+                .and(number.eq(account_number))
+                .and(end_block_num.eq(i64::MAX))
+                .and(expires_at.gt(from))
+                .and(expires_at.le(until)),
+        )
+        .order_by(expires_at.asc())
+        .load::<models::Lot>(connection)
+}
+
 pub fn insert_merchant(
     merchant: models::NewMerchant,
     connection: &PgConnection,
 ) -> QueryResult<usize> {
     use schema::merchants::dsl::*;
 
-    diesel::update(merchants.filter(end_block_num.eq(merchant.end_block_num)))
-        .set(end_block_num.eq(merchant.start_block_num))
-        .execute(connection)?;
+    diesel::update(
+        merchants.filter(
+            public_key
+                .eq(merchant.public_key)
+                .and(end_block_num.eq(merchant.end_block_num)),
+        ),
+    )
+    .set(end_block_num.eq(merchant.start_block_num))
+    .execute(connection)?;
     diesel::insert_into(merchants)
         .values(&merchant)
         .execute(connection)
 }
 
+pub fn insert_catalog_item(
+    item: models::NewCatalogItem,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::catalog_items::dsl::*;
+
+    diesel::update(
+        catalog_items.filter(
+            public_key
+                .eq(item.public_key)
+                .and(item_id.eq(item.item_id))
+                .and(end_block_num.eq(item.end_block_num)),
+        ),
+    )
+    .set(end_block_num.eq(item.start_block_num))
+    .execute(connection)?;
+    diesel::insert_into(catalog_items)
+        .values(&item)
+        .execute(connection)
+}
+
+/// Current catalog, of a single merchant when one is given
+pub fn fetch_catalog_items(
+    merchant_public_key: Option<String>,
+    connection: &PgConnection,
+) -> QueryResult<Vec<models::CatalogItem>> {
+    use schema::catalog_items::dsl::*;
+
+    let mut query = catalog_items
+        .filter(end_block_num.eq(i64::MAX))
+        .into_boxed();
+    if let Some(merchant_public_key) = merchant_public_key {
+        query = query.filter(public_key.eq(merchant_public_key));
+    }
+    query
+        .order_by((public_key.asc(), item_id.asc()))
+        .load::<models::CatalogItem>(connection)
+}
+
+pub fn insert_voucher(
+    voucher: models::NewVoucher,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::vouchers::dsl::*;
+
+    diesel::update(
+        vouchers.filter(
+            public_key
+                .eq(voucher.public_key)
+                .and(voucher_id.eq(voucher.voucher_id))
+                .and(end_block_num.eq(voucher.end_block_num)),
+        ),
+    )
+    .set(end_block_num.eq(voucher.start_block_num))
+    .execute(connection)?;
+    diesel::insert_into(vouchers)
+        .values(&voucher)
+        .execute(connection)
+}
+
+/// Current vouchers issued to an account, consumed ones included
+pub fn fetch_vouchers(
+    account_name: String,
+    account_number: u32,
+    connection: &PgConnection,
+) -> QueryResult<Vec<models::Voucher>> {
+    use schema::vouchers::dsl::*;
+
+    vouchers
+        .filter(name.eq(account_name))
+        .filter(number.eq(account_number as i32))
+        .filter(end_block_num.eq(i64::MAX))
+        .order_by(issued_at.asc())
+        .load::<models::Voucher>(connection)
+}
+
+pub fn insert_settlement(
+    settlement: models::NewSettlement,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::settlements::dsl::*;
+
+    diesel::update(
+        settlements.filter(
+            issuer
+                .eq(settlement.issuer)
+                .and(redeemer.eq(settlement.redeemer))
+                .and(period_start.eq(settlement.period_start))
+                .and(end_block_num.eq(settlement.end_block_num)),
+        ),
+    )
+    .set(end_block_num.eq(settlement.start_block_num))
+    .execute(connection)?;
+    diesel::insert_into(settlements)
+        .values(&settlement)
+        .execute(connection)
+}
+
+/// Current settlements of the days starting within `[from, until)`
+pub fn fetch_settlements(
+    from: NaiveDateTime,
+    until: NaiveDateTime,
+    connection: &PgConnection,
+) -> QueryResult<Vec<models::Settlement>> {
+    use schema::settlements::dsl::*;
+
+    settlements
+        .filter(end_block_num.eq(i64::MAX))
+        .filter(period_start.ge(from))
+        .filter(period_start.lt(until))
+        .order_by((issuer.asc(), redeemer.asc(), period_start.asc()))
+        .load::<models::Settlement>(connection)
+}
+
 pub fn insert_auth(credentials: models::NewCredentials, connection: &PgConnection) -> QueryResult<usize> {
     use schema::auth::dsl::*;
 
@@ -168,6 +550,58 @@ pub fn fetch_auth(
         .get_result::<models::Credentials>(connection)
 }
 
+/// Records the key pair a merchant is rotating to before the rotation is submitted, the
+/// private key encrypted like the current one
+pub fn set_pending_rotation(
+    public_key_param: String,
+    new_public_key: &str,
+    new_encrypted_private_key: &str,
+    batch_id: &str,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::auth::dsl::*;
+
+    diesel::update(auth.filter(public_key.eq(public_key_param)))
+        .set((
+            pending_public_key.eq(Some(new_public_key)),
+            pending_private_key.eq(Some(new_encrypted_private_key)),
+            pending_batch_id.eq(Some(batch_id)),
+        ))
+        .execute(connection)
+}
+
+/// Drops a pending rotation whose batch will never commit
+pub fn clear_pending_rotation(
+    public_key_param: String,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::auth::dsl::*;
+
+    diesel::update(auth.filter(public_key.eq(public_key_param)))
+        .set((
+            pending_public_key.eq(None::<String>),
+            pending_private_key.eq(None::<String>),
+            pending_batch_id.eq(None::<String>),
+        ))
+        .execute(connection)
+}
+
+/// Moves the credentials of a merchant over to its rotated key
+pub fn rotate_auth(
+    old_public_key: String,
+    credentials: models::NewCredentials,
+    connection: &PgConnection,
+) -> QueryResult<usize> {
+    use schema::auth::dsl::*;
+
+    connection.transaction(|| {
+        diesel::delete(auth.filter(public_key.eq(old_public_key))).execute(connection)?;
+        diesel::insert_into(auth)
+            .values(&credentials)
+            .execute(connection)
+    })
+}
+
 #[cfg(test)]
 mod test {
     use crate::fetch_auth;
@@ -1,4 +1,7 @@
-use super::schema::{accounts, auth, blocks, merchants};
+use super::schema::{
+    account_lots, account_tiers, accounts, auth, blocks, catalog_items, merchants, settlements,
+    vouchers,
+};
 use chrono::NaiveDateTime;
 use diesel::{Insertable, Queryable};
 
@@ -6,9 +9,16 @@ use diesel::{Insertable, Queryable};
 pub struct Account {
     pub name: String,
     pub number: u32,
-    pub balance: i32,
+    pub balance: i64,
     pub start_block_num: Option<i64>,
     pub end_block_num: Option<i64>,
+    pub id: i32,
+    pub previous_number: Option<i32>,
+    pub status: String,
+    pub closure_reason: Option<String>,
+    pub currency: String,
+    pub tier: Option<String>,
+    pub lifetime_earned: i64,
 }
 
 #[derive(AsChangeset, Clone, Debug, Insertable)]
@@ -17,7 +27,65 @@ pub struct Account {
 pub struct NewAccount<'a> {
     pub name: &'a str,
     pub number: i32,
-    pub balance: i32,
+    pub balance: i64,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+    pub previous_number: Option<i32>,
+    pub status: &'a str,
+    pub closure_reason: Option<&'a str>,
+    pub currency: &'a str,
+    pub tier: Option<&'a str>,
+    pub lifetime_earned: i64,
+}
+
+#[derive(Clone, Debug, Queryable)]
+pub struct Lot {
+    pub id: i32,
+    pub name: String,
+    pub number: i32,
+    pub lot_id: i64,
+    pub amount: i64,
+    pub earned_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+    pub issuer: Option<String>,
+    pub currency: String,
+}
+
+#[derive(Clone, Debug, Insertable)]
+#[table_name = "account_lots"]
+pub struct NewLot<'a> {
+    pub name: &'a str,
+    pub number: i32,
+    pub lot_id: i64,
+    pub amount: i64,
+    pub earned_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+    pub issuer: Option<&'a str>,
+    pub currency: &'a str,
+}
+
+#[derive(Clone, Debug, Queryable)]
+pub struct AccountTier {
+    pub id: i32,
+    pub name: String,
+    pub number: i32,
+    pub tier: Option<String>,
+    pub changed_at: NaiveDateTime,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+#[derive(Clone, Debug, Insertable)]
+#[table_name = "account_tiers"]
+pub struct NewAccountTier<'a> {
+    pub name: &'a str,
+    pub number: i32,
+    pub tier: Option<&'a str>,
+    pub changed_at: NaiveDateTime,
     pub start_block_num: Option<i64>,
     pub end_block_num: Option<i64>,
 }
@@ -27,6 +95,10 @@ pub struct Credentials {
     pub public_key: String,
     pub hashed_password: String,
     pub encrypted_private_key: String,
+    // Key pair and batch of a rotation submitted without its outcome being known yet
+    pub pending_public_key: Option<String>,
+    pub pending_private_key: Option<String>,
+    pub pending_batch_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Insertable)]
@@ -57,6 +129,8 @@ pub struct Merchant {
     pub created: NaiveDateTime,
     pub start_block_num: Option<i64>,
     pub end_block_num: Option<i64>,
+    pub id: i32,
+    pub successor_public_key: Option<String>,
 }
 
 #[derive(AsChangeset, Clone, Debug, Insertable)]
@@ -68,4 +142,81 @@ pub struct NewMerchant<'a> {
     pub created: &'a NaiveDateTime,
     pub start_block_num: Option<i64>,
     pub end_block_num: Option<i64>,
+    pub successor_public_key: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, Queryable)]
+pub struct CatalogItem {
+    pub id: i32,
+    pub public_key: String,
+    pub item_id: String,
+    pub description: String,
+    pub point_cost: i64,
+    pub stock: i64,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+#[derive(Clone, Debug, Insertable)]
+#[table_name = "catalog_items"]
+pub struct NewCatalogItem<'a> {
+    pub public_key: &'a str,
+    pub item_id: &'a str,
+    pub description: &'a str,
+    pub point_cost: i64,
+    pub stock: i64,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+#[derive(Clone, Debug, Queryable)]
+pub struct Voucher {
+    pub id: i32,
+    pub public_key: String,
+    pub voucher_id: String,
+    pub name: String,
+    pub number: i32,
+    pub discount: i64,
+    pub issued_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub consumed_at: Option<NaiveDateTime>,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+#[derive(Clone, Debug, Insertable)]
+#[table_name = "vouchers"]
+pub struct NewVoucher<'a> {
+    pub public_key: &'a str,
+    pub voucher_id: &'a str,
+    pub name: &'a str,
+    pub number: i32,
+    pub discount: i64,
+    pub issued_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub consumed_at: Option<NaiveDateTime>,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+#[derive(Clone, Debug, Queryable)]
+pub struct Settlement {
+    pub id: i32,
+    pub issuer: String,
+    pub redeemer: String,
+    pub period_start: NaiveDateTime,
+    pub amount: i64,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+#[derive(Clone, Debug, Insertable)]
+#[table_name = "settlements"]
+pub struct NewSettlement<'a> {
+    pub issuer: &'a str,
+    pub redeemer: &'a str,
+    pub period_start: NaiveDateTime,
+    pub amount: i64,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
 }
@@ -1,10 +1,45 @@
 table! {
-    accounts (name, number) {
+    account_lots (id) {
+        id -> Int4,
         name -> Varchar,
         number -> Int4,
-        balance -> Int4,
+        lot_id -> Int8,
+        amount -> Int8,
+        earned_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
         start_block_num -> Nullable<Int8>,
         end_block_num -> Nullable<Int8>,
+        issuer -> Nullable<Varchar>,
+        currency -> Varchar,
+    }
+}
+
+table! {
+    account_tiers (id) {
+        id -> Int4,
+        name -> Varchar,
+        number -> Int4,
+        tier -> Nullable<Varchar>,
+        changed_at -> Timestamp,
+        start_block_num -> Nullable<Int8>,
+        end_block_num -> Nullable<Int8>,
+    }
+}
+
+table! {
+    accounts (id) {
+        name -> Varchar,
+        number -> Int4,
+        balance -> Int8,
+        start_block_num -> Nullable<Int8>,
+        end_block_num -> Nullable<Int8>,
+        id -> Int4,
+        previous_number -> Nullable<Int4>,
+        status -> Varchar,
+        closure_reason -> Nullable<Varchar>,
+        currency -> Varchar,
+        tier -> Nullable<Varchar>,
+        lifetime_earned -> Int8,
     }
 }
 
@@ -13,6 +48,9 @@ table! {
         public_key -> Varchar,
         hashed_password -> Varchar,
         encrypted_private_key -> Varchar,
+        pending_public_key -> Nullable<Varchar>,
+        pending_private_key -> Nullable<Varchar>,
+        pending_batch_id -> Nullable<Varchar>,
     }
 }
 
@@ -24,20 +62,66 @@ table! {
 }
 
 table! {
-    merchants (public_key) {
+    catalog_items (id) {
+        id -> Int4,
+        public_key -> Varchar,
+        item_id -> Varchar,
+        description -> Varchar,
+        point_cost -> Int8,
+        stock -> Int8,
+        start_block_num -> Nullable<Int8>,
+        end_block_num -> Nullable<Int8>,
+    }
+}
+
+table! {
+    merchants (id) {
         name -> Nullable<Varchar>,
         public_key -> Varchar,
         created -> Nullable<Timestamp>,
         start_block_num -> Nullable<Int8>,
         end_block_num -> Nullable<Int8>,
+        id -> Int4,
+        successor_public_key -> Nullable<Varchar>,
+    }
+}
+
+table! {
+    settlements (id) {
+        id -> Int4,
+        issuer -> Varchar,
+        redeemer -> Varchar,
+        period_start -> Timestamp,
+        amount -> Int8,
+        start_block_num -> Nullable<Int8>,
+        end_block_num -> Nullable<Int8>,
     }
 }
 
-joinable!(auth -> merchants (public_key));
+table! {
+    vouchers (id) {
+        id -> Int4,
+        public_key -> Varchar,
+        voucher_id -> Varchar,
+        name -> Varchar,
+        number -> Int4,
+        discount -> Int8,
+        issued_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
+        consumed_at -> Nullable<Timestamp>,
+        start_block_num -> Nullable<Int8>,
+        end_block_num -> Nullable<Int8>,
+    }
+}
 
 allow_tables_to_appear_in_same_query!(
+    account_lots,
+    account_tiers,
     accounts,
     auth,
     blocks,
+    catalog_items,
     merchants,
+    settlements,
+    vouchers,
 );
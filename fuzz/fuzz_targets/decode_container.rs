@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use archer::get_archer_prefix;
+use archer_protobuf::deserialize_data;
+
+// The first byte picks the address type, unknown ones included, and the rest is the
+// container the subscriber reads off the state change
+fuzz_target!(|data: &[u8]| {
+    if let Some((address_type, container)) = data.split_first() {
+        let address = format!("{}{:02x}", get_archer_prefix(), address_type % 8);
+        let _ = deserialize_data(&address, container.to_vec());
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use archer::FamilyVersion;
+use archer_processor::payload::ArcherPayload;
+
+// Payloads come straight from the transaction, decoding may reject them but never panic
+fuzz_target!(|data: &[u8]| {
+    for family_version in FamilyVersion::ALL.iter() {
+        let _ = ArcherPayload::new(data, *family_version);
+    }
+});
@@ -0,0 +1,280 @@
+//! Addresses each action reads and writes, which transactions declare as their inputs
+//! and outputs. Clients build their headers from these, and the processor tests hold
+//! the handler to them.
+
+use super::{
+    calculate_account_address, calculate_catalog_item_address, calculate_earn_rule_address,
+    calculate_merchant_address, calculate_setting_address, calculate_voucher_address,
+    get_archer_prefix, get_type_prefix, ArcherTypes, ADMIN_KEYS_SETTING, TIERS_SETTING,
+    VELOCITY_LIMITS_SETTING,
+};
+
+/// Inputs and outputs of a transaction, the validator rejects reads and writes outside
+/// of them
+pub type Addresses = (Vec<String>, Vec<String>);
+
+/// Every address holding records of the type, for records whose keys are only known
+/// once the state is read
+pub fn type_namespace(archer_type: &ArcherTypes) -> String {
+    get_archer_prefix() + &get_type_prefix(archer_type).expect("Invalid archer type")
+}
+
+/// Debits may settle with any merchant, which is only known once the lots are read
+pub fn settlement_namespace() -> String {
+    type_namespace(&ArcherTypes::Settlement)
+}
+
+/// Deposits and withdrawals are held to the velocity limits set through the settings
+fn velocity_limits_address() -> String {
+    calculate_setting_address(VELOCITY_LIMITS_SETTING)
+}
+
+/// Union of the addresses of the operations a transaction carries, along with the
+/// signer's merchant address, which the processor reads first to turn rotated keys away
+pub fn transaction_addresses(signer: &str, operations: Vec<Addresses>) -> Addresses {
+    let mut inputs: Vec<String> = Vec::new();
+    let mut outputs: Vec<String> = Vec::new();
+    for (operation_inputs, operation_outputs) in operations {
+        add_addresses(&mut inputs, operation_inputs);
+        add_addresses(&mut outputs, operation_outputs);
+    }
+    add_addresses(&mut inputs, vec![calculate_merchant_address(signer)]);
+    (inputs, outputs)
+}
+
+/// Adds addresses not declared yet, keeping the order they were first seen in
+fn add_addresses(addresses: &mut Vec<String>, new_addresses: Vec<String>) {
+    for address in new_addresses {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+}
+
+/// Deposits count toward the velocity limits of the merchant
+pub fn deposit_addresses(signer: &str, name: &str) -> Addresses {
+    let address = calculate_account_address(name);
+    let merchant_address = calculate_merchant_address(signer);
+    (
+        vec![
+            String::from(&address),
+            String::from(&merchant_address),
+            velocity_limits_address(),
+        ],
+        vec![address, merchant_address],
+    )
+}
+
+/// Withdrawals count toward the velocity limits of the merchant. Points issued under
+/// rotated keys settle with the issuer's current key.
+pub fn withdraw_addresses(signer: &str, name: &str) -> Addresses {
+    let address = calculate_account_address(name);
+    let merchant_address = calculate_merchant_address(signer);
+    (
+        vec![
+            String::from(&address),
+            String::from(&merchant_address),
+            settlement_namespace(),
+            velocity_limits_address(),
+            type_namespace(&ArcherTypes::Merchant),
+        ],
+        vec![address, merchant_address, settlement_namespace()],
+    )
+}
+
+/// Transfers count toward the withdrawal limits of the merchant moving the points
+pub fn transfer_addresses(signer: &str, name: &str, recipient_name: &str) -> Addresses {
+    let mut outputs = vec![calculate_account_address(name)];
+    add_addresses(
+        &mut outputs,
+        vec![
+            calculate_account_address(recipient_name),
+            calculate_merchant_address(signer),
+        ],
+    );
+    let mut inputs = outputs.clone();
+    inputs.push(velocity_limits_address());
+    (inputs, outputs)
+}
+
+/// Earnings follow the signer's rule, are multiplied by the tier the account reached
+/// and count toward the velocity limits of the merchant
+pub fn earn_addresses(signer: &str, name: &str) -> Addresses {
+    let address = calculate_account_address(name);
+    let merchant_address = calculate_merchant_address(signer);
+    (
+        vec![
+            String::from(&address),
+            calculate_earn_rule_address(signer),
+            calculate_setting_address(TIERS_SETTING),
+            String::from(&merchant_address),
+            velocity_limits_address(),
+        ],
+        vec![address, merchant_address],
+    )
+}
+
+/// Sweeps are accepted from merchants and from the keys listed in the admin setting
+pub fn expire_addresses(signer: &str, name: &str) -> Addresses {
+    let address = calculate_account_address(name);
+    (
+        vec![
+            String::from(&address),
+            calculate_merchant_address(signer),
+            calculate_setting_address(ADMIN_KEYS_SETTING),
+        ],
+        vec![address],
+    )
+}
+
+/// The item may have been published under a previous key of the signer, and points
+/// issued under rotated keys settle with the issuer's current key
+pub fn redeem_addresses(signer: &str, name: &str) -> Addresses {
+    let address = calculate_account_address(name);
+    let merchant_address = calculate_merchant_address(signer);
+    (
+        vec![
+            String::from(&address),
+            String::from(&merchant_address),
+            type_namespace(&ArcherTypes::CatalogItem),
+            settlement_namespace(),
+            velocity_limits_address(),
+            type_namespace(&ArcherTypes::Merchant),
+        ],
+        vec![
+            address,
+            merchant_address,
+            type_namespace(&ArcherTypes::CatalogItem),
+            settlement_namespace(),
+        ],
+    )
+}
+
+/// The id is checked against the vouchers of the signer's previous keys as well
+pub fn issue_voucher_addresses(signer: &str, name: &str, voucher_id: &str) -> Addresses {
+    (
+        vec![
+            calculate_account_address(name),
+            calculate_merchant_address(signer),
+            type_namespace(&ArcherTypes::Voucher),
+        ],
+        vec![calculate_voucher_address(signer, voucher_id)],
+    )
+}
+
+/// The voucher may have been issued under a previous key of the signer
+pub fn consume_voucher_addresses(signer: &str, name: &str) -> Addresses {
+    (
+        vec![
+            calculate_account_address(name),
+            type_namespace(&ArcherTypes::Voucher),
+            calculate_merchant_address(signer),
+        ],
+        vec![type_namespace(&ArcherTypes::Voucher)],
+    )
+}
+
+/// Freezing, unfreezing and closing are accepted from the keys listed in the admin setting
+pub fn status_addresses(name: &str) -> Addresses {
+    let address = calculate_account_address(name);
+    (
+        vec![
+            String::from(&address),
+            calculate_setting_address(ADMIN_KEYS_SETTING),
+        ],
+        vec![address],
+    )
+}
+
+pub fn update_number_addresses(signer: &str, name: &str) -> Addresses {
+    let address = calculate_account_address(name);
+    (
+        vec![String::from(&address), calculate_merchant_address(signer)],
+        vec![address],
+    )
+}
+
+pub fn add_account_addresses(signer: &str, name: &str) -> Addresses {
+    update_number_addresses(signer, name)
+}
+
+pub fn add_merchant_addresses(signer: &str) -> Addresses {
+    let address = calculate_merchant_address(signer);
+    (vec![String::from(&address)], vec![address])
+}
+
+/// The merchant and its earn rule move to the addresses of the new key
+pub fn rotate_merchant_key_addresses(signer: &str, new_public_key: &str) -> Addresses {
+    let mut inputs = vec![calculate_merchant_address(signer)];
+    add_addresses(
+        &mut inputs,
+        vec![
+            calculate_merchant_address(new_public_key),
+            calculate_earn_rule_address(signer),
+            calculate_earn_rule_address(new_public_key),
+        ],
+    );
+    let outputs = inputs.clone();
+    (inputs, outputs)
+}
+
+pub fn set_earn_rule_addresses(signer: &str) -> Addresses {
+    let address = calculate_earn_rule_address(signer);
+    (
+        vec![String::from(&address), calculate_merchant_address(signer)],
+        vec![address],
+    )
+}
+
+pub fn set_catalog_item_addresses(signer: &str, item_id: &str) -> Addresses {
+    let address = calculate_catalog_item_address(signer, item_id);
+    (
+        vec![String::from(&address), calculate_merchant_address(signer)],
+        vec![address],
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn declares_signer_once() {
+        let (inputs, outputs) = transaction_addresses(
+            "merchant",
+            vec![
+                deposit_addresses("merchant", "John Doe"),
+                status_addresses("John Doe"),
+            ],
+        );
+        assert_eq!(
+            inputs,
+            vec![
+                calculate_account_address("John Doe"),
+                calculate_merchant_address("merchant"),
+                velocity_limits_address(),
+                calculate_setting_address(ADMIN_KEYS_SETTING),
+            ]
+        );
+        assert_eq!(
+            outputs,
+            vec![
+                calculate_account_address("John Doe"),
+                calculate_merchant_address("merchant"),
+            ]
+        );
+    }
+
+    #[test]
+    fn declares_transfers_between_namesakes_once() {
+        let (inputs, outputs) = transfer_addresses("merchant", "John Doe", "John Doe");
+        assert_eq!(
+            outputs,
+            vec![
+                calculate_account_address("John Doe"),
+                calculate_merchant_address("merchant"),
+            ]
+        );
+        assert_eq!(inputs.len(), 3);
+    }
+}
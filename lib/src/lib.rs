@@ -1,5 +1,7 @@
 use ring::digest;
+use std::convert::TryFrom;
 
+pub mod addresses;
 pub mod error;
 
 /*
@@ -11,18 +13,201 @@ pub mod error;
 
 pub const NAME: &str = "archer";
 
+/// Sawtooth setting holding the comma-separated public keys of program administrators
+pub const ADMIN_KEYS_SETTING: &str = "archer.admin_keys";
+
+/// Sawtooth setting holding the membership tiers, lowest first, as comma-separated
+/// `name:lifetime_threshold:rolling_threshold:multiplier` entries
+pub const TIERS_SETTING: &str = "archer.tiers";
+
+/// Sawtooth setting holding the velocity limits as comma-separated
+/// `scope.direction:max_per_transaction:max_per_window:window_seconds` entries, the
+/// scope being `account` or `merchant` and the direction `deposit` or `withdrawal`
+pub const VELOCITY_LIMITS_SETTING: &str = "archer.velocity_limits";
+
+/// Payload formats the processor accepts, 1.0 sends a bare `Payload` and 2.0
+/// wraps it in a `PayloadEnvelope`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FamilyVersion {
+    V1,
+    V2,
+}
+
+impl FamilyVersion {
+    pub const ALL: [FamilyVersion; 2] = [FamilyVersion::V1, FamilyVersion::V2];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FamilyVersion::V1 => "1.0",
+            FamilyVersion::V2 => "2.0",
+        }
+    }
+}
+
+impl TryFrom<&str> for FamilyVersion {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        FamilyVersion::ALL
+            .iter()
+            .find(|version| version.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("{} is not a supported family version", s))
+    }
+}
+
+/// Events the processor adds alongside its state changes, so subscribers learn what
+/// happened without diffing containers. Account events carry the `name`, `number`,
+/// `merchant`, `currency`, `amount` and resulting `balance` attributes, transfers also
+/// name the recipient. Tier changes carry the `name`, `number` and new `tier` of the
+/// account. Merchant registrations carry the `merchant` and its `name`, key rotations
+/// the retired `merchant` key and its `successor`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArcherEvent {
+    PointsDeposited,
+    PointsWithdrawn,
+    PointsEarned,
+    PointsTransferred,
+    PointsRedeemed,
+    PointsExpired,
+    TierChanged,
+    MerchantRegistered,
+    MerchantKeyRotated,
+}
+
+impl ArcherEvent {
+    pub const ALL: [ArcherEvent; 9] = [
+        ArcherEvent::PointsDeposited,
+        ArcherEvent::PointsWithdrawn,
+        ArcherEvent::PointsEarned,
+        ArcherEvent::PointsTransferred,
+        ArcherEvent::PointsRedeemed,
+        ArcherEvent::PointsExpired,
+        ArcherEvent::TierChanged,
+        ArcherEvent::MerchantRegistered,
+        ArcherEvent::MerchantKeyRotated,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArcherEvent::PointsDeposited => "archer/points-deposited",
+            ArcherEvent::PointsWithdrawn => "archer/points-withdrawn",
+            ArcherEvent::PointsEarned => "archer/points-earned",
+            ArcherEvent::PointsTransferred => "archer/points-transferred",
+            ArcherEvent::PointsRedeemed => "archer/points-redeemed",
+            ArcherEvent::PointsExpired => "archer/points-expired",
+            ArcherEvent::TierChanged => "archer/tier-changed",
+            ArcherEvent::MerchantRegistered => "archer/merchant-registered",
+            ArcherEvent::MerchantKeyRotated => "archer/merchant-key-rotated",
+        }
+    }
+}
+
+impl TryFrom<&str> for ArcherEvent {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        ArcherEvent::ALL
+            .iter()
+            .find(|event| event.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("{} is not an archer event", s))
+    }
+}
+
+/// Only active accounts can have points move in or out
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccountStatus {
+    Active,
+    Frozen,
+    Closed,
+}
+
+impl AccountStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountStatus::Active => "active",
+            AccountStatus::Frozen => "frozen",
+            AccountStatus::Closed => "closed",
+        }
+    }
+}
+
 pub struct Account {
     pub name: String,
     pub number: u32,
-    pub balance: i32,
+    pub balance: u64,
+    pub currency_balances: Vec<(String, u64)>,
+    pub lots: Vec<Lot>,
+    pub owner_public_key: Option<String>,
+    pub previous_numbers: Vec<u32>,
+    pub status: AccountStatus,
+    pub closure_reason: Option<String>,
+    pub lifetime_earned: u64,
+    pub tier: Option<String>,
+    pub tier_changed_at: Option<i64>,
     pub start_block_num: Option<i64>,
     pub end_block_num: Option<i64>,
 }
 
+pub struct Lot {
+    pub id: u64,
+    pub amount: u64,
+    pub earned_at: i64,
+    pub expires_at: Option<i64>,
+    pub issuer: Option<String>,
+    pub currency: Option<String>,
+}
+
 pub struct Merchant {
     pub name: String,
     pub public_key: String,
     pub timestamp: i64,
+    pub successor_public_key: Option<String>,
+    pub previous_public_keys: Vec<String>,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+pub struct CatalogItem {
+    pub public_key: String,
+    pub id: String,
+    pub description: String,
+    pub point_cost: u64,
+    pub stock: u64,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+pub struct Voucher {
+    pub public_key: String,
+    pub id: String,
+    pub name: String,
+    pub number: u32,
+    pub discount: u64,
+    pub issued_at: i64,
+    pub expires_at: Option<i64>,
+    pub consumed_at: Option<i64>,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+pub struct Settlement {
+    pub issuer: String,
+    pub redeemer: String,
+    pub period_start: i64,
+    pub amount: u64,
+    pub start_block_num: Option<i64>,
+    pub end_block_num: Option<i64>,
+}
+
+pub struct EarnRule {
+    pub public_key: String,
+    pub points_per_unit: u32,
+    pub rounding: String,
+    pub minimum_purchase: u64,
+    pub multipliers: Vec<(String, u32)>,
+    pub points_lifetime: Option<i64>,
     pub start_block_num: Option<i64>,
     pub end_block_num: Option<i64>,
 }
@@ -39,20 +224,52 @@ pub enum ArcherModules {
 pub enum ArcherStructs {
     Account(Account),
     Merchant(Merchant),
+    EarnRule(EarnRule),
+    CatalogItem(CatalogItem),
+    Voucher(Voucher),
+    Settlement(Settlement),
 }
 
 impl ArcherStructs {
     pub fn account(self) -> Option<Account> {
         match self {
             ArcherStructs::Account(account) => Some(account),
-            ArcherStructs::Merchant(_merchant) => None,
+            _ => None,
         }
     }
 
     pub fn merchant(self) -> Option<Merchant> {
         match self {
             ArcherStructs::Merchant(merchant) => Some(merchant),
-            ArcherStructs::Account(_account) => None,
+            _ => None,
+        }
+    }
+
+    pub fn earn_rule(self) -> Option<EarnRule> {
+        match self {
+            ArcherStructs::EarnRule(earn_rule) => Some(earn_rule),
+            _ => None,
+        }
+    }
+
+    pub fn catalog_item(self) -> Option<CatalogItem> {
+        match self {
+            ArcherStructs::CatalogItem(catalog_item) => Some(catalog_item),
+            _ => None,
+        }
+    }
+
+    pub fn voucher(self) -> Option<Voucher> {
+        match self {
+            ArcherStructs::Voucher(voucher) => Some(voucher),
+            _ => None,
+        }
+    }
+
+    pub fn settlement(self) -> Option<Settlement> {
+        match self {
+            ArcherStructs::Settlement(settlement) => Some(settlement),
+            _ => None,
         }
     }
 }
@@ -61,6 +278,10 @@ impl ArcherStructs {
 pub enum ArcherTypes {
     Account,
     Merchant,
+    EarnRule,
+    CatalogItem,
+    Voucher,
+    Settlement,
 }
 
 pub fn get_archer_prefix() -> String {
@@ -73,6 +294,10 @@ pub fn get_type_prefix(archer_type: &ArcherTypes) -> Option<String> {
     match archer_type {
         ArcherTypes::Account => Some(String::from("00")),
         ArcherTypes::Merchant => Some(String::from("01")),
+        ArcherTypes::Voucher => Some(String::from("02")),
+        ArcherTypes::EarnRule => Some(String::from("03")),
+        ArcherTypes::CatalogItem => Some(String::from("04")),
+        ArcherTypes::Settlement => Some(String::from("05")),
     }
 }
 
@@ -92,10 +317,131 @@ pub fn calculate_merchant_address(public_key: &str) -> String {
     prefix + &result[..62].to_string()
 }
 
+pub fn calculate_earn_rule_address(public_key: &str) -> String {
+    let result = digest::digest(&digest::SHA512, public_key.as_bytes());
+    let result = to_hex_string(result.as_ref());
+    let mut prefix = get_archer_prefix();
+    prefix.push_str(&get_type_prefix(&ArcherTypes::EarnRule).expect("Invalid archer type"));
+    prefix + &result[..62]
+}
+
+/// Items of one merchant are spread over addresses, the id can be any string
+pub fn calculate_catalog_item_address(public_key: &str, item_id: &str) -> String {
+    let key = format!("{}{}", public_key, item_id);
+    let result = digest::digest(&digest::SHA512, key.as_bytes());
+    let result = to_hex_string(result.as_ref());
+    let mut prefix = get_archer_prefix();
+    prefix.push_str(&get_type_prefix(&ArcherTypes::CatalogItem).expect("Invalid archer type"));
+    prefix + &result[..62]
+}
+
+/// Voucher ids are chosen by the issuing merchant, like catalog item ids
+pub fn calculate_voucher_address(public_key: &str, voucher_id: &str) -> String {
+    let key = format!("{}{}", public_key, voucher_id);
+    let result = digest::digest(&digest::SHA512, key.as_bytes());
+    let result = to_hex_string(result.as_ref());
+    let mut prefix = get_archer_prefix();
+    prefix.push_str(&get_type_prefix(&ArcherTypes::Voucher).expect("Invalid archer type"));
+    prefix + &result[..62]
+}
+
+/// Length of a settlement period, obligations accumulate per UTC day
+pub const SETTLEMENT_PERIOD: i64 = 86400;
+
+/// Start of the settlement period a timestamp falls in
+pub fn settlement_period_start(timestamp: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(SETTLEMENT_PERIOD)
+}
+
+/// Each issuer and redeemer pair gets one address per period
+pub fn calculate_settlement_address(issuer: &str, redeemer: &str, period_start: i64) -> String {
+    let key = format!("{}{}{}", issuer, redeemer, period_start);
+    let result = digest::digest(&digest::SHA512, key.as_bytes());
+    let result = to_hex_string(result.as_ref());
+    let mut prefix = get_archer_prefix();
+    prefix.push_str(&get_type_prefix(&ArcherTypes::Settlement).expect("Invalid archer type"));
+    prefix + &result[..62]
+}
+
+/// Address of a key in the Sawtooth settings namespace: the first four dot-separated
+/// parts are each hashed with SHA-256, missing parts count as empty strings
+pub fn calculate_setting_address(key: &str) -> String {
+    let mut parts: Vec<&str> = key.splitn(4, '.').collect();
+    parts.resize(4, "");
+    let mut address = String::from("000000");
+    for part in parts {
+        let result = digest::digest(&digest::SHA256, part.as_bytes());
+        address.push_str(&to_hex_string(result.as_ref())[..16]);
+    }
+    address
+}
+
+/// Debits an account owner can authorize, named in the message they sign. Redemptions
+/// also name what is bought with the points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DebitAction<'a> {
+    Withdraw,
+    Transfer,
+    Redeem { item_id: &'a str, quantity: u64 },
+}
+
+impl DebitAction<'_> {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DebitAction::Withdraw => "withdraw",
+            DebitAction::Transfer => "transfer",
+            DebitAction::Redeem { .. } => "redeem",
+        }
+    }
+}
+
+/// Message an account owner signs to let points leave their account. It names the
+/// debit and the merchant submitting it, so a signature cannot be replayed as another
+/// action or by another merchant. Withdrawals and redemptions have an empty recipient,
+/// the timestamp keeps each signature from being reused. Redemptions append the item
+/// and quantity, so the points cannot be spent on another item. Merchant currencies
+/// are appended last, the shared pool has none.
+#[allow(clippy::too_many_arguments)]
+pub fn make_owner_authorization(
+    action: DebitAction,
+    merchant: &str,
+    name: &str,
+    number: u32,
+    recipient_name: &str,
+    recipient_number: u32,
+    amount: u64,
+    timestamp: i64,
+    currency: &str,
+) -> Vec<u8> {
+    let mut message = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+        action.as_str(),
+        merchant,
+        name,
+        number,
+        recipient_name,
+        recipient_number,
+        amount,
+        timestamp
+    );
+    if let DebitAction::Redeem { item_id, quantity } = action {
+        message.push_str(&format!("\n{}\n{}", item_id, quantity));
+    }
+    if !currency.is_empty() {
+        message.push('\n');
+        message.push_str(currency);
+    }
+    message.into_bytes()
+}
+
 pub fn get_address_type(address: &str) -> Option<ArcherTypes> {
-    match &address[6..8] {
+    match address.get(6..8)? {
         "00" => Some(ArcherTypes::Account),
         "01" => Some(ArcherTypes::Merchant),
+        "02" => Some(ArcherTypes::Voucher),
+        "03" => Some(ArcherTypes::EarnRule),
+        "04" => Some(ArcherTypes::CatalogItem),
+        "05" => Some(ArcherTypes::Settlement),
         _ => None,
     }
 }
@@ -129,6 +475,36 @@ mod test {
         // todo!();
     }
 
+    #[test]
+    fn family_versions() {
+        for version in FamilyVersion::ALL.iter() {
+            assert_eq!(FamilyVersion::try_from(version.as_str()), Ok(*version));
+        }
+        assert!(FamilyVersion::try_from("3.0").is_err());
+        assert!(FamilyVersion::try_from("").is_err());
+    }
+
+    #[test]
+    fn setting_address() {
+        assert_eq!(
+            calculate_setting_address("sawtooth.settings.vote.authorized_keys"),
+            "000000a87cb5eafdcca6a8cde0fb0dec1400c5ab274474a6aa82c12840f169a04216b7"
+        );
+        assert_eq!(
+            calculate_setting_address(ADMIN_KEYS_SETTING),
+            "000000e226306f3f87b8f3be7655821d6075afe3b0c44298fc1c14e3b0c44298fc1c14"
+        );
+    }
+
+    #[test]
+    fn archer_events() {
+        for event in ArcherEvent::ALL.iter() {
+            assert!(event.as_str().starts_with("archer/"));
+            assert_eq!(ArcherEvent::try_from(event.as_str()), Ok(*event));
+        }
+        assert!(ArcherEvent::try_from("sawtooth/state-delta").is_err());
+    }
+
     #[test]
     fn archer_prefix() {
         assert_eq!(get_archer_prefix(), "9abef4");
@@ -144,6 +520,22 @@ mod test {
             get_type_prefix(&ArcherTypes::Merchant).unwrap(),
             String::from("01")
         );
+        assert_eq!(
+            get_type_prefix(&ArcherTypes::Voucher).unwrap(),
+            String::from("02")
+        );
+        assert_eq!(
+            get_type_prefix(&ArcherTypes::EarnRule).unwrap(),
+            String::from("03")
+        );
+        assert_eq!(
+            get_type_prefix(&ArcherTypes::CatalogItem).unwrap(),
+            String::from("04")
+        );
+        assert_eq!(
+            get_type_prefix(&ArcherTypes::Settlement).unwrap(),
+            String::from("05")
+        );
     }
 
     #[test]
@@ -162,10 +554,160 @@ mod test {
         assert_eq!(result.chars().count(), 70);
     }
 
+    #[test]
+    fn earn_rule_address() {
+        let public_key = "abcdefghijklmnopqrstuv";
+        let result = calculate_earn_rule_address(public_key);
+        assert_eq!(&result[..8].to_string(), "9abef403");
+        assert_eq!(result.chars().count(), 70);
+    }
+
+    #[test]
+    fn catalog_item_address() {
+        let public_key = "abcdefghijklmnopqrstuv";
+        let result = calculate_catalog_item_address(public_key, "mug");
+        assert_eq!(&result[..8].to_string(), "9abef404");
+        assert_eq!(result.chars().count(), 70);
+        assert_ne!(result, calculate_catalog_item_address(public_key, "hat"));
+        assert_eq!(get_address_type(&result), Some(ArcherTypes::CatalogItem));
+    }
+
+    #[test]
+    fn voucher_address() {
+        let public_key = "abcdefghijklmnopqrstuv";
+        let result = calculate_voucher_address(public_key, "spring-10");
+        assert_eq!(&result[..8].to_string(), "9abef402");
+        assert_eq!(result.chars().count(), 70);
+        assert_eq!(get_address_type(&result), Some(ArcherTypes::Voucher));
+    }
+
+    #[test]
+    fn settlement_address() {
+        let period_start = settlement_period_start(1_600_000_000);
+        assert_eq!(period_start, 1_599_955_200);
+        assert_eq!(settlement_period_start(period_start), period_start);
+        assert_eq!(settlement_period_start(-1), -SETTLEMENT_PERIOD);
+        let result = calculate_settlement_address("issuer", "redeemer", period_start);
+        assert_eq!(&result[..8].to_string(), "9abef405");
+        assert_eq!(result.chars().count(), 70);
+        assert_ne!(
+            result,
+            calculate_settlement_address("redeemer", "issuer", period_start)
+        );
+        assert_eq!(get_address_type(&result), Some(ArcherTypes::Settlement));
+    }
 
     #[test]
     fn address_type() {
         assert_eq!(get_address_type("12345600").unwrap(), ArcherTypes::Account);
         assert_eq!(get_address_type("address1234"), None);
+        assert_eq!(get_address_type("123456"), None);
+        assert_eq!(get_address_type("12345\u{e9}0"), None);
+    }
+
+    #[test]
+    fn owner_authorization() {
+        let withdrawal = make_owner_authorization(
+            DebitAction::Withdraw,
+            "merchant",
+            "John Doe",
+            12345,
+            "",
+            0,
+            50,
+            10003456,
+            "",
+        );
+        assert_eq!(
+            withdrawal,
+            b"withdraw\nmerchant\nJohn Doe\n12345\n\n0\n50\n10003456".to_vec()
+        );
+        let transfer = make_owner_authorization(
+            DebitAction::Transfer,
+            "merchant",
+            "John Doe",
+            12345,
+            "Jane Doe",
+            54321,
+            50,
+            10003456,
+            "",
+        );
+        assert_ne!(withdrawal, transfer);
+        let redemption = make_owner_authorization(
+            DebitAction::Redeem {
+                item_id: "mug",
+                quantity: 2,
+            },
+            "merchant",
+            "John Doe",
+            12345,
+            "",
+            0,
+            50,
+            10003456,
+            "shop",
+        );
+        assert_eq!(
+            redemption,
+            b"redeem\nmerchant\nJohn Doe\n12345\n\n0\n50\n10003456\nmug\n2\nshop".to_vec()
+        );
+        let other_item = make_owner_authorization(
+            DebitAction::Redeem {
+                item_id: "pen",
+                quantity: 2,
+            },
+            "merchant",
+            "John Doe",
+            12345,
+            "",
+            0,
+            50,
+            10003456,
+            "shop",
+        );
+        assert_ne!(redemption, other_item);
+        let other_quantity = make_owner_authorization(
+            DebitAction::Redeem {
+                item_id: "mug",
+                quantity: 1,
+            },
+            "merchant",
+            "John Doe",
+            12345,
+            "",
+            0,
+            50,
+            10003456,
+            "shop",
+        );
+        assert_ne!(redemption, other_quantity);
+        let other_merchant = make_owner_authorization(
+            DebitAction::Withdraw,
+            "other",
+            "John Doe",
+            12345,
+            "",
+            0,
+            50,
+            10003456,
+            "",
+        );
+        assert_ne!(withdrawal, other_merchant);
+        let currency = make_owner_authorization(
+            DebitAction::Withdraw,
+            "merchant",
+            "John Doe",
+            12345,
+            "",
+            0,
+            50,
+            10003456,
+            "shop",
+        );
+        assert_eq!(
+            currency,
+            b"withdraw\nmerchant\nJohn Doe\n12345\n\n0\n50\n10003456\nshop".to_vec()
+        );
     }
 }
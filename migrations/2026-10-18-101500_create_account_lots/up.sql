@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+CREATE TABLE account_lots (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    number INTEGER NOT NULL,
+    lot_id BIGINT NOT NULL,
+    amount INTEGER NOT NULL,
+    earned_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP,
+    start_block_num BIGINT REFERENCES blocks(block_num),
+    end_block_num BIGINT REFERENCES blocks(block_num)
+);
+
+CREATE INDEX account_lots_account_idx ON account_lots (name, number);
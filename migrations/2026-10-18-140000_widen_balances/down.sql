@@ -0,0 +1,5 @@
+ALTER TABLE account_lots DROP CONSTRAINT account_lots_amount_positive;
+ALTER TABLE account_lots ALTER COLUMN amount TYPE INTEGER;
+
+ALTER TABLE accounts DROP CONSTRAINT accounts_balance_non_negative;
+ALTER TABLE accounts ALTER COLUMN balance TYPE INTEGER;
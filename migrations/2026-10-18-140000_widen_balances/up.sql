@@ -0,0 +1,7 @@
+-- Balances are unsigned 64-bit on chain and capped at BIGINT's maximum
+
+ALTER TABLE accounts ALTER COLUMN balance TYPE BIGINT;
+ALTER TABLE accounts ADD CONSTRAINT accounts_balance_non_negative CHECK (balance >= 0);
+
+ALTER TABLE account_lots ALTER COLUMN amount TYPE BIGINT;
+ALTER TABLE account_lots ADD CONSTRAINT account_lots_amount_positive CHECK (amount > 0);
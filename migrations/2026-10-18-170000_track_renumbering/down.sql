@@ -0,0 +1,5 @@
+DROP INDEX accounts_account_idx;
+
+ALTER TABLE accounts DROP COLUMN previous_number;
+ALTER TABLE accounts DROP COLUMN id;
+ALTER TABLE accounts ADD PRIMARY KEY (name, number);
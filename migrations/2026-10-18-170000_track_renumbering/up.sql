@@ -0,0 +1,9 @@
+-- Accounts keep one row per block range, so (name, number) can no longer be the key
+
+ALTER TABLE accounts DROP CONSTRAINT accounts_pkey;
+ALTER TABLE accounts ALTER COLUMN name SET NOT NULL;
+ALTER TABLE accounts ALTER COLUMN number SET NOT NULL;
+ALTER TABLE accounts ADD COLUMN id SERIAL PRIMARY KEY;
+ALTER TABLE accounts ADD COLUMN previous_number INTEGER;
+
+CREATE INDEX accounts_account_idx ON accounts (name, number);
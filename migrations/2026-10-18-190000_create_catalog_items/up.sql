@@ -0,0 +1,14 @@
+-- Your SQL goes here
+
+CREATE TABLE catalog_items (
+    id SERIAL PRIMARY KEY,
+    public_key VARCHAR NOT NULL,
+    item_id VARCHAR NOT NULL,
+    description VARCHAR NOT NULL,
+    point_cost BIGINT NOT NULL CHECK (point_cost > 0),
+    stock BIGINT NOT NULL CHECK (stock >= 0),
+    start_block_num BIGINT REFERENCES blocks(block_num),
+    end_block_num BIGINT REFERENCES blocks(block_num)
+);
+
+CREATE INDEX catalog_items_item_idx ON catalog_items (public_key, item_id);
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+
+CREATE TABLE vouchers (
+    id SERIAL PRIMARY KEY,
+    public_key VARCHAR NOT NULL,
+    voucher_id VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    number INTEGER NOT NULL,
+    discount BIGINT NOT NULL CHECK (discount > 0),
+    issued_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP,
+    consumed_at TIMESTAMP,
+    start_block_num BIGINT REFERENCES blocks(block_num),
+    end_block_num BIGINT REFERENCES blocks(block_num)
+);
+
+CREATE INDEX vouchers_voucher_idx ON vouchers (public_key, voucher_id);
+CREATE INDEX vouchers_account_idx ON vouchers (name, number);
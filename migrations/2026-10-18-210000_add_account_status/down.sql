@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE accounts DROP COLUMN closure_reason;
+ALTER TABLE accounts DROP COLUMN status;
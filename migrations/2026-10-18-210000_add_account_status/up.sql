@@ -0,0 +1,5 @@
+-- Your SQL goes here
+
+ALTER TABLE accounts ADD COLUMN status VARCHAR NOT NULL DEFAULT 'active'
+    CHECK (status IN ('active', 'frozen', 'closed'));
+ALTER TABLE accounts ADD COLUMN closure_reason VARCHAR;
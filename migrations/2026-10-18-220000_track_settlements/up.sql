@@ -0,0 +1,16 @@
+-- Your SQL goes here
+
+ALTER TABLE account_lots ADD COLUMN issuer VARCHAR;
+
+CREATE TABLE settlements (
+    id SERIAL PRIMARY KEY,
+    issuer VARCHAR NOT NULL,
+    redeemer VARCHAR NOT NULL,
+    period_start TIMESTAMP NOT NULL,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    start_block_num BIGINT REFERENCES blocks(block_num),
+    end_block_num BIGINT REFERENCES blocks(block_num)
+);
+
+CREATE INDEX settlements_pair_idx ON settlements (issuer, redeemer, period_start);
+CREATE INDEX settlements_period_idx ON settlements (period_start);
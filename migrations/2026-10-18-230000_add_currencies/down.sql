@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX accounts_currency_idx;
+DELETE FROM accounts WHERE currency <> '';
+ALTER TABLE account_lots DROP COLUMN currency;
+ALTER TABLE accounts DROP COLUMN currency;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+-- An account keeps one row per currency, the shared pool is the empty currency
+ALTER TABLE accounts ADD COLUMN currency VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE account_lots ADD COLUMN currency VARCHAR NOT NULL DEFAULT '';
+
+CREATE INDEX accounts_currency_idx ON accounts (name, number, currency);
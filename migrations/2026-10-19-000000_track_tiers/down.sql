@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE account_tiers;
+ALTER TABLE accounts DROP COLUMN lifetime_earned;
+ALTER TABLE accounts DROP COLUMN tier;
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+
+ALTER TABLE accounts ADD COLUMN tier VARCHAR;
+ALTER TABLE accounts ADD COLUMN lifetime_earned BIGINT NOT NULL DEFAULT 0;
+
+-- One row per tier an account reached, the current one is left open
+CREATE TABLE account_tiers (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    number INTEGER NOT NULL,
+    tier VARCHAR,
+    changed_at TIMESTAMP NOT NULL,
+    start_block_num BIGINT REFERENCES blocks(block_num),
+    end_block_num BIGINT REFERENCES blocks(block_num)
+);
+
+CREATE INDEX account_tiers_account_idx ON account_tiers (name, number);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX merchants_public_key_idx;
+
+ALTER TABLE merchants DROP COLUMN successor_public_key;
+ALTER TABLE merchants DROP COLUMN id;
+ALTER TABLE merchants ADD PRIMARY KEY (public_key);
+ALTER TABLE auth ADD CONSTRAINT auth_public_key_fkey
+    FOREIGN KEY (public_key) REFERENCES merchants(public_key);
@@ -0,0 +1,10 @@
+-- Merchants keep one row per block range and rotated keys leave a tombstone behind, so
+-- the public key can no longer be the key. Credentials follow the merchant to its new key.
+
+ALTER TABLE auth DROP CONSTRAINT auth_public_key_fkey;
+ALTER TABLE merchants DROP CONSTRAINT merchants_pkey;
+ALTER TABLE merchants ALTER COLUMN public_key SET NOT NULL;
+ALTER TABLE merchants ADD COLUMN id SERIAL PRIMARY KEY;
+ALTER TABLE merchants ADD COLUMN successor_public_key VARCHAR;
+
+CREATE INDEX merchants_public_key_idx ON merchants (public_key);
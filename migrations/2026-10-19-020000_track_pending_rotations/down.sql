@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE auth DROP COLUMN pending_batch_id;
+ALTER TABLE auth DROP COLUMN pending_private_key;
+ALTER TABLE auth DROP COLUMN pending_public_key;
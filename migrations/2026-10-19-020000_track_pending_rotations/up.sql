@@ -0,0 +1,6 @@
+-- The key a merchant is rotating to is stored before the rotation is submitted, so the
+-- credentials can still follow it when the response is lost.
+
+ALTER TABLE auth ADD COLUMN pending_public_key VARCHAR;
+ALTER TABLE auth ADD COLUMN pending_private_key VARCHAR;
+ALTER TABLE auth ADD COLUMN pending_batch_id VARCHAR;
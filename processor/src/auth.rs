@@ -0,0 +1,54 @@
+use sawtooth_sdk::processor::handler::ApplyError;
+use sawtooth_sdk::signing::secp256k1::{Secp256k1Context, Secp256k1PublicKey};
+use sawtooth_sdk::signing::{Context, PublicKey};
+
+/// Only checks the key is a compressed secp256k1 key, which is what sawtooth signers produce
+pub fn validate_public_key(public_key: &str) -> Result<(), ApplyError> {
+    match Secp256k1PublicKey::from_hex(public_key) {
+        Ok(key) if key.as_slice().len() == 33 && matches!(key.as_slice()[0], 2 | 3) => Ok(()),
+        _ => Err(ApplyError::InvalidTransaction(format!(
+            "Invalid public key {}",
+            public_key
+        ))),
+    }
+}
+
+pub fn verify_signature(
+    public_key: &str,
+    signature: &str,
+    message: &[u8],
+) -> Result<(), ApplyError> {
+    let key = Secp256k1PublicKey::from_hex(public_key)
+        .map_err(|_| ApplyError::InternalError(format!("Invalid public key {}", public_key)))?;
+    let context = Secp256k1Context::new();
+    match context.verify(signature, message, &key) {
+        Ok(true) => Ok(()),
+        _ => Err(ApplyError::InvalidTransaction(String::from(
+            "Invalid owner signature",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sawtooth_sdk::signing::secp256k1::Secp256k1PrivateKey;
+    use sawtooth_sdk::signing::PrivateKey;
+
+    const PRIVATE_KEY: &str = "2f1e7b7a130d7ba9da0068b3bb0ba1d79e7e77110302c9f746c3c2a63fe40088";
+
+    #[test]
+    fn verifies_signatures() {
+        let context = Secp256k1Context::new();
+        let private_key = Secp256k1PrivateKey::from_hex(PRIVATE_KEY).unwrap();
+        let public_key = context.get_public_key(&private_key).unwrap().as_hex();
+        let signature = context.sign(b"message", &private_key).unwrap();
+
+        assert!(validate_public_key(&public_key).is_ok());
+        assert!(verify_signature(&public_key, &signature, b"message").is_ok());
+        assert!(verify_signature(&public_key, &signature, b"other message").is_err());
+        assert!(verify_signature(&public_key, "not a signature", b"message").is_err());
+        assert!(validate_public_key(&private_key.as_hex()).is_err());
+        assert!(validate_public_key("not a key").is_err());
+    }
+}
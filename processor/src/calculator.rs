@@ -0,0 +1,172 @@
+use sawtooth_sdk::processor::handler::ApplyError;
+use std::convert::TryFrom;
+
+use super::lots::MAX_BALANCE;
+use archer_protobuf::earn_rule::{EarnRule as EarnRulePB, EarnRule_Rounding};
+
+// Purchase amounts are expressed in cents and multipliers in percent
+const CENTS_PER_UNIT: u128 = 100;
+const BASE_MULTIPLIER: u32 = 100;
+
+pub fn calculate_points(
+    rule: &EarnRulePB,
+    purchase_amount: u64,
+    category: &str,
+) -> Result<u64, ApplyError> {
+    if purchase_amount < rule.get_minimum_purchase() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Purchase amount {} is below the minimum of {}",
+            purchase_amount,
+            rule.get_minimum_purchase()
+        )));
+    }
+
+    let multiplier = rule
+        .get_multipliers()
+        .iter()
+        .find(|entry| entry.get_category() == category)
+        .map(|entry| entry.get_multiplier())
+        .unwrap_or(BASE_MULTIPLIER);
+
+    let too_many_points = || {
+        ApplyError::InvalidTransaction(String::from(
+            "Purchase earns more points than an account can hold",
+        ))
+    };
+
+    let numerator = u128::from(purchase_amount)
+        .checked_mul(u128::from(rule.get_points_per_unit()))
+        .and_then(|product| product.checked_mul(u128::from(multiplier)))
+        .ok_or_else(too_many_points)?;
+    let denominator = CENTS_PER_UNIT * u128::from(BASE_MULTIPLIER);
+
+    let points = match rule.get_rounding() {
+        EarnRule_Rounding::FLOOR => numerator / denominator,
+        EarnRule_Rounding::CEIL => numerator.div_ceil(denominator),
+        EarnRule_Rounding::NEAREST => (numerator + denominator / 2) / denominator,
+    };
+
+    u64::try_from(points)
+        .ok()
+        .filter(|points| *points <= MAX_BALANCE)
+        .ok_or_else(too_many_points)
+}
+
+pub fn calculate_expiry(rule: &EarnRulePB, earned_at: i64) -> Result<i64, ApplyError> {
+    match rule.get_points_lifetime() {
+        0 => Ok(0),
+        lifetime => earned_at
+            .checked_add(lifetime)
+            .ok_or_else(|| ApplyError::InvalidTransaction(String::from("Invalid earn timestamp"))),
+    }
+}
+
+pub fn validate_earn_rule(rule: &EarnRulePB) -> Result<(), ApplyError> {
+    if rule.get_points_per_unit() == 0 {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Earn rule must credit at least one point per unit",
+        )));
+    }
+    if rule.get_points_lifetime() < 0 {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Points lifetime cannot be negative",
+        )));
+    }
+
+    let multipliers = rule.get_multipliers();
+    for (index, entry) in multipliers.iter().enumerate() {
+        if multipliers[..index]
+            .iter()
+            .any(|other| other.get_category() == entry.get_category())
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Duplicate multiplier for category {}",
+                entry.get_category()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archer_protobuf::earn_rule::EarnRule_CategoryMultiplier;
+
+    fn make_rule(rounding: EarnRule_Rounding) -> EarnRulePB {
+        let mut multiplier = EarnRule_CategoryMultiplier::new();
+        multiplier.set_category(String::from("dessert"));
+        multiplier.set_multiplier(250);
+
+        let mut rule = EarnRulePB::new();
+        rule.set_public_key(String::from("abcdef"));
+        rule.set_points_per_unit(3);
+        rule.set_rounding(rounding);
+        rule.set_minimum_purchase(500);
+        rule.mut_multipliers().push(multiplier);
+        rule
+    }
+
+    #[test]
+    fn rounds_points() {
+        // $12.34 at 3 points per dollar is 37.02 points
+        let floor = make_rule(EarnRule_Rounding::FLOOR);
+        let ceil = make_rule(EarnRule_Rounding::CEIL);
+        let nearest = make_rule(EarnRule_Rounding::NEAREST);
+        assert_eq!(calculate_points(&floor, 1234, "").unwrap(), 37);
+        assert_eq!(calculate_points(&ceil, 1234, "").unwrap(), 38);
+        assert_eq!(calculate_points(&nearest, 1234, "").unwrap(), 37);
+        assert_eq!(calculate_points(&nearest, 1250, "").unwrap(), 38);
+    }
+
+    #[test]
+    fn applies_category_multiplier() {
+        let rule = make_rule(EarnRule_Rounding::FLOOR);
+        assert_eq!(calculate_points(&rule, 1000, "dessert").unwrap(), 75);
+        assert_eq!(calculate_points(&rule, 1000, "drinks").unwrap(), 30);
+    }
+
+    #[test]
+    fn rejects_small_purchases() {
+        let rule = make_rule(EarnRule_Rounding::FLOOR);
+        assert!(calculate_points(&rule, 499, "").is_err());
+        assert_eq!(calculate_points(&rule, 500, "").unwrap(), 15);
+    }
+
+    #[test]
+    fn rejects_overflowing_points() {
+        let mut rule = make_rule(EarnRule_Rounding::FLOOR);
+        rule.set_points_per_unit(u32::MAX);
+        assert!(calculate_points(&rule, u64::MAX, "").is_err());
+        rule.mut_multipliers()[0].set_multiplier(u32::MAX);
+        assert!(calculate_points(&rule, u64::MAX, "dessert").is_err());
+    }
+
+    #[test]
+    fn calculates_expiry() {
+        let mut rule = make_rule(EarnRule_Rounding::FLOOR);
+        assert_eq!(calculate_expiry(&rule, 1000).unwrap(), 0);
+        rule.set_points_lifetime(500);
+        assert_eq!(calculate_expiry(&rule, 1000).unwrap(), 1500);
+        assert!(calculate_expiry(&rule, i64::MAX).is_err());
+    }
+
+    #[test]
+    fn validates_rules() {
+        let mut rule = make_rule(EarnRule_Rounding::FLOOR);
+        assert!(validate_earn_rule(&rule).is_ok());
+
+        let duplicate = rule.get_multipliers()[0].clone();
+        rule.mut_multipliers().push(duplicate);
+        assert!(validate_earn_rule(&rule).is_err());
+
+        let mut rule = make_rule(EarnRule_Rounding::FLOOR);
+        rule.set_points_per_unit(0);
+        assert!(validate_earn_rule(&rule).is_err());
+
+        let mut rule = make_rule(EarnRule_Rounding::FLOOR);
+        rule.set_points_lifetime(-1);
+        assert!(validate_earn_rule(&rule).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use sawtooth_sdk::processor::handler::ApplyError;
+
+use super::lots::MAX_BALANCE;
+use archer_protobuf::catalog::CatalogItem as CatalogItemPB;
+
+const MAX_ITEM_ID_LENGTH: usize = 64;
+
+pub fn validate_catalog_item(item: &CatalogItemPB) -> Result<(), ApplyError> {
+    let id = item.get_id();
+    if id.is_empty() || id.len() > MAX_ITEM_ID_LENGTH || id.chars().any(char::is_control) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Invalid catalog item id {:?}",
+            id
+        )));
+    }
+    if item.get_point_cost() == 0 || item.get_point_cost() > MAX_BALANCE {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Invalid point cost {} for catalog item {}",
+            item.get_point_cost(),
+            id
+        )));
+    }
+    if item.get_stock() > MAX_BALANCE {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Invalid stock {} for catalog item {}",
+            item.get_stock(),
+            id
+        )));
+    }
+    Ok(())
+}
+
+/// Points debited for redeeming `quantity` units, which must still be in stock
+pub fn calculate_redemption_cost(item: &CatalogItemPB, quantity: u64) -> Result<u64, ApplyError> {
+    if quantity > item.get_stock() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Only {} units of catalog item {} are left",
+            item.get_stock(),
+            item.get_id()
+        )));
+    }
+    item.get_point_cost()
+        .checked_mul(quantity)
+        .filter(|cost| *cost <= MAX_BALANCE)
+        .ok_or_else(|| {
+            ApplyError::InvalidTransaction(String::from(
+                "Redemption costs more points than an account can hold",
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_item(point_cost: u64, stock: u64) -> CatalogItemPB {
+        let mut item = CatalogItemPB::new();
+        item.set_id(String::from("mug"));
+        item.set_description(String::from("Coffee mug"));
+        item.set_point_cost(point_cost);
+        item.set_stock(stock);
+        item
+    }
+
+    #[test]
+    fn validates_catalog_items() {
+        assert!(validate_catalog_item(&make_item(500, 0)).is_ok());
+        assert!(validate_catalog_item(&make_item(0, 10)).is_err());
+        assert!(validate_catalog_item(&make_item(MAX_BALANCE + 1, 10)).is_err());
+        assert!(validate_catalog_item(&make_item(500, MAX_BALANCE + 1)).is_err());
+
+        let mut item = make_item(500, 10);
+        item.set_id(String::new());
+        assert!(validate_catalog_item(&item).is_err());
+        item.set_id("a".repeat(MAX_ITEM_ID_LENGTH + 1));
+        assert!(validate_catalog_item(&item).is_err());
+    }
+
+    #[test]
+    fn calculates_redemption_costs() {
+        let item = make_item(500, 3);
+        assert_eq!(calculate_redemption_cost(&item, 1).unwrap(), 500);
+        assert_eq!(calculate_redemption_cost(&item, 3).unwrap(), 1500);
+        assert!(calculate_redemption_cost(&item, 4).is_err());
+
+        let item = make_item(MAX_BALANCE, u64::MAX);
+        assert!(calculate_redemption_cost(&item, 2).is_err());
+    }
+}
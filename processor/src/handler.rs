@@ -1,11 +1,12 @@
-use protobuf::parse_from_bytes;
 use sawtooth_sdk::messages::processor::TpProcessRequest;
 use sawtooth_sdk::processor::handler::{ApplyError, TransactionContext, TransactionHandler};
 
 use super::payload::ArcherPayload;
-use super::state::ArcherState;
-use archer::get_archer_prefix;
-use archer_protobuf::payload::{Payload as PayloadPB, Payload_Action};
+use super::state::{ArcherState, BalanceChange};
+use archer::{get_archer_prefix, ArcherEvent, DebitAction, FamilyVersion};
+use archer_protobuf::account::Account_Status;
+use archer_protobuf::receipt::Receipt as ReceiptPB;
+use std::convert::TryFrom;
 
 pub struct ArcherTransactionHandler {
     family_name: String,
@@ -17,7 +18,10 @@ impl ArcherTransactionHandler {
     pub fn new(name: &str) -> ArcherTransactionHandler {
         ArcherTransactionHandler {
             family_name: String::from(name),
-            family_versions: vec![String::from("1.0")],
+            family_versions: FamilyVersion::ALL
+                .iter()
+                .map(|version| String::from(version.as_str()))
+                .collect(),
             namespaces: vec![get_archer_prefix()],
         }
     }
@@ -29,41 +33,31 @@ impl TransactionHandler for ArcherTransactionHandler {
         request: &TpProcessRequest,
         context: &mut dyn TransactionContext,
     ) -> Result<(), ApplyError> {
-        let header = &request.header;
-        let _signer = match &header.as_ref() {
-            Some(s) => &s.signer_public_key,
+        let header = match request.header.as_ref() {
+            Some(header) => header,
             None => {
                 return Err(ApplyError::InvalidTransaction(String::from(
                     "Invalid header",
                 )))
             }
         };
+        let signer = &header.signer_public_key;
 
         let mut state = ArcherState::new(context);
 
-        let payload = ArcherPayload::new(&request.payload)?;
+        let family_version = FamilyVersion::try_from(header.get_family_version())
+            .map_err(ApplyError::InvalidTransaction)?;
+        let payload = ArcherPayload::new(&request.payload, family_version)?;
 
-        let data: PayloadPB =
-            parse_from_bytes(&(payload.data()?)).expect("Error converting bytes to action");
+        // A rotated key signs nothing anymore, the merchant carries on under its successor
+        if let Some(successor) = state.get_successor(signer)? {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Signer {} was rotated to {}",
+                signer, successor
+            )));
+        }
 
-        match payload.action() {
-            Payload_Action::DEPOSIT => {
-                state.update_balance(data.get_name(), data.get_number(), data.get_amount())?;
-            }
-            Payload_Action::WITHDRAW => {
-                state.update_balance(data.get_name(), data.get_number(), -data.get_amount())?;
-            }
-            Payload_Action::UPDATE_NUMBER => {
-                state.update_number(data.get_name(), data.get_number(), data.get_new_number())?;
-            }
-            Payload_Action::ADD_ACCOUNT => {
-                state.set_account(data.get_name(), data.get_number())?;
-            }
-            Payload_Action::ADD_MERCHANT => {
-                state.set_merchant(data.get_public_key(), data.get_name(), data.get_timestamp())?;
-            }
-        };
-        Ok(())
+        apply_payload(&mut state, signer, payload)
     }
 
     fn family_name(&self) -> String {
@@ -78,3 +72,2165 @@ impl TransactionHandler for ArcherTransactionHandler {
         self.namespaces.clone()
     }
 }
+
+fn apply_payload(
+    state: &mut ArcherState,
+    signer: &str,
+    payload: ArcherPayload,
+) -> Result<(), ApplyError> {
+    if payload.requires_merchant() && !state.is_merchant(signer)? {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Signer {} is not a registered merchant",
+            signer
+        )));
+    }
+    if let Some(debit) = payload.owner_debit() {
+        if !state.is_merchant(signer)? && !state.is_owner(signer, &debit.name, debit.number)? {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Signer {} is neither a registered merchant nor the owner of {} {}",
+                signer, debit.name, debit.number
+            )));
+        }
+    }
+    if payload.requires_merchant_or_admin()
+        && !state.is_merchant(signer)?
+        && !state.is_admin(signer)?
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Signer {} is neither a registered merchant nor an administrator",
+            signer
+        )));
+    }
+    if payload.requires_admin() && !state.is_admin(signer)? {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Signer {} is not an administrator",
+            signer
+        )));
+    }
+
+    match payload {
+        ArcherPayload::Atomic(operations) => {
+            // A failed operation fails the transaction, so none of the earlier writes land
+            for operation in operations {
+                apply_payload(state, signer, operation)?;
+            }
+        }
+        ArcherPayload::Deposit {
+            name,
+            number,
+            currency,
+            amount,
+            earned_at,
+            expires_at,
+        } => {
+            let receipt = state.update_balance(
+                signer,
+                &name,
+                number,
+                &currency,
+                BalanceChange::Deposit {
+                    amount,
+                    earned_at,
+                    expires_at,
+                },
+            )?;
+            state.add_event(
+                ArcherEvent::PointsDeposited,
+                account_attributes(signer, amount, &receipt),
+            )?;
+            state.add_receipt(&receipt)?;
+        }
+        ArcherPayload::Withdraw(debit) => {
+            state.authorize_owner(signer, DebitAction::Withdraw, &debit, "", 0)?;
+            let receipt = state.update_balance(
+                signer,
+                &debit.name,
+                debit.number,
+                &debit.currency,
+                BalanceChange::Withdrawal {
+                    amount: debit.amount,
+                    timestamp: debit.timestamp,
+                },
+            )?;
+            state.add_event(
+                ArcherEvent::PointsWithdrawn,
+                account_attributes(signer, debit.amount, &receipt),
+            )?;
+            state.add_receipt(&receipt)?;
+        }
+        ArcherPayload::UpdateNumber {
+            name,
+            number,
+            new_number,
+        } => {
+            state.update_number(&name, number, new_number)?;
+        }
+        ArcherPayload::AddAccount {
+            name,
+            number,
+            owner_public_key,
+        } => {
+            state.set_account(&name, number, owner_public_key.as_deref())?;
+        }
+        ArcherPayload::AddMerchant { name, timestamp } => {
+            state.set_merchant(signer, &name, timestamp)?;
+            state.add_event(
+                ArcherEvent::MerchantRegistered,
+                vec![
+                    (String::from("merchant"), String::from(signer)),
+                    (String::from("name"), name),
+                ],
+            )?;
+        }
+        ArcherPayload::Transfer {
+            debit,
+            recipient_name,
+            recipient_number,
+        } => {
+            state.authorize_owner(
+                signer,
+                DebitAction::Transfer,
+                &debit,
+                &recipient_name,
+                recipient_number,
+            )?;
+            let (receipt, recipient_receipt) = state.transfer(
+                signer,
+                &debit.name,
+                debit.number,
+                &recipient_name,
+                recipient_number,
+                &debit.currency,
+                debit.amount,
+                debit.timestamp,
+            )?;
+            let mut attributes = account_attributes(signer, debit.amount, &receipt);
+            attributes.push((String::from("recipient_name"), recipient_name));
+            attributes.push((
+                String::from("recipient_number"),
+                recipient_number.to_string(),
+            ));
+            attributes.push((
+                String::from("recipient_balance"),
+                recipient_receipt.get_balance().to_string(),
+            ));
+            state.add_event(ArcherEvent::PointsTransferred, attributes)?;
+            state.add_receipt(&receipt)?;
+            state.add_receipt(&recipient_receipt)?;
+        }
+        ArcherPayload::SetEarnRule(rule) => {
+            state.set_earn_rule(signer, &rule)?;
+        }
+        ArcherPayload::Earn {
+            name,
+            number,
+            currency,
+            purchase_amount,
+            category,
+            timestamp,
+        } => {
+            let receipt = state.earn(
+                signer,
+                &name,
+                number,
+                &currency,
+                purchase_amount,
+                &category,
+                timestamp,
+            )?;
+            state.add_event(
+                ArcherEvent::PointsEarned,
+                account_attributes(signer, receipt.get_points(), &receipt),
+            )?;
+            if receipt.get_tier_changed() {
+                state.add_event(
+                    ArcherEvent::TierChanged,
+                    vec![
+                        (String::from("name"), String::from(receipt.get_name())),
+                        (String::from("number"), receipt.get_number().to_string()),
+                        (String::from("tier"), String::from(receipt.get_tier())),
+                    ],
+                )?;
+            }
+            state.add_receipt(&receipt)?;
+        }
+        ArcherPayload::Expire {
+            name,
+            number,
+            currency,
+            timestamp,
+        } => {
+            let (expired, receipt) = state.expire(&name, number, &currency, timestamp)?;
+            if expired > 0 {
+                state.add_event(
+                    ArcherEvent::PointsExpired,
+                    account_attributes(signer, expired, &receipt),
+                )?;
+            }
+            state.add_receipt(&receipt)?;
+        }
+        ArcherPayload::SetCatalogItem(item) => {
+            state.set_catalog_item(signer, &item)?;
+        }
+        ArcherPayload::Redeem {
+            debit,
+            item_id,
+            quantity,
+        } => {
+            let action = DebitAction::Redeem {
+                item_id: &item_id,
+                quantity,
+            };
+            state.authorize_owner(signer, action, &debit, "", 0)?;
+            let receipt = state.redeem(
+                signer,
+                &debit.name,
+                debit.number,
+                &debit.currency,
+                &item_id,
+                quantity,
+                debit.amount,
+                debit.timestamp,
+            )?;
+            let mut attributes = account_attributes(signer, debit.amount, &receipt);
+            attributes.push((String::from("item_id"), item_id));
+            attributes.push((String::from("quantity"), quantity.to_string()));
+            state.add_event(ArcherEvent::PointsRedeemed, attributes)?;
+            state.add_receipt(&receipt)?;
+        }
+        ArcherPayload::IssueVoucher {
+            name,
+            number,
+            voucher_id,
+            discount,
+            issued_at,
+            expires_at,
+        } => {
+            state.issue_voucher(
+                signer,
+                &name,
+                number,
+                &voucher_id,
+                discount,
+                issued_at,
+                expires_at,
+            )?;
+        }
+        ArcherPayload::ConsumeVoucher {
+            name,
+            number,
+            voucher_id,
+            timestamp,
+        } => {
+            state.consume_voucher(signer, &name, number, &voucher_id, timestamp)?;
+        }
+        ArcherPayload::Freeze { name, number } => {
+            state.set_status(&name, number, Account_Status::FROZEN)?;
+        }
+        ArcherPayload::Unfreeze { name, number } => {
+            state.set_status(&name, number, Account_Status::ACTIVE)?;
+        }
+        ArcherPayload::Close {
+            name,
+            number,
+            closure_reason,
+        } => {
+            state.close(&name, number, closure_reason.as_deref())?;
+        }
+        ArcherPayload::RotateMerchantKey { new_public_key } => {
+            state.rotate_merchant_key(signer, &new_public_key)?;
+            state.add_event(
+                ArcherEvent::MerchantKeyRotated,
+                vec![
+                    (String::from("merchant"), String::from(signer)),
+                    (String::from("successor"), new_public_key),
+                ],
+            )?;
+        }
+    };
+    Ok(())
+}
+
+fn account_attributes(merchant: &str, amount: u64, receipt: &ReceiptPB) -> Vec<(String, String)> {
+    vec![
+        (String::from("name"), String::from(receipt.get_name())),
+        (String::from("number"), receipt.get_number().to_string()),
+        (String::from("merchant"), String::from(merchant)),
+        (
+            String::from("currency"),
+            String::from(receipt.get_currency()),
+        ),
+        (String::from("amount"), amount.to_string()),
+        (String::from("balance"), receipt.get_balance().to_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lots::{MAX_BALANCE, MAX_EXPIRY_SKEW};
+    use crate::testing::{
+        payload_addresses, KeyPair, MockTransactionContext, RecordedEvent, ADMIN, MERCHANT,
+        OTHER_MERCHANT, OWNER, STRANGER,
+    };
+    use crate::velocity::MAX_VELOCITY_SKEW;
+    use archer::{
+        calculate_account_address, calculate_catalog_item_address, calculate_merchant_address,
+        calculate_setting_address, calculate_settlement_address, ADMIN_KEYS_SETTING, TIERS_SETTING,
+        VELOCITY_LIMITS_SETTING,
+    };
+    use archer_protobuf::account::AccountContainer;
+    use archer_protobuf::catalog::{CatalogItem as CatalogItemPB, CatalogItemContainer};
+    use archer_protobuf::earn_rule::{EarnRule as EarnRulePB, EarnRule_Rounding};
+    use archer_protobuf::merchant::MerchantContainer;
+    use archer_protobuf::payload::{Payload as PayloadPB, PayloadEnvelope, Payload_Action};
+    use archer_protobuf::settlement::SettlementContainer;
+    use proptest::prelude::*;
+    use protobuf::parse_from_bytes;
+    use protobuf::{Message, RepeatedField};
+    use sawtooth_sdk::messages::setting::{Setting, Setting_Entry};
+
+    const MERCHANT_KEY: &str = MERCHANT.public_key;
+    const OTHER_MERCHANT_KEY: &str = OTHER_MERCHANT.public_key;
+    const STRANGER_KEY: &str = STRANGER.public_key;
+    const ADMIN_KEY: &str = ADMIN.public_key;
+
+    fn make_payload(action: Payload_Action, name: &str) -> PayloadPB {
+        let mut payload = PayloadPB::new();
+        payload.set_action(action);
+        payload.set_name(String::from(name));
+        payload.set_timestamp(10000);
+        payload
+    }
+
+    fn make_balance_payload(action: Payload_Action, amount: u64) -> PayloadPB {
+        let mut payload = make_payload(action, "John Doe");
+        payload.set_number(12345);
+        payload.set_amount(amount);
+        payload
+    }
+
+    fn apply(
+        context: &mut MockTransactionContext,
+        signer: &str,
+        payload: PayloadPB,
+    ) -> Result<(), ApplyError> {
+        apply_version(context, signer, payload, FamilyVersion::V2)
+    }
+
+    fn apply_version(
+        context: &mut MockTransactionContext,
+        signer: &str,
+        payload: PayloadPB,
+        family_version: FamilyVersion,
+    ) -> Result<(), ApplyError> {
+        match family_version {
+            FamilyVersion::V1 => {
+                let payload_data = payload.write_to_bytes().unwrap();
+                process(context, signer, family_version, payload_data)
+            }
+            FamilyVersion::V2 => apply_atomic(context, signer, vec![payload]),
+        }
+    }
+
+    fn apply_atomic(
+        context: &mut MockTransactionContext,
+        signer: &str,
+        payloads: Vec<PayloadPB>,
+    ) -> Result<(), ApplyError> {
+        let mut envelope = PayloadEnvelope::new();
+        envelope.set_payloads(RepeatedField::from_vec(payloads));
+        let payload_data = envelope.write_to_bytes().unwrap();
+        process(context, signer, FamilyVersion::V2, payload_data)
+    }
+
+    fn process(
+        context: &mut MockTransactionContext,
+        signer: &str,
+        family_version: FamilyVersion,
+        payload_data: Vec<u8>,
+    ) -> Result<(), ApplyError> {
+        let addresses = payload_addresses(signer, family_version, &payload_data);
+        context.process(&KeyPair::find(signer).make_request(
+            family_version,
+            payload_data,
+            addresses,
+        ))
+    }
+
+    fn add_account(context: &mut MockTransactionContext, name: &str, number: u32, owner: &str) {
+        let mut payload = make_payload(Payload_Action::ADD_ACCOUNT, name);
+        payload.set_number(number);
+        payload.set_public_key(String::from(owner));
+        apply(context, MERCHANT_KEY, payload).unwrap();
+    }
+
+    fn setup_account(context: &mut MockTransactionContext, owner: &str) {
+        apply(
+            context,
+            MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Shop"),
+        )
+        .unwrap();
+        add_account(context, "John Doe", 12345, owner);
+    }
+
+    /// Co-signs the payload for `MERCHANT_KEY` to submit
+    fn owner_sign(payload: &mut PayloadPB) {
+        let action = match payload.get_action() {
+            Payload_Action::TRANSFER => DebitAction::Transfer,
+            Payload_Action::REDEEM => DebitAction::Redeem {
+                item_id: payload.get_item_id(),
+                quantity: payload.get_quantity(),
+            },
+            _ => DebitAction::Withdraw,
+        };
+        let message = archer::make_owner_authorization(
+            action,
+            MERCHANT_KEY,
+            payload.get_name(),
+            payload.get_number(),
+            payload.get_recipient_name(),
+            payload.get_recipient_number(),
+            payload.get_amount(),
+            payload.get_timestamp(),
+            payload.get_currency(),
+        );
+        payload.set_owner_signature(OWNER.sign(&message));
+    }
+
+    fn get_balance(context: &MockTransactionContext) -> u64 {
+        let state = context.state.borrow();
+        let data = state.get(&calculate_account_address("John Doe")).unwrap();
+        let container: AccountContainer = parse_from_bytes(data).unwrap();
+        container.get_entries()[0].get_balance()
+    }
+
+    #[test]
+    fn accepts_balance_updates_from_merchants() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 20),
+        )
+        .unwrap();
+
+        assert_eq!(get_balance(&context), 30);
+    }
+
+    #[test]
+    fn rejects_balance_updates_from_unregistered_signers() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+
+        let deposit = apply(
+            &mut context,
+            STRANGER_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        );
+        assert!(matches!(deposit, Err(ApplyError::InvalidTransaction(_))));
+
+        let withdrawal = apply(
+            &mut context,
+            STRANGER_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 20),
+        );
+        assert!(matches!(withdrawal, Err(ApplyError::InvalidTransaction(_))));
+
+        assert_eq!(get_balance(&context), 0);
+    }
+
+    #[test]
+    fn rejects_accounts_from_unregistered_signers() {
+        let mut context = MockTransactionContext::default();
+        let mut payload = make_payload(Payload_Action::ADD_ACCOUNT, "John Doe");
+        payload.set_number(12345);
+
+        let result = apply(&mut context, STRANGER_KEY, payload);
+
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert!(context.state.borrow().is_empty());
+    }
+
+    #[test]
+    fn requires_owner_signature_for_withdrawals() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, OWNER.public_key);
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+
+        let mut withdrawal = make_balance_payload(Payload_Action::WITHDRAW, 20);
+        withdrawal.set_timestamp(10003456);
+        let unsigned = apply(&mut context, MERCHANT_KEY, withdrawal.clone());
+        assert!(matches!(unsigned, Err(ApplyError::InvalidTransaction(_))));
+
+        owner_sign(&mut withdrawal);
+        apply(&mut context, MERCHANT_KEY, withdrawal.clone()).unwrap();
+        assert_eq!(get_balance(&context), 30);
+
+        let replayed = apply(&mut context, MERCHANT_KEY, withdrawal);
+        assert!(matches!(replayed, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 30);
+
+        // Owners sign their own withdrawals, which no one else can submit unsigned
+        let withdrawal = make_balance_payload(Payload_Action::WITHDRAW, 10);
+        let result = apply(&mut context, STRANGER_KEY, withdrawal.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        apply(&mut context, OWNER.public_key, withdrawal).unwrap();
+        assert_eq!(get_balance(&context), 20);
+    }
+
+    #[test]
+    fn rejects_signatures_over_other_amounts() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, OWNER.public_key);
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+
+        let mut withdrawal = make_balance_payload(Payload_Action::WITHDRAW, 20);
+        withdrawal.set_timestamp(10003456);
+        owner_sign(&mut withdrawal);
+        withdrawal.set_amount(50);
+
+        let result = apply(&mut context, MERCHANT_KEY, withdrawal);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 50);
+    }
+
+    #[test]
+    fn rejects_signatures_for_other_actions_and_merchants() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, OWNER.public_key);
+        apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Other shop"),
+        )
+        .unwrap();
+        publish_item(&mut context, MERCHANT_KEY, 20, 1);
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+
+        let mut withdrawal = make_balance_payload(Payload_Action::WITHDRAW, 20);
+        withdrawal.set_timestamp(10003456);
+        owner_sign(&mut withdrawal);
+
+        let mut redemption = make_redeem_payload(1, 20);
+        redemption.set_timestamp(10003456);
+        redemption.set_owner_signature(String::from(withdrawal.get_owner_signature()));
+        let result = apply(&mut context, MERCHANT_KEY, redemption);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let result = apply(&mut context, OTHER_MERCHANT_KEY, withdrawal.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 50);
+
+        apply(&mut context, MERCHANT_KEY, withdrawal).unwrap();
+        assert_eq!(get_balance(&context), 30);
+    }
+
+    #[test]
+    fn accepts_transfers_cosigned_by_owner() {
+        let mut context = MockTransactionContext::default();
+        let owner = OWNER.public_key;
+        setup_account(&mut context, owner);
+        add_account(&mut context, "Jane Doe", 54321, "");
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+
+        let mut transfer = make_balance_payload(Payload_Action::TRANSFER, 20);
+        transfer.set_recipient_name(String::from("Jane Doe"));
+        transfer.set_recipient_number(54321);
+
+        let result = apply(&mut context, MERCHANT_KEY, transfer.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        // Owners move their own points without a merchant
+        apply(&mut context, owner, transfer.clone()).unwrap();
+        assert_eq!(get_balance(&context), 30);
+
+        transfer.set_timestamp(10003456);
+        owner_sign(&mut transfer);
+        apply(&mut context, MERCHANT_KEY, transfer).unwrap();
+        assert_eq!(get_balance(&context), 10);
+    }
+
+    #[test]
+    fn rejects_transfers_from_unregistered_signers() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        add_account(&mut context, "Jane Doe", 54321, "");
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+
+        // The account has no owner to sign for it
+        let mut transfer = make_balance_payload(Payload_Action::TRANSFER, 20);
+        transfer.set_recipient_name(String::from("Jane Doe"));
+        transfer.set_recipient_number(54321);
+        let result = apply(&mut context, STRANGER_KEY, transfer);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 50);
+    }
+
+    #[test]
+    fn rejects_replayed_accounts_and_merchants() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+
+        let merchant = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Shop"),
+        );
+        assert!(matches!(merchant, Err(ApplyError::InvalidTransaction(_))));
+
+        let mut payload = make_payload(Payload_Action::ADD_ACCOUNT, "John Doe");
+        payload.set_number(12345);
+        let account = apply(&mut context, MERCHANT_KEY, payload);
+        assert!(matches!(account, Err(ApplyError::InvalidTransaction(_))));
+
+        let state = context.state.borrow();
+        let data = state.get(&calculate_account_address("John Doe")).unwrap();
+        let accounts: AccountContainer = parse_from_bytes(data).unwrap();
+        assert_eq!(accounts.get_entries().len(), 1);
+        let data = state
+            .get(&calculate_merchant_address(MERCHANT_KEY))
+            .unwrap();
+        let merchants: MerchantContainer = parse_from_bytes(data).unwrap();
+        assert_eq!(merchants.get_entries().len(), 1);
+    }
+
+    #[test]
+    fn accepts_accounts_sharing_a_name() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        add_account(&mut context, "John Doe", 54321, "");
+
+        let state = context.state.borrow();
+        let data = state.get(&calculate_account_address("John Doe")).unwrap();
+        let accounts: AccountContainer = parse_from_bytes(data).unwrap();
+        assert_eq!(accounts.get_entries().len(), 2);
+    }
+
+    #[test]
+    fn rejects_deposits_that_overflow() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, u64::MAX),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, MAX_BALANCE),
+        )
+        .unwrap();
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 1),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), MAX_BALANCE);
+    }
+
+    #[test]
+    fn rejects_updates_to_missing_accounts() {
+        let mut context = MockTransactionContext::default();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Shop"),
+        )
+        .unwrap();
+
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        add_account(&mut context, "John Doe", 54321, "");
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 50),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let mut payload = make_payload(Payload_Action::UPDATE_NUMBER, "John Doe");
+        payload.set_number(12345);
+        payload.set_new_number(23456);
+        let result = apply(&mut context, MERCHANT_KEY, payload);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    fn make_update_number_payload(number: u32, new_number: u32) -> PayloadPB {
+        let mut payload = make_payload(Payload_Action::UPDATE_NUMBER, "John Doe");
+        payload.set_number(number);
+        payload.set_new_number(new_number);
+        payload
+    }
+
+    #[test]
+    fn persists_renumbered_accounts() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_update_number_payload(12345, 23456),
+        )
+        .unwrap();
+
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let mut payload = make_balance_payload(Payload_Action::DEPOSIT, 25);
+        payload.set_number(23456);
+        apply(&mut context, MERCHANT_KEY, payload).unwrap();
+
+        let state = context.state.borrow();
+        let data = state.get(&calculate_account_address("John Doe")).unwrap();
+        let container: AccountContainer = parse_from_bytes(data).unwrap();
+        let account = &container.get_entries()[0];
+        assert_eq!(account.get_number(), 23456);
+        assert_eq!(account.get_previous_numbers(), &[12345]);
+        assert_eq!(account.get_balance(), 75);
+    }
+
+    #[test]
+    fn rejects_renumbering_onto_used_numbers() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        add_account(&mut context, "John Doe", 54321, "");
+
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_update_number_payload(12345, 54321),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_update_number_payload(12345, 23456),
+        )
+        .unwrap();
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_update_number_payload(54321, 12345),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        // Nor can a new account take the retired number
+        let mut payload = make_payload(Payload_Action::ADD_ACCOUNT, "John Doe");
+        payload.set_number(12345);
+        let result = apply(&mut context, MERCHANT_KEY, payload);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let result = apply(
+            &mut context,
+            STRANGER_KEY,
+            make_update_number_payload(23456, 34567),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    #[test]
+    fn reports_malformed_state_as_internal_error() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        context.state.borrow_mut().insert(
+            calculate_account_address("John Doe"),
+            vec![0xff, 0xff, 0xff],
+        );
+
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        );
+        assert!(matches!(result, Err(ApplyError::InternalError(_))));
+
+        context.state.borrow_mut().insert(
+            calculate_merchant_address(MERCHANT_KEY),
+            vec![0xff, 0xff, 0xff],
+        );
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        );
+        assert!(matches!(result, Err(ApplyError::InternalError(_))));
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        let mut context = MockTransactionContext::default();
+        let handler = ArcherTransactionHandler::new("archer");
+
+        let mut request = TpProcessRequest::new();
+        request.set_payload(vec![0xff, 0xff, 0xff]);
+        let result = handler.apply(&request, &mut context);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let payload_data = vec![0xff, 0xff, 0xff];
+        let addresses = payload_addresses(MERCHANT_KEY, FamilyVersion::V1, &payload_data);
+        let request = MERCHANT.make_request(FamilyVersion::V1, payload_data, addresses);
+        let result = handler.apply(&request, &mut context);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    #[test]
+    fn applies_to_accounts_of_the_baseline_schema() {
+        let mut context = MockTransactionContext::default();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Shop"),
+        )
+        .unwrap();
+        // John Doe 12345 holding 50, balance being a sint32 on tag 3
+        context.state.borrow_mut().insert(
+            calculate_account_address("John Doe"),
+            b"\x0a\x0f\x0a\x08John Doe\x10\xb9\x60\x18\x64".to_vec(),
+        );
+
+        // WITHDRAW of 20 at 10000, amount being a sint32 on tag 5
+        let payload_data = b"\x08\x01\x12\x08John Doe\x18\xb9\x60\x28\x28\x38\xa0\x9c\x01";
+        process(
+            &mut context,
+            MERCHANT_KEY,
+            FamilyVersion::V1,
+            payload_data.to_vec(),
+        )
+        .unwrap();
+        assert_eq!(get_balance(&context), 30);
+
+        let state = context.state.borrow();
+        let data = state.get(&calculate_account_address("John Doe")).unwrap();
+        let container: AccountContainer = parse_from_bytes(data).unwrap();
+        assert!(!container.get_entries()[0].has_legacy_balance());
+    }
+
+    #[test]
+    fn applies_payloads_of_baseline_clients() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+
+        // DEPOSIT of 50 and WITHDRAW of 20, undated with amounts being a sint32 on tag 5
+        for payload_data in [
+            &b"\x08\x00\x12\x08John Doe\x18\xb9\x60\x28\x64"[..],
+            &b"\x08\x01\x12\x08John Doe\x18\xb9\x60\x28\x28"[..],
+        ]
+        .iter()
+        {
+            process(
+                &mut context,
+                MERCHANT_KEY,
+                FamilyVersion::V1,
+                payload_data.to_vec(),
+            )
+            .unwrap();
+        }
+        assert_eq!(get_balance(&context), 30);
+    }
+
+    #[test]
+    fn applies_legacy_payloads() {
+        let mut context = MockTransactionContext::default();
+        apply_version(
+            &mut context,
+            MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Shop"),
+            FamilyVersion::V1,
+        )
+        .unwrap();
+        let mut payload = make_payload(Payload_Action::ADD_ACCOUNT, "John Doe");
+        payload.set_number(12345);
+        apply_version(&mut context, MERCHANT_KEY, payload, FamilyVersion::V1).unwrap();
+
+        apply_version(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+            FamilyVersion::V1,
+        )
+        .unwrap();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 20),
+        )
+        .unwrap();
+        apply_version(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 10),
+            FamilyVersion::V1,
+        )
+        .unwrap();
+        assert_eq!(get_balance(&context), 20);
+    }
+
+    #[test]
+    fn rejects_unknown_family_versions() {
+        let mut context = MockTransactionContext::default();
+        let handler = ArcherTransactionHandler::new("archer");
+        assert_eq!(handler.family_versions(), vec!["1.0", "2.0"]);
+
+        let payload_data = make_payload(Payload_Action::ADD_MERCHANT, "Shop")
+            .write_to_bytes()
+            .unwrap();
+        let addresses = payload_addresses(MERCHANT_KEY, FamilyVersion::V1, &payload_data);
+        let mut request = MERCHANT.make_request(FamilyVersion::V1, payload_data, addresses);
+        request.mut_header().set_family_version(String::from("3.0"));
+        let result = handler.apply(&request, &mut context);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    #[test]
+    fn applies_atomic_operations_in_order() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 100),
+        )
+        .unwrap();
+
+        apply_atomic(
+            &mut context,
+            MERCHANT_KEY,
+            vec![
+                make_balance_payload(Payload_Action::WITHDRAW, 120),
+                make_balance_payload(Payload_Action::DEPOSIT, 50),
+            ],
+        )
+        .unwrap_err();
+
+        apply_atomic(
+            &mut context,
+            MERCHANT_KEY,
+            vec![
+                make_balance_payload(Payload_Action::DEPOSIT, 50),
+                make_balance_payload(Payload_Action::WITHDRAW, 120),
+            ],
+        )
+        .unwrap();
+        assert_eq!(get_balance(&context), 30);
+    }
+
+    #[test]
+    fn checks_each_atomic_operation_signer() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+
+        let result = apply_atomic(
+            &mut context,
+            STRANGER_KEY,
+            vec![
+                make_balance_payload(Payload_Action::EXPIRE, 1),
+                make_balance_payload(Payload_Action::DEPOSIT, 50),
+            ],
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    fn publish_item(
+        context: &mut MockTransactionContext,
+        signer: &str,
+        point_cost: u64,
+        stock: u64,
+    ) {
+        let mut item = CatalogItemPB::new();
+        item.set_public_key(String::new());
+        item.set_id(String::from("mug"));
+        item.set_description(String::from("Coffee mug"));
+        item.set_point_cost(point_cost);
+        item.set_stock(stock);
+        let mut payload = make_payload(Payload_Action::SET_CATALOG_ITEM, "");
+        payload.set_catalog_item(item);
+        apply(context, signer, payload).unwrap();
+    }
+
+    fn make_redeem_payload(quantity: u64, points: u64) -> PayloadPB {
+        let mut payload = make_balance_payload(Payload_Action::REDEEM, points);
+        payload.set_item_id(String::from("mug"));
+        payload.set_quantity(quantity);
+        payload
+    }
+
+    fn get_stock(context: &MockTransactionContext) -> u64 {
+        let state = context.state.borrow();
+        let data = state
+            .get(&calculate_catalog_item_address(MERCHANT_KEY, "mug"))
+            .unwrap();
+        let container: CatalogItemContainer = parse_from_bytes(data).unwrap();
+        container.get_entries()[0].get_stock()
+    }
+
+    #[test]
+    fn redeems_catalog_items() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        publish_item(&mut context, MERCHANT_KEY, 200, 3);
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 1000),
+        )
+        .unwrap();
+
+        apply(&mut context, MERCHANT_KEY, make_redeem_payload(2, 400)).unwrap();
+        assert_eq!(get_balance(&context), 600);
+        assert_eq!(get_stock(&context), 1);
+
+        let result = apply(&mut context, MERCHANT_KEY, make_redeem_payload(2, 400));
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        // Republishing restocks and reprices the item
+        publish_item(&mut context, MERCHANT_KEY, 300, 5);
+        let result = apply(&mut context, MERCHANT_KEY, make_redeem_payload(1, 200));
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        apply(&mut context, MERCHANT_KEY, make_redeem_payload(1, 300)).unwrap();
+        assert_eq!(get_balance(&context), 300);
+        assert_eq!(get_stock(&context), 4);
+
+        let result = apply(&mut context, MERCHANT_KEY, make_redeem_payload(2, 600));
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    #[test]
+    fn rejects_redemption_signatures_for_other_items() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, OWNER.public_key);
+        publish_item(&mut context, MERCHANT_KEY, 10, 5);
+        let mut item = CatalogItemPB::new();
+        item.set_public_key(String::new());
+        item.set_id(String::from("pen"));
+        item.set_description(String::from("Ballpoint pen"));
+        item.set_point_cost(20);
+        item.set_stock(5);
+        let mut payload = make_payload(Payload_Action::SET_CATALOG_ITEM, "");
+        payload.set_catalog_item(item);
+        apply(&mut context, MERCHANT_KEY, payload).unwrap();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+
+        let mut redemption = make_redeem_payload(2, 20);
+        redemption.set_timestamp(10003456);
+        owner_sign(&mut redemption);
+
+        let mut other_item = redemption.clone();
+        other_item.set_item_id(String::from("pen"));
+        other_item.set_quantity(1);
+        let result = apply(&mut context, MERCHANT_KEY, other_item);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        // Repriced so a single mug costs what the owner agreed to pay for two
+        publish_item(&mut context, MERCHANT_KEY, 20, 5);
+        let mut other_quantity = redemption.clone();
+        other_quantity.set_quantity(1);
+        let result = apply(&mut context, MERCHANT_KEY, other_quantity);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 50);
+
+        publish_item(&mut context, MERCHANT_KEY, 10, 5);
+        apply(&mut context, MERCHANT_KEY, redemption).unwrap();
+        assert_eq!(get_balance(&context), 30);
+    }
+
+    #[test]
+    fn rejects_catalog_items_from_unregistered_signers() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+
+        let mut item = CatalogItemPB::new();
+        item.set_public_key(String::new());
+        item.set_id(String::from("mug"));
+        item.set_description(String::from("Coffee mug"));
+        item.set_point_cost(200);
+        item.set_stock(3);
+        let mut payload = make_payload(Payload_Action::SET_CATALOG_ITEM, "");
+        payload.set_catalog_item(item);
+        let result = apply(&mut context, STRANGER_KEY, payload);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let result = apply(&mut context, MERCHANT_KEY, make_redeem_payload(1, 200));
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    fn make_voucher_payload(action: Payload_Action, name: &str) -> PayloadPB {
+        let mut payload = make_payload(action, name);
+        payload.set_number(12345);
+        payload.set_voucher_id(String::from("spring-10"));
+        payload
+    }
+
+    fn issue_voucher(
+        context: &mut MockTransactionContext,
+        expires_at: i64,
+    ) -> Result<(), ApplyError> {
+        let mut payload = make_voucher_payload(Payload_Action::ISSUE_VOUCHER, "John Doe");
+        payload.set_discount(1000);
+        payload.set_expires_at(expires_at);
+        apply(context, MERCHANT_KEY, payload)
+    }
+
+    #[test]
+    fn consumes_vouchers_once() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        issue_voucher(&mut context, 20000).unwrap();
+        let result = issue_voucher(&mut context, 20000);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let payload = make_voucher_payload(Payload_Action::CONSUME_VOUCHER, "Jane Doe");
+        let result = apply(&mut context, MERCHANT_KEY, payload);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let payload = make_voucher_payload(Payload_Action::CONSUME_VOUCHER, "John Doe");
+        let result = apply(&mut context, STRANGER_KEY, payload.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        apply(&mut context, MERCHANT_KEY, payload.clone()).unwrap();
+        let result = apply(&mut context, MERCHANT_KEY, payload);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        // Consumed vouchers keep their id from being issued again
+        let result = issue_voucher(&mut context, 20000);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    #[test]
+    fn rejects_expired_vouchers() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        issue_voucher(&mut context, 10000).unwrap_err();
+        issue_voucher(&mut context, 10001).unwrap();
+
+        let mut payload = make_voucher_payload(Payload_Action::CONSUME_VOUCHER, "John Doe");
+        payload.set_timestamp(10001);
+        let result = apply(&mut context, MERCHANT_KEY, payload);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    #[test]
+    fn rejects_vouchers_for_missing_accounts() {
+        let mut context = MockTransactionContext::default();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Shop"),
+        )
+        .unwrap();
+        let result = issue_voucher(&mut context, 0);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    fn apply_status(
+        context: &mut MockTransactionContext,
+        action: Payload_Action,
+    ) -> Result<(), ApplyError> {
+        let mut payload = make_payload(action, "John Doe");
+        payload.set_number(12345);
+        apply(context, ADMIN_KEY, payload)
+    }
+
+    /// What the settings transaction processor writes for `sawset proposal create`
+    fn seed_setting(context: &mut MockTransactionContext, key: &str, value: &str) {
+        let mut entry = Setting_Entry::new();
+        entry.set_key(String::from(key));
+        entry.set_value(String::from(value));
+        let mut setting = Setting::new();
+        setting.mut_entries().push(entry);
+        context.state.borrow_mut().insert(
+            calculate_setting_address(key),
+            setting.write_to_bytes().unwrap(),
+        );
+    }
+
+    fn seed_admins(context: &mut MockTransactionContext, admin_keys: &str) {
+        seed_setting(context, ADMIN_KEYS_SETTING, admin_keys);
+    }
+
+    #[test]
+    fn gates_lifecycle_on_admin_setting() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        let mut payload = make_payload(Payload_Action::FREEZE, "John Doe");
+        payload.set_number(12345);
+
+        // Nobody is an administrator until the setting exists
+        let result = apply(&mut context, ADMIN_KEY, payload.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        seed_admins(&mut context, &format!("{}, {}", STRANGER_KEY, ADMIN_KEY));
+        let result = apply(&mut context, MERCHANT_KEY, payload.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        apply(&mut context, ADMIN_KEY, payload).unwrap();
+    }
+
+    #[test]
+    fn freezes_accounts() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        seed_admins(&mut context, ADMIN_KEY);
+        add_account(&mut context, "Jane Doe", 54321, "");
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+        let result = apply_status(&mut context, Payload_Action::UNFREEZE);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        apply_status(&mut context, Payload_Action::FREEZE).unwrap();
+
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        let mut transfer = make_balance_payload(Payload_Action::TRANSFER, 20);
+        transfer.set_recipient_name(String::from("Jane Doe"));
+        transfer.set_recipient_number(54321);
+        let result = apply(&mut context, MERCHANT_KEY, transfer.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        apply_status(&mut context, Payload_Action::UNFREEZE).unwrap();
+        apply(&mut context, MERCHANT_KEY, transfer).unwrap();
+        assert_eq!(get_balance(&context), 30);
+    }
+
+    #[test]
+    fn rejects_vouchers_and_sweeps_on_inactive_accounts() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        seed_admins(&mut context, ADMIN_KEY);
+        let mut deposit = make_balance_payload(Payload_Action::DEPOSIT, 50);
+        deposit.set_expires_at(20000);
+        apply(&mut context, MERCHANT_KEY, deposit).unwrap();
+        issue_voucher(&mut context, 30000).unwrap();
+        apply_status(&mut context, Payload_Action::FREEZE).unwrap();
+
+        let consume = make_voucher_payload(Payload_Action::CONSUME_VOUCHER, "John Doe");
+        let result = apply(&mut context, MERCHANT_KEY, consume.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        let mut expire = make_payload(Payload_Action::EXPIRE, "John Doe");
+        expire.set_number(12345);
+        expire.set_timestamp(20000);
+        let result = apply(&mut context, MERCHANT_KEY, expire.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 50);
+
+        apply_status(&mut context, Payload_Action::UNFREEZE).unwrap();
+        apply(&mut context, MERCHANT_KEY, consume).unwrap();
+        apply(&mut context, MERCHANT_KEY, expire).unwrap();
+        assert_eq!(get_balance(&context), 0);
+    }
+
+    #[test]
+    fn closes_accounts() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        seed_admins(&mut context, ADMIN_KEY);
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+        let result = apply_status(&mut context, Payload_Action::CLOSE);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        let mut payload = make_payload(Payload_Action::CLOSE, "John Doe");
+        payload.set_number(12345);
+        payload.set_closure_reason(String::from("Fraudulent activity"));
+        apply(&mut context, ADMIN_KEY, payload).unwrap();
+        assert_eq!(get_balance(&context), 0);
+
+        let result = apply_status(&mut context, Payload_Action::UNFREEZE);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        let result = apply_status(&mut context, Payload_Action::CLOSE);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+    }
+
+    fn get_attribute<'e>(attributes: &'e [(String, String)], key: &str) -> &'e str {
+        attributes
+            .iter()
+            .find(|(attribute, _)| attribute == key)
+            .map(|(_, value)| value.as_str())
+            .unwrap()
+    }
+
+    #[test]
+    fn emits_archer_events() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        add_account(&mut context, "Jane Doe", 54321, "");
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+        let mut transfer = make_balance_payload(Payload_Action::TRANSFER, 20);
+        transfer.set_recipient_name(String::from("Jane Doe"));
+        transfer.set_recipient_number(54321);
+        apply(&mut context, MERCHANT_KEY, transfer).unwrap();
+
+        let events = context.events.borrow();
+        let event_types: Vec<&str> = events
+            .iter()
+            .map(|(event_type, _)| event_type.as_str())
+            .collect();
+        assert_eq!(
+            event_types,
+            vec![
+                ArcherEvent::MerchantRegistered.as_str(),
+                ArcherEvent::PointsDeposited.as_str(),
+                ArcherEvent::PointsTransferred.as_str(),
+            ]
+        );
+        let (_, attributes) = &events[1];
+        assert_eq!(get_attribute(attributes, "merchant"), MERCHANT_KEY);
+        assert_eq!(get_attribute(attributes, "amount"), "50");
+        assert_eq!(get_attribute(attributes, "balance"), "50");
+        let (_, attributes) = &events[2];
+        assert_eq!(get_attribute(attributes, "balance"), "30");
+        assert_eq!(get_attribute(attributes, "recipient_name"), "Jane Doe");
+        assert_eq!(get_attribute(attributes, "recipient_balance"), "20");
+    }
+
+    #[test]
+    fn attaches_receipts() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        add_account(&mut context, "Jane Doe", 54321, "");
+        let mut transfer = make_balance_payload(Payload_Action::TRANSFER, 20);
+        transfer.set_recipient_name(String::from("Jane Doe"));
+        transfer.set_recipient_number(54321);
+        apply_atomic(
+            &mut context,
+            MERCHANT_KEY,
+            vec![make_balance_payload(Payload_Action::DEPOSIT, 50), transfer],
+        )
+        .unwrap();
+
+        let receipts: Vec<ReceiptPB> = context
+            .receipts
+            .borrow()
+            .iter()
+            .map(|data| parse_from_bytes(data).unwrap())
+            .collect();
+        assert_eq!(receipts.len(), 3);
+        assert_eq!(receipts[0].get_balance(), 50);
+        assert_eq!(receipts[0].get_lot_ids(), &[0]);
+        assert_eq!(receipts[1].get_name(), "John Doe");
+        assert_eq!(receipts[1].get_balance(), 30);
+        assert_eq!(receipts[1].get_lot_ids(), &[0]);
+        assert_eq!(receipts[2].get_name(), "Jane Doe");
+        assert_eq!(receipts[2].get_balance(), 20);
+    }
+
+    fn get_settlement(context: &MockTransactionContext, issuer: &str, redeemer: &str) -> u64 {
+        let state = context.state.borrow();
+        let data = state.get(&calculate_settlement_address(issuer, redeemer, 0));
+        data.map(|data| {
+            let container: SettlementContainer = parse_from_bytes(data).unwrap();
+            container.get_entries()[0].get_amount()
+        })
+        .unwrap_or(0)
+    }
+
+    #[test]
+    fn settles_points_across_merchants() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Other shop"),
+        )
+        .unwrap();
+        publish_item(&mut context, OTHER_MERCHANT_KEY, 150, 1);
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 100),
+        )
+        .unwrap();
+        apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 100),
+        )
+        .unwrap();
+
+        // Points of the other merchant are only settled once dated
+        let mut withdrawal = make_balance_payload(Payload_Action::WITHDRAW, 150);
+        withdrawal.clear_timestamp();
+        let result = apply(&mut context, OTHER_MERCHANT_KEY, withdrawal);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_redeem_payload(1, 150),
+        )
+        .unwrap();
+        assert_eq!(
+            get_settlement(&context, MERCHANT_KEY, OTHER_MERCHANT_KEY),
+            100
+        );
+        assert_eq!(
+            get_settlement(&context, OTHER_MERCHANT_KEY, MERCHANT_KEY),
+            0
+        );
+
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 50),
+        )
+        .unwrap();
+        assert_eq!(
+            get_settlement(&context, OTHER_MERCHANT_KEY, MERCHANT_KEY),
+            50
+        );
+        assert_eq!(get_balance(&context), 0);
+    }
+
+    #[test]
+    fn keeps_merchant_currencies_apart() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Other shop"),
+        )
+        .unwrap();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+
+        // Only the merchant a currency is named after issues it
+        let mut deposit = make_balance_payload(Payload_Action::DEPOSIT, 100);
+        deposit.set_currency(String::from(OTHER_MERCHANT_KEY));
+        let result = apply(&mut context, MERCHANT_KEY, deposit.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        apply(&mut context, OTHER_MERCHANT_KEY, deposit).unwrap();
+
+        let result = apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 80),
+        );
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        let mut withdrawal = make_balance_payload(Payload_Action::WITHDRAW, 80);
+        withdrawal.set_currency(String::from(OTHER_MERCHANT_KEY));
+        apply(&mut context, OTHER_MERCHANT_KEY, withdrawal).unwrap();
+
+        assert_eq!(get_balance(&context), 50);
+        let state = context.state.borrow();
+        let data = state.get(&calculate_account_address("John Doe")).unwrap();
+        let container: AccountContainer = parse_from_bytes(data).unwrap();
+        let currency_balances = container.get_entries()[0].get_currency_balances();
+        assert_eq!(currency_balances.len(), 1);
+        assert_eq!(currency_balances[0].get_currency(), OTHER_MERCHANT_KEY);
+        assert_eq!(currency_balances[0].get_balance(), 20);
+
+        let receipt: ReceiptPB =
+            parse_from_bytes(context.receipts.borrow().last().unwrap()).unwrap();
+        assert_eq!(receipt.get_currency(), OTHER_MERCHANT_KEY);
+        assert_eq!(receipt.get_balance(), 20);
+        let events = context.events.borrow();
+        let (_, attributes) = events.last().unwrap();
+        assert_eq!(get_attribute(attributes, "currency"), OTHER_MERCHANT_KEY);
+    }
+
+    fn publish_earn_rule(context: &mut MockTransactionContext) {
+        let mut rule = EarnRulePB::new();
+        rule.set_public_key(String::from(MERCHANT_KEY));
+        rule.set_points_per_unit(1);
+        rule.set_rounding(EarnRule_Rounding::FLOOR);
+        rule.set_minimum_purchase(0);
+        let mut payload = make_payload(Payload_Action::SET_EARN_RULE, "");
+        payload.set_earn_rule(rule);
+        apply(context, MERCHANT_KEY, payload).unwrap();
+    }
+
+    fn earn(context: &mut MockTransactionContext, purchase_amount: u64, timestamp: i64) {
+        let mut payload = make_payload(Payload_Action::EARN, "John Doe");
+        payload.set_number(12345);
+        payload.set_purchase_amount(purchase_amount);
+        payload.set_timestamp(timestamp);
+        apply(context, MERCHANT_KEY, payload).unwrap();
+    }
+
+    #[test]
+    fn rejects_earnings_without_earn_rule() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        let mut payload = make_payload(Payload_Action::EARN, "John Doe");
+        payload.set_number(12345);
+        payload.set_purchase_amount(1000);
+        match apply(&mut context, MERCHANT_KEY, payload.clone()) {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert!(message.starts_with("No earn rule"))
+            }
+            _ => panic!("Earnings without an earn rule were accepted"),
+        }
+
+        publish_earn_rule(&mut context);
+        apply(&mut context, MERCHANT_KEY, payload).unwrap();
+        assert_eq!(get_balance(&context), 10);
+    }
+
+    #[test]
+    fn rejects_earn_rules_and_earnings_from_unregistered_signers() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+
+        let mut rule = EarnRulePB::new();
+        rule.set_public_key(String::from(STRANGER_KEY));
+        rule.set_points_per_unit(u32::MAX);
+        rule.set_rounding(EarnRule_Rounding::FLOOR);
+        rule.set_minimum_purchase(0);
+        let mut set_earn_rule = make_payload(Payload_Action::SET_EARN_RULE, "");
+        set_earn_rule.set_earn_rule(rule);
+        let mut earn = make_payload(Payload_Action::EARN, "John Doe");
+        earn.set_number(12345);
+        earn.set_purchase_amount(1000);
+
+        for payload in [set_earn_rule, earn] {
+            match apply(&mut context, STRANGER_KEY, payload) {
+                Err(ApplyError::InvalidTransaction(message)) => {
+                    assert!(message.ends_with("is not a registered merchant"))
+                }
+                _ => panic!("Unregistered signer was allowed to earn"),
+            }
+        }
+        assert_eq!(get_balance(&context), 0);
+    }
+
+    #[test]
+    fn expires_lapsed_lots() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        let mut deposit = make_balance_payload(Payload_Action::DEPOSIT, 50);
+        deposit.set_expires_at(20000);
+        apply(&mut context, MERCHANT_KEY, deposit).unwrap();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 30),
+        )
+        .unwrap();
+
+        let mut expire = make_payload(Payload_Action::EXPIRE, "John Doe");
+        expire.set_number(12345);
+        expire.set_timestamp(19999);
+        apply(&mut context, MERCHANT_KEY, expire.clone()).unwrap();
+        assert_eq!(get_balance(&context), 80);
+
+        expire.set_timestamp(20000);
+        apply(&mut context, MERCHANT_KEY, expire).unwrap();
+        assert_eq!(get_balance(&context), 30);
+
+        let events = context.events.borrow();
+        let (event_type, attributes) = events.last().unwrap();
+        assert_eq!(event_type, ArcherEvent::PointsExpired.as_str());
+        assert_eq!(get_attribute(attributes, "amount"), "50");
+        assert_eq!(get_attribute(attributes, "balance"), "30");
+        assert_eq!(context.receipts.borrow().len(), 4);
+    }
+
+    #[test]
+    fn rejects_sweeps_from_strangers_and_the_future() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        seed_admins(&mut context, ADMIN_KEY);
+        let mut deposit = make_balance_payload(Payload_Action::DEPOSIT, 50);
+        deposit.set_expires_at(20000);
+        apply(&mut context, MERCHANT_KEY, deposit).unwrap();
+
+        let mut expire = make_payload(Payload_Action::EXPIRE, "John Doe");
+        expire.set_number(12345);
+        expire.set_timestamp(20000);
+        let result = apply(&mut context, STRANGER_KEY, expire.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 50);
+
+        // A sweep dated far past the latest deposit would wipe every dated lot
+        let mut far_future = expire.clone();
+        far_future.set_timestamp(10000 + MAX_EXPIRY_SKEW + 1);
+        let result = apply(&mut context, MERCHANT_KEY, far_future);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 50);
+
+        apply(&mut context, ADMIN_KEY, expire).unwrap();
+        assert_eq!(get_balance(&context), 0);
+    }
+
+    #[test]
+    fn applies_tier_multipliers_to_earnings() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        publish_earn_rule(&mut context);
+        seed_setting(&mut context, TIERS_SETTING, "silver:100:100:150");
+
+        // 100 units below silver, then 100 more at the silver multiplier
+        earn(&mut context, 10000, 10000);
+        earn(&mut context, 10000, 20000);
+        assert_eq!(get_balance(&context), 250);
+
+        let receipts: Vec<ReceiptPB> = context
+            .receipts
+            .borrow()
+            .iter()
+            .map(|data| parse_from_bytes(data).unwrap())
+            .collect();
+        assert_eq!(receipts[0].get_points(), 100);
+        assert_eq!(receipts[0].get_tier(), "silver");
+        assert!(receipts[0].get_tier_changed());
+        assert_eq!(receipts[1].get_points(), 150);
+        assert!(!receipts[1].get_tier_changed());
+
+        let events = context.events.borrow();
+        let tier_changes: Vec<&RecordedEvent> = events
+            .iter()
+            .filter(|(event_type, _)| event_type == ArcherEvent::TierChanged.as_str())
+            .collect();
+        assert_eq!(tier_changes.len(), 1);
+        assert_eq!(get_attribute(&tier_changes[0].1, "tier"), "silver");
+
+        let state = context.state.borrow();
+        let data = state.get(&calculate_account_address("John Doe")).unwrap();
+        let container: AccountContainer = parse_from_bytes(data).unwrap();
+        let account = &container.get_entries()[0];
+        assert_eq!(account.get_lifetime_earned(), 250);
+        assert_eq!(account.get_tier_changed_at(), 10000);
+    }
+
+    #[test]
+    fn rejects_earnings_under_invalid_tiers() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        publish_earn_rule(&mut context);
+        seed_setting(&mut context, TIERS_SETTING, "silver:100");
+
+        let mut payload = make_payload(Payload_Action::EARN, "John Doe");
+        payload.set_number(12345);
+        payload.set_purchase_amount(10000);
+        let result = apply(&mut context, MERCHANT_KEY, payload);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 0);
+    }
+
+    #[test]
+    fn enforces_velocity_limits() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        add_account(&mut context, "Jane Doe", 54321, "");
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 100),
+        )
+        .unwrap();
+        let mut payload = make_payload(Payload_Action::DEPOSIT, "Jane Doe");
+        payload.set_number(54321);
+        payload.set_amount(100);
+        apply(&mut context, MERCHANT_KEY, payload).unwrap();
+        seed_setting(
+            &mut context,
+            VELOCITY_LIMITS_SETTING,
+            "account.withdrawal:50:0:0,merchant.withdrawal:0:60:86400",
+        );
+
+        let result = apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 51),
+        );
+        match result {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert!(message.contains("per transaction"))
+            }
+            _ => panic!("Withdrawal over the per transaction limit was accepted"),
+        }
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 50),
+        )
+        .unwrap();
+
+        // The merchant already withdrew 50 of its 60 for the day
+        let mut payload = make_payload(Payload_Action::WITHDRAW, "Jane Doe");
+        payload.set_number(54321);
+        payload.set_amount(20);
+        match apply(&mut context, MERCHANT_KEY, payload.clone()) {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert!(message.starts_with(&format!("Merchant {}", MERCHANT_KEY)))
+            }
+            _ => panic!("Withdrawal over the merchant limit was accepted"),
+        }
+        // Dating the withdrawal a day later cannot empty the merchant's window at once
+        payload.set_timestamp(10000 + 86400);
+        match apply(&mut context, MERCHANT_KEY, payload.clone()) {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert!(message.contains("too far past"))
+            }
+            _ => panic!("Forward-dated withdrawal was accepted"),
+        }
+        payload.set_timestamp(10000 + MAX_VELOCITY_SKEW);
+        let result = apply(&mut context, MERCHANT_KEY, payload);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        assert_eq!(get_balance(&context), 50);
+    }
+
+    #[test]
+    fn holds_transfers_and_redemptions_to_velocity_limits() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        add_account(&mut context, "Jane Doe", 54321, "");
+        publish_item(&mut context, MERCHANT_KEY, 200, 5);
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 1000),
+        )
+        .unwrap();
+        seed_setting(
+            &mut context,
+            VELOCITY_LIMITS_SETTING,
+            "account.withdrawal:300:0:0,merchant.withdrawal:0:500:86400",
+        );
+
+        let mut transfer = make_balance_payload(Payload_Action::TRANSFER, 301);
+        transfer.set_recipient_name(String::from("Jane Doe"));
+        transfer.set_recipient_number(54321);
+        match apply(&mut context, MERCHANT_KEY, transfer.clone()) {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert!(message.contains("per transaction"))
+            }
+            _ => panic!("Transfer over the per transaction limit was accepted"),
+        }
+        transfer.set_amount(300);
+        apply(&mut context, MERCHANT_KEY, transfer).unwrap();
+
+        match apply(&mut context, MERCHANT_KEY, make_redeem_payload(2, 400)) {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert!(message.contains("per transaction"))
+            }
+            _ => panic!("Redemption over the per transaction limit was accepted"),
+        }
+        apply(&mut context, MERCHANT_KEY, make_redeem_payload(1, 200)).unwrap();
+
+        // The transfer and the redemption used up the merchant's 500 for the day
+        match apply(&mut context, MERCHANT_KEY, make_redeem_payload(1, 200)) {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert!(message.starts_with(&format!("Merchant {}", MERCHANT_KEY)))
+            }
+            _ => panic!("Redemption over the merchant limit was accepted"),
+        }
+        assert_eq!(get_balance(&context), 500);
+        assert_eq!(get_stock(&context), 4);
+    }
+
+    fn rotate_key(
+        context: &mut MockTransactionContext,
+        signer: &str,
+        new_public_key: &str,
+    ) -> Result<(), ApplyError> {
+        let mut payload = make_payload(Payload_Action::ROTATE_MERCHANT_KEY, "");
+        payload.set_public_key(String::from(new_public_key));
+        apply(context, signer, payload)
+    }
+
+    #[test]
+    fn rotates_merchant_keys() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        rotate_key(&mut context, MERCHANT_KEY, OTHER_MERCHANT_KEY).unwrap();
+
+        assert!(apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .is_err());
+        apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 50),
+        )
+        .unwrap();
+        assert_eq!(get_balance(&context), 50);
+
+        {
+            let state = context.state.borrow();
+            let data = state
+                .get(&calculate_merchant_address(MERCHANT_KEY))
+                .unwrap();
+            let container: MerchantContainer = parse_from_bytes(data).unwrap();
+            let tombstone = &container.get_entries()[0];
+            assert_eq!(tombstone.get_successor_public_key(), OTHER_MERCHANT_KEY);
+            let data = state
+                .get(&calculate_merchant_address(OTHER_MERCHANT_KEY))
+                .unwrap();
+            let container: MerchantContainer = parse_from_bytes(data).unwrap();
+            let merchant = &container.get_entries()[0];
+            assert_eq!(merchant.get_name(), "Shop");
+            assert!(!merchant.has_successor_public_key());
+            assert_eq!(merchant.get_previous_public_keys(), &[MERCHANT_KEY]);
+        }
+
+        let events = context.events.borrow();
+        let (_, attributes) = events
+            .iter()
+            .find(|(event_type, _)| event_type == ArcherEvent::MerchantKeyRotated.as_str())
+            .unwrap();
+        assert_eq!(get_attribute(attributes, "merchant"), MERCHANT_KEY);
+        assert_eq!(get_attribute(attributes, "successor"), OTHER_MERCHANT_KEY);
+    }
+
+    #[test]
+    fn forwards_merchant_records_after_rotation() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        publish_earn_rule(&mut context);
+        publish_item(&mut context, MERCHANT_KEY, 200, 3);
+        issue_voucher(&mut context, 20000).unwrap();
+        let mut deposit = make_balance_payload(Payload_Action::DEPOSIT, 500);
+        deposit.set_currency(String::from(MERCHANT_KEY));
+        apply(&mut context, MERCHANT_KEY, deposit.clone()).unwrap();
+        rotate_key(&mut context, MERCHANT_KEY, OTHER_MERCHANT_KEY).unwrap();
+
+        // The currency keeps the name of the key it was first issued under
+        apply(&mut context, OTHER_MERCHANT_KEY, deposit).unwrap();
+        let mut redemption = make_redeem_payload(1, 200);
+        redemption.set_currency(String::from(MERCHANT_KEY));
+        apply(&mut context, OTHER_MERCHANT_KEY, redemption.clone()).unwrap();
+        assert_eq!(get_stock(&context), 2);
+
+        let consumption = make_voucher_payload(Payload_Action::CONSUME_VOUCHER, "John Doe");
+        apply(&mut context, OTHER_MERCHANT_KEY, consumption.clone()).unwrap();
+        let result = apply(&mut context, OTHER_MERCHANT_KEY, consumption.clone());
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+        let mut reissue = make_voucher_payload(Payload_Action::ISSUE_VOUCHER, "John Doe");
+        reissue.set_discount(1000);
+        reissue.set_expires_at(20000);
+        let result = apply(&mut context, OTHER_MERCHANT_KEY, reissue);
+        assert!(matches!(result, Err(ApplyError::InvalidTransaction(_))));
+
+        // The earn rule moved to the new key
+        let mut earning = make_payload(Payload_Action::EARN, "John Doe");
+        earning.set_number(12345);
+        earning.set_purchase_amount(100);
+        apply(&mut context, OTHER_MERCHANT_KEY, earning.clone()).unwrap();
+        assert_eq!(get_balance(&context), 1);
+        {
+            let state = context.state.borrow();
+            let data = state.get(&calculate_account_address("John Doe")).unwrap();
+            let container: AccountContainer = parse_from_bytes(data).unwrap();
+            let currency_balances = container.get_entries()[0].get_currency_balances();
+            assert_eq!(currency_balances[0].get_currency(), MERCHANT_KEY);
+            assert_eq!(currency_balances[0].get_balance(), 800);
+        }
+
+        let mut rule = EarnRulePB::new();
+        rule.set_public_key(String::from(MERCHANT_KEY));
+        rule.set_points_per_unit(2);
+        rule.set_rounding(EarnRule_Rounding::FLOOR);
+        rule.set_minimum_purchase(0);
+        let mut set_earn_rule = make_payload(Payload_Action::SET_EARN_RULE, "");
+        set_earn_rule.set_earn_rule(rule);
+        let mut retired_deposit = make_balance_payload(Payload_Action::DEPOSIT, 10);
+        retired_deposit.set_currency(String::from(MERCHANT_KEY));
+        for payload in [
+            set_earn_rule,
+            earning,
+            redemption,
+            consumption,
+            retired_deposit,
+        ] {
+            match apply(&mut context, MERCHANT_KEY, payload) {
+                Err(ApplyError::InvalidTransaction(message)) => {
+                    assert!(message.ends_with(&format!("was rotated to {}", OTHER_MERCHANT_KEY)))
+                }
+                _ => panic!("Retired key was accepted"),
+            }
+        }
+    }
+
+    #[test]
+    fn settles_points_of_rotated_keys_with_the_successor() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Other shop"),
+        )
+        .unwrap();
+        apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_balance_payload(Payload_Action::DEPOSIT, 100),
+        )
+        .unwrap();
+        rotate_key(&mut context, MERCHANT_KEY, STRANGER_KEY).unwrap();
+
+        apply(
+            &mut context,
+            OTHER_MERCHANT_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 60),
+        )
+        .unwrap();
+        assert_eq!(
+            get_settlement(&context, STRANGER_KEY, OTHER_MERCHANT_KEY),
+            60
+        );
+        assert_eq!(
+            get_settlement(&context, MERCHANT_KEY, OTHER_MERCHANT_KEY),
+            0
+        );
+
+        // Points the merchant issued under its previous key are its own
+        apply(
+            &mut context,
+            STRANGER_KEY,
+            make_balance_payload(Payload_Action::WITHDRAW, 40),
+        )
+        .unwrap();
+        assert_eq!(get_settlement(&context, MERCHANT_KEY, STRANGER_KEY), 0);
+        assert_eq!(get_settlement(&context, STRANGER_KEY, STRANGER_KEY), 0);
+        assert_eq!(get_balance(&context), 0);
+    }
+
+    #[test]
+    fn rejects_rotations_onto_used_keys() {
+        let mut context = MockTransactionContext::default();
+        setup_account(&mut context, "");
+        apply(
+            &mut context,
+            STRANGER_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Other Shop"),
+        )
+        .unwrap();
+
+        assert!(rotate_key(&mut context, MERCHANT_KEY, STRANGER_KEY).is_err());
+        assert!(rotate_key(&mut context, MERCHANT_KEY, MERCHANT_KEY).is_err());
+        assert!(rotate_key(&mut context, MERCHANT_KEY, "not a key").is_err());
+        assert!(rotate_key(&mut context, ADMIN_KEY, OTHER_MERCHANT_KEY).is_err());
+
+        // The retired key can neither rotate again nor register anew
+        rotate_key(&mut context, MERCHANT_KEY, OTHER_MERCHANT_KEY).unwrap();
+        assert!(rotate_key(&mut context, MERCHANT_KEY, ADMIN_KEY).is_err());
+        assert!(apply(
+            &mut context,
+            MERCHANT_KEY,
+            make_payload(Payload_Action::ADD_MERCHANT, "Shop"),
+        )
+        .is_err());
+        assert!(rotate_key(&mut context, OTHER_MERCHANT_KEY, MERCHANT_KEY).is_err());
+    }
+
+    const ACCOUNTS: [(&str, u32); 3] = [
+        ("John Doe", 12345),
+        ("Jane Doe", 54321),
+        ("John Doe", 67890),
+    ];
+
+    #[derive(Clone, Debug)]
+    enum Operation {
+        Deposit(usize, u64),
+        Withdraw(usize, u64),
+        Transfer(usize, usize, u64),
+    }
+
+    impl Operation {
+        fn into_payload(self) -> PayloadPB {
+            let (action, (name, number), amount) = match self {
+                Operation::Deposit(account, amount) => {
+                    (Payload_Action::DEPOSIT, ACCOUNTS[account], amount)
+                }
+                Operation::Withdraw(account, amount) => {
+                    (Payload_Action::WITHDRAW, ACCOUNTS[account], amount)
+                }
+                Operation::Transfer(account, _, amount) => {
+                    (Payload_Action::TRANSFER, ACCOUNTS[account], amount)
+                }
+            };
+            let mut payload = make_payload(action, name);
+            payload.set_number(number);
+            payload.set_amount(amount);
+            if let Operation::Transfer(_, recipient, _) = self {
+                let (recipient_name, recipient_number) = ACCOUNTS[recipient];
+                payload.set_recipient_name(String::from(recipient_name));
+                payload.set_recipient_number(recipient_number);
+            }
+            payload
+        }
+    }
+
+    fn operation() -> impl Strategy<Value = Operation> {
+        let account = 0..ACCOUNTS.len();
+        // Large amounts run into the balance cap
+        let amount = prop_oneof![1..1000u64, MAX_BALANCE - 1000..=MAX_BALANCE];
+        prop_oneof![
+            (account.clone(), amount.clone())
+                .prop_map(|(account, amount)| Operation::Deposit(account, amount)),
+            (account.clone(), amount.clone())
+                .prop_map(|(account, amount)| Operation::Withdraw(account, amount)),
+            (account.clone(), account, amount).prop_map(|(account, recipient, amount)| {
+                Operation::Transfer(account, recipient, amount)
+            }),
+        ]
+    }
+
+    /// Balance and lots total of each account
+    fn get_holdings(context: &MockTransactionContext) -> Vec<(u64, u64)> {
+        let state = context.state.borrow();
+        ACCOUNTS
+            .iter()
+            .map(|(name, number)| {
+                let data = state.get(&calculate_account_address(name)).unwrap();
+                let container: AccountContainer = parse_from_bytes(data).unwrap();
+                let account = container
+                    .get_entries()
+                    .iter()
+                    .find(|account| account.get_number() == *number)
+                    .unwrap();
+                let lots = account.get_lots().iter().map(|lot| lot.get_amount()).sum();
+                (account.get_balance(), lots)
+            })
+            .collect()
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn conserves_points(operations in prop::collection::vec(operation(), 1..24)) {
+            let mut context = MockTransactionContext::default();
+            setup_account(&mut context, "");
+            for (name, number) in ACCOUNTS.iter().skip(1) {
+                add_account(&mut context, name, *number, "");
+            }
+
+            let mut balances = vec![0u64; ACCOUNTS.len()];
+            let (mut deposited, mut withdrawn) = (0u128, 0u128);
+            for operation in operations {
+                let result = apply(&mut context, MERCHANT_KEY, operation.clone().into_payload());
+                match operation {
+                    Operation::Deposit(account, amount) => {
+                        let balance = balances[account];
+                        prop_assert_eq!(result.is_ok(), balance + amount <= MAX_BALANCE);
+                        if result.is_ok() {
+                            balances[account] += amount;
+                            deposited += u128::from(amount);
+                        }
+                    }
+                    // Balances never go negative, overdrafts are rejected
+                    Operation::Withdraw(account, amount) => {
+                        prop_assert_eq!(result.is_ok(), amount <= balances[account]);
+                        if result.is_ok() {
+                            balances[account] -= amount;
+                            withdrawn += u128::from(amount);
+                        }
+                    }
+                    Operation::Transfer(account, recipient, amount) => {
+                        prop_assert_eq!(
+                            result.is_ok(),
+                            account != recipient
+                                && amount <= balances[account]
+                                && balances[recipient] + amount <= MAX_BALANCE
+                        );
+                        if result.is_ok() {
+                            balances[account] -= amount;
+                            balances[recipient] += amount;
+                        }
+                    }
+                }
+
+                let holdings = get_holdings(&context);
+                for (index, (balance, lots)) in holdings.iter().enumerate() {
+                    prop_assert_eq!(*balance, balances[index]);
+                    prop_assert_eq!(*lots, *balance);
+                }
+                let total: u128 = holdings.iter().map(|(balance, _)| u128::from(*balance)).sum();
+                prop_assert_eq!(total, deposited - withdrawn);
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub mod auth;
+pub mod calculator;
+pub mod catalog;
+pub mod handler;
+pub mod lots;
+pub mod payload;
+pub mod settlement;
+pub mod state;
+#[cfg(test)]
+pub mod testing;
+pub mod tiers;
+pub mod velocity;
+pub mod voucher;
@@ -0,0 +1,353 @@
+use protobuf::RepeatedField;
+use sawtooth_sdk::processor::handler::ApplyError;
+use std::cmp::min;
+
+use archer_protobuf::account::{
+    Account as AccountPB, CurrencyBalance as CurrencyBalancePB, Lot as LotPB,
+};
+
+// Balances stay within what the read model's BIGINT columns can hold
+pub const MAX_BALANCE: u64 = i64::MAX as u64;
+
+/// How far past the latest activity on an account an expiry sweep may be dated
+pub const MAX_EXPIRY_SKEW: i64 = 86400;
+
+/// Balance of a merchant currency, or of the shared pool when the currency is empty
+pub fn currency_balance(account: &AccountPB, currency: &str) -> u64 {
+    if currency.is_empty() {
+        return account.get_balance();
+    }
+    account
+        .get_currency_balances()
+        .iter()
+        .find(|entry| entry.get_currency() == currency)
+        .map(|entry| entry.get_balance())
+        .unwrap_or(0)
+}
+
+// Currencies stay listed once held, even when their balance drops back to zero
+fn set_currency_balance(account: &mut AccountPB, currency: &str, balance: u64) {
+    if currency.is_empty() {
+        account.set_balance(balance);
+        return;
+    }
+    match account
+        .mut_currency_balances()
+        .iter_mut()
+        .find(|entry| entry.get_currency() == currency)
+    {
+        Some(entry) => entry.set_balance(balance),
+        None => {
+            let mut entry = CurrencyBalancePB::new();
+            entry.set_currency(String::from(currency));
+            entry.set_balance(balance);
+            account.mut_currency_balances().push(entry);
+        }
+    }
+}
+
+/// Opens a lot of points issued by the merchant, an empty issuer leaves it unset
+pub fn add_lot(
+    account: &mut AccountPB,
+    currency: &str,
+    amount: u64,
+    earned_at: i64,
+    expires_at: i64,
+    issuer: &str,
+) -> Result<u64, ApplyError> {
+    if amount == 0 {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Invalid deposit amount",
+        )));
+    }
+    if expires_at != 0 && expires_at <= earned_at {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Points cannot expire before they are earned",
+        )));
+    }
+
+    let balance = currency_balance(account, currency)
+        .checked_add(amount)
+        .filter(|balance| *balance <= MAX_BALANCE)
+        .ok_or_else(|| {
+            ApplyError::InvalidTransaction(String::from("Deposit would overflow the balance"))
+        })?;
+    let next_lot_id = account.get_next_lot_id().checked_add(1).ok_or_else(|| {
+        ApplyError::InvalidTransaction(String::from("Account cannot hold any more lots"))
+    })?;
+
+    let id = account.get_next_lot_id();
+    let mut lot = LotPB::new();
+    lot.set_id(id);
+    lot.set_amount(amount);
+    lot.set_earned_at(earned_at);
+    lot.set_expires_at(expires_at);
+    if !issuer.is_empty() {
+        lot.set_issuer(String::from(issuer));
+    }
+    if !currency.is_empty() {
+        lot.set_currency(String::from(currency));
+    }
+
+    account.mut_lots().push(lot);
+    account.set_next_lot_id(next_lot_id);
+    set_currency_balance(account, currency, balance);
+
+    Ok(id)
+}
+
+/// Debits the currency oldest lots first and returns the portions that were consumed.
+/// Lots lapsed by the timestamp are left for the expiry sweep and cannot be spent.
+pub fn consume_lots(
+    account: &mut AccountPB,
+    currency: &str,
+    amount: u64,
+    timestamp: i64,
+) -> Result<Vec<LotPB>, ApplyError> {
+    if amount == 0 {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Invalid withdrawal amount",
+        )));
+    }
+    let balance = currency_balance(account, currency);
+    let lapsed: u64 = account
+        .get_lots()
+        .iter()
+        .filter(|lot| lot.get_currency() == currency && is_lapsed(lot, timestamp))
+        .map(|lot| lot.get_amount())
+        .sum();
+    if balance.saturating_sub(lapsed) < amount {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Insufficient balance",
+        )));
+    }
+
+    let mut consumed: Vec<LotPB> = Vec::new();
+    let mut remaining = amount;
+
+    // Points held before lots were tracked are the oldest and never expire
+    let untracked = balance.saturating_sub(lots_total(account, currency));
+    if untracked > 0 {
+        let taken = min(untracked, remaining);
+        let mut portion = LotPB::new();
+        portion.set_amount(taken);
+        consumed.push(portion);
+        remaining -= taken;
+    }
+
+    let mut lots = account.take_lots().into_vec();
+    lots.sort_by_key(|lot| (lot.get_earned_at(), lot.get_id()));
+    for lot in lots
+        .iter_mut()
+        .filter(|lot| lot.get_currency() == currency && !is_lapsed(lot, timestamp))
+    {
+        if remaining == 0 {
+            break;
+        }
+        let taken = min(lot.get_amount(), remaining);
+        let mut portion = lot.clone();
+        portion.set_amount(taken);
+        consumed.push(portion);
+        lot.set_amount(lot.get_amount() - taken);
+        remaining -= taken;
+    }
+    lots.retain(|lot| lot.get_amount() > 0);
+
+    account.set_lots(RepeatedField::from_vec(lots));
+    set_currency_balance(account, currency, balance - amount);
+
+    Ok(consumed)
+}
+
+/// Removes the currency lots that lapsed at or before the timestamp and returns the
+/// points removed
+pub fn expire_lots(
+    account: &mut AccountPB,
+    currency: &str,
+    timestamp: i64,
+) -> Result<u64, ApplyError> {
+    let latest = latest_activity(account);
+    if timestamp > latest.saturating_add(MAX_EXPIRY_SKEW) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Expiry timestamp {} is too far past the latest activity at {}",
+            timestamp, latest
+        )));
+    }
+    let (expired, kept): (Vec<LotPB>, Vec<LotPB>) = account
+        .get_lots()
+        .iter()
+        .cloned()
+        .partition(|lot| lot.get_currency() == currency && is_lapsed(lot, timestamp));
+    let expired_total: u64 = expired.iter().map(|lot| lot.get_amount()).sum();
+    let balance = currency_balance(account, currency)
+        .checked_sub(expired_total)
+        .ok_or_else(|| {
+            ApplyError::InternalError(String::from("Lots exceed the account balance"))
+        })?;
+
+    account.set_lots(RepeatedField::from_vec(kept));
+    set_currency_balance(account, currency, balance);
+
+    Ok(expired_total)
+}
+
+fn is_lapsed(lot: &LotPB, timestamp: i64) -> bool {
+    lot.get_expires_at() != 0 && lot.get_expires_at() <= timestamp
+}
+
+/// Keeps the newest timestamp points moved at, undated debits leave it as it was
+pub fn record_activity(account: &mut AccountPB, timestamp: i64) {
+    if timestamp > account.get_last_activity_at() {
+        account.set_last_activity_at(timestamp);
+    }
+}
+
+// Accounts that moved points before activity was recorded fall back on their lots
+fn latest_activity(account: &AccountPB) -> i64 {
+    account
+        .get_lots()
+        .iter()
+        .map(|lot| lot.get_earned_at())
+        .fold(account.get_last_activity_at(), i64::max)
+}
+
+// Lots never add up to more than their balance, which is capped at MAX_BALANCE
+fn lots_total(account: &AccountPB, currency: &str) -> u64 {
+    account
+        .get_lots()
+        .iter()
+        .filter(|lot| lot.get_currency() == currency)
+        .map(|lot| lot.get_amount())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_account() -> AccountPB {
+        let mut account = AccountPB::new();
+        account.set_name(String::from("John Doe"));
+        account.set_number(12345);
+        account.set_balance(0);
+        account
+    }
+
+    #[test]
+    fn adds_lots() {
+        let mut account = make_account();
+        assert_eq!(add_lot(&mut account, "", 100, 10, 0, "").unwrap(), 0);
+        assert_eq!(
+            add_lot(&mut account, "", 50, 20, 30, "merchant").unwrap(),
+            1
+        );
+        assert_eq!(account.get_balance(), 150);
+        assert_eq!(account.get_lots().len(), 2);
+        assert!(!account.get_lots()[0].has_issuer());
+        assert_eq!(account.get_lots()[1].get_issuer(), "merchant");
+        assert!(add_lot(&mut account, "", 50, 20, 20, "").is_err());
+        assert!(add_lot(&mut account, "", 0, 20, 0, "").is_err());
+        assert!(add_lot(&mut account, "", MAX_BALANCE, 20, 0, "").is_err());
+        assert_eq!(account.get_balance(), 150);
+    }
+
+    #[test]
+    fn consumes_oldest_lots_first() {
+        let mut account = make_account();
+        add_lot(&mut account, "", 100, 20, 0, "").unwrap();
+        add_lot(&mut account, "", 100, 10, 0, "").unwrap();
+        let consumed = consume_lots(&mut account, "", 150, 30).unwrap();
+        assert_eq!(consumed.len(), 2);
+        assert_eq!(consumed[0].get_earned_at(), 10);
+        assert_eq!(consumed[0].get_amount(), 100);
+        assert_eq!(consumed[1].get_earned_at(), 20);
+        assert_eq!(consumed[1].get_amount(), 50);
+        assert_eq!(account.get_balance(), 50);
+        assert_eq!(account.get_lots().len(), 1);
+        assert_eq!(account.get_lots()[0].get_amount(), 50);
+        assert!(consume_lots(&mut account, "", 51, 30).is_err());
+    }
+
+    #[test]
+    fn consumes_untracked_points_first() {
+        let mut account = make_account();
+        account.set_balance(30);
+        add_lot(&mut account, "", 100, 10, 0, "").unwrap();
+        let consumed = consume_lots(&mut account, "", 50, 30).unwrap();
+        assert_eq!(consumed[0].get_amount(), 30);
+        assert_eq!(consumed[1].get_amount(), 20);
+        assert_eq!(account.get_balance(), 80);
+        assert_eq!(lots_total(&account, ""), 80);
+    }
+
+    #[test]
+    fn skips_lapsed_lots() {
+        let mut account = make_account();
+        add_lot(&mut account, "", 100, 10, 50, "").unwrap();
+        add_lot(&mut account, "", 100, 20, 0, "").unwrap();
+        assert!(consume_lots(&mut account, "", 101, 50).is_err());
+        let consumed = consume_lots(&mut account, "", 60, 50).unwrap();
+        assert_eq!(consumed.len(), 1);
+        assert_eq!(consumed[0].get_earned_at(), 20);
+        assert_eq!(account.get_balance(), 140);
+        assert_eq!(expire_lots(&mut account, "", 50).unwrap(), 100);
+        assert_eq!(account.get_balance(), 40);
+
+        // Undated debits spend every lot
+        add_lot(&mut account, "", 100, 30, 50, "").unwrap();
+        assert_eq!(consume_lots(&mut account, "", 140, 0).unwrap().len(), 2);
+        assert_eq!(account.get_balance(), 0);
+    }
+
+    #[test]
+    fn expires_lapsed_lots() {
+        let mut account = make_account();
+        add_lot(&mut account, "", 100, 10, 50, "").unwrap();
+        add_lot(&mut account, "", 100, 10, 100, "").unwrap();
+        add_lot(&mut account, "", 100, 10, 0, "").unwrap();
+        assert_eq!(expire_lots(&mut account, "", 49).unwrap(), 0);
+        assert_eq!(expire_lots(&mut account, "", 50).unwrap(), 100);
+        assert_eq!(account.get_balance(), 200);
+        assert_eq!(expire_lots(&mut account, "", 1000).unwrap(), 100);
+        assert_eq!(account.get_balance(), 100);
+    }
+
+    #[test]
+    fn bounds_expiry_by_latest_activity() {
+        let mut account = make_account();
+        add_lot(&mut account, "", 100, 10, 50, "").unwrap();
+        assert!(expire_lots(&mut account, "", 10 + MAX_EXPIRY_SKEW + 1).is_err());
+
+        record_activity(&mut account, 1000);
+        record_activity(&mut account, 0);
+        assert_eq!(account.get_last_activity_at(), 1000);
+        assert_eq!(
+            expire_lots(&mut account, "", 1000 + MAX_EXPIRY_SKEW).unwrap(),
+            100
+        );
+        assert!(expire_lots(&mut account, "", 1000 + MAX_EXPIRY_SKEW + 1).is_err());
+    }
+
+    #[test]
+    fn keeps_currencies_apart() {
+        let mut account = make_account();
+        add_lot(&mut account, "", 100, 10, 0, "").unwrap();
+        add_lot(&mut account, "shop", 50, 20, 30, "shop").unwrap();
+        assert_eq!(account.get_balance(), 100);
+        assert_eq!(currency_balance(&account, "shop"), 50);
+        assert_eq!(currency_balance(&account, "other"), 0);
+
+        assert!(consume_lots(&mut account, "shop", 60, 25).is_err());
+        let consumed = consume_lots(&mut account, "shop", 20, 25).unwrap();
+        assert_eq!(consumed[0].get_currency(), "shop");
+        assert_eq!(currency_balance(&account, "shop"), 30);
+        assert_eq!(account.get_balance(), 100);
+
+        assert_eq!(expire_lots(&mut account, "", 1000).unwrap(), 0);
+        assert_eq!(expire_lots(&mut account, "shop", 1000).unwrap(), 30);
+        assert_eq!(currency_balance(&account, "shop"), 0);
+        assert_eq!(account.get_currency_balances().len(), 1);
+        assert_eq!(account.get_balance(), 100);
+        assert_eq!(lots_total(&account, ""), 100);
+    }
+}
@@ -3,11 +3,7 @@ use archer_config::get_configuration;
 use log::info;
 use sawtooth_sdk::processor::TransactionProcessor;
 
-pub mod handler;
-pub mod payload;
-pub mod state;
-
-use handler::ArcherTransactionHandler;
+use archer_processor::handler::ArcherTransactionHandler;
 
 fn main() {
     info!("Starting the processor");
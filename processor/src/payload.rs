@@ -1,22 +1,131 @@
-// use regex::Regex;
-use protobuf::{parse_from_bytes, Message, ProtobufError};
+use protobuf::{parse_from_bytes, ProtobufError};
 use sawtooth_sdk::processor::handler::ApplyError;
-// use serde::{Serialize, Deserialize};
+use std::convert::TryFrom;
 
-use archer_protobuf::payload::{Payload as PayloadPB, Payload_Action};
+use archer::FamilyVersion;
+use archer_protobuf::catalog::CatalogItem as CatalogItemPB;
+use archer_protobuf::earn_rule::EarnRule as EarnRulePB;
+use archer_protobuf::payload::{Payload as PayloadPB, PayloadEnvelope, Payload_Action};
 
-// #[derive(Debug, Serialize, Deserialize)]
-// #[serde(rename_all = "PascalCase")]
-pub struct ArcherPayload {
-    payload: PayloadPB,
-    action: Payload_Action,
+// Names are embedded in the messages owners sign, which separate fields with newlines
+const MAX_NAME_LENGTH: usize = 128;
+const MAX_OPERATIONS: usize = 32;
+const MAX_ID_LENGTH: usize = 64;
+const MAX_REASON_LENGTH: usize = 256;
+// Currencies are named after the public key of their merchant
+const MAX_CURRENCY_LENGTH: usize = 66;
+
+/// Points leaving an account, which its owner may have to authorize
+pub struct Debit {
+    pub name: String,
+    pub number: u32,
+    pub amount: u64,
+    // Zero when the payload has none
+    pub timestamp: i64,
+    pub owner_signature: Option<String>,
+    // Empty for the shared pool
+    pub currency: String,
+}
+
+pub enum ArcherPayload {
+    Deposit {
+        name: String,
+        number: u32,
+        currency: String,
+        amount: u64,
+        earned_at: i64,
+        expires_at: i64,
+    },
+    Withdraw(Debit),
+    UpdateNumber {
+        name: String,
+        number: u32,
+        new_number: u32,
+    },
+    AddAccount {
+        name: String,
+        number: u32,
+        owner_public_key: Option<String>,
+    },
+    AddMerchant {
+        name: String,
+        timestamp: i64,
+    },
+    Transfer {
+        debit: Debit,
+        recipient_name: String,
+        recipient_number: u32,
+    },
+    SetEarnRule(EarnRulePB),
+    Earn {
+        name: String,
+        number: u32,
+        currency: String,
+        purchase_amount: u64,
+        category: String,
+        timestamp: i64,
+    },
+    Expire {
+        name: String,
+        number: u32,
+        currency: String,
+        timestamp: i64,
+    },
+    SetCatalogItem(CatalogItemPB),
+    /// The debit amount is the total cost the owner agreed to
+    Redeem {
+        debit: Debit,
+        item_id: String,
+        quantity: u64,
+    },
+    IssueVoucher {
+        name: String,
+        number: u32,
+        voucher_id: String,
+        discount: u64,
+        issued_at: i64,
+        expires_at: i64,
+    },
+    ConsumeVoucher {
+        name: String,
+        number: u32,
+        voucher_id: String,
+        timestamp: i64,
+    },
+    Freeze {
+        name: String,
+        number: u32,
+    },
+    Unfreeze {
+        name: String,
+        number: u32,
+    },
+    /// The reason is required when the account still holds points, which are forfeited
+    Close {
+        name: String,
+        number: u32,
+        closure_reason: Option<String>,
+    },
+    /// Signed by the merchant's current key
+    RotateMerchantKey {
+        new_public_key: String,
+    },
+    /// Several operations applied in order within one transaction
+    Atomic(Vec<ArcherPayload>),
 }
 
 impl ArcherPayload {
-    pub fn new(payload_data: &[u8]) -> Result<ArcherPayload, ApplyError> {
-        let payload: Result<PayloadPB, ProtobufError> = parse_from_bytes(payload_data);
+    pub fn new(
+        payload_data: &[u8],
+        family_version: FamilyVersion,
+    ) -> Result<ArcherPayload, ApplyError> {
+        let payloads: Result<Vec<PayloadPB>, ProtobufError> = match family_version {
+            FamilyVersion::V1 => parse_from_bytes(payload_data).map(|payload| vec![payload]),
+            FamilyVersion::V2 => parse_from_bytes(payload_data)
+                .map(|mut envelope: PayloadEnvelope| envelope.take_payloads().into_vec()),
+        };
 
-        let payload: PayloadPB = match payload {
+        let mut payloads: Vec<PayloadPB> = match payloads {
             Ok(pl) => pl,
             Err(_) => {
                 return Err(ApplyError::InvalidTransaction(String::from(
@@ -25,94 +134,673 @@ impl ArcherPayload {
             }
         };
 
-        let action = payload.get_action();
-
-        Ok(ArcherPayload {
-            payload: payload,
-            action: action,
-        })
-    }
-
-    pub fn data(&self) -> Result<Vec<u8>, ApplyError> {
-        match self.payload.get_action() {
-            Payload_Action::DEPOSIT => {
-                if self
-                    .payload
-                    .descriptor()
-                    .get_field_by_name("amount")
-                    .is_some()
-                {
-                    Ok(self
-                        .payload
-                        .write_to_bytes()
-                        .expect("Error converting action message to bytes"))
-                } else {
-                    Err(ApplyError::InvalidTransaction(String::from(
-                        "Action does not match payload data",
-                    )))
+        // 1.0 clients never dated their deposits, which leaves the lots undated like the
+        // points held before lots were tracked
+        if family_version == FamilyVersion::V1 {
+            for payload in payloads.iter_mut() {
+                if payload.get_action() == Payload_Action::DEPOSIT && !payload.has_timestamp() {
+                    payload.set_timestamp(0);
+                }
+            }
+        }
+
+        match payloads.len() {
+            0 => Err(ApplyError::InvalidTransaction(String::from(
+                "Payload carries no operations",
+            ))),
+            1 => ArcherPayload::from_proto(payloads.remove(0)),
+            count if count > MAX_OPERATIONS => Err(ApplyError::InvalidTransaction(format!(
+                "Payload cannot carry more than {} operations",
+                MAX_OPERATIONS
+            ))),
+            _ => Ok(ArcherPayload::Atomic(
+                payloads
+                    .into_iter()
+                    .map(ArcherPayload::from_proto)
+                    .collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+
+    fn from_proto(mut payload: PayloadPB) -> Result<ArcherPayload, ApplyError> {
+        match payload.get_action() {
+            Payload_Action::DEPOSIT => Ok(ArcherPayload::Deposit {
+                name: validate_name(payload.take_name())?,
+                number: require_number(&payload)?,
+                currency: validate_currency(payload.take_currency())?,
+                amount: require_amount(&payload)?,
+                earned_at: require_timestamp(&payload)?,
+                expires_at: payload.get_expires_at(),
+            }),
+            Payload_Action::WITHDRAW => Ok(ArcherPayload::Withdraw(make_debit(&mut payload)?)),
+            Payload_Action::UPDATE_NUMBER => {
+                let number = require_number(&payload)?;
+                let new_number = required(
+                    payload.has_new_number(),
+                    payload.get_new_number(),
+                    "new_number",
+                )?;
+                if new_number == number {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "New number must differ from the current one",
+                    )));
+                }
+                Ok(ArcherPayload::UpdateNumber {
+                    name: validate_name(payload.take_name())?,
+                    number,
+                    new_number,
+                })
+            }
+            Payload_Action::ADD_ACCOUNT => Ok(ArcherPayload::AddAccount {
+                name: validate_name(payload.take_name())?,
+                number: require_number(&payload)?,
+                owner_public_key: match payload.take_public_key() {
+                    owner_public_key if owner_public_key.is_empty() => None,
+                    owner_public_key => Some(owner_public_key),
+                },
+            }),
+            Payload_Action::ADD_MERCHANT => Ok(ArcherPayload::AddMerchant {
+                name: validate_name(payload.take_name())?,
+                timestamp: require_timestamp(&payload)?,
+            }),
+            Payload_Action::TRANSFER => {
+                if !payload.has_recipient_name() {
+                    return Err(missing_field("recipient_name"));
+                }
+                let recipient_number = required(
+                    payload.has_recipient_number(),
+                    payload.get_recipient_number(),
+                    "recipient_number",
+                )?;
+                Ok(ArcherPayload::Transfer {
+                    recipient_name: validate_name(payload.take_recipient_name())?,
+                    recipient_number,
+                    debit: make_debit(&mut payload)?,
+                })
+            }
+            Payload_Action::SET_EARN_RULE => {
+                if !payload.has_earn_rule() {
+                    return Err(missing_field("earn_rule"));
                 }
+                Ok(ArcherPayload::SetEarnRule(payload.take_earn_rule()))
             }
-            Payload_Action::WITHDRAW => {
-                if self
-                    .payload
-                    .descriptor()
-                    .get_field_by_name("amount")
-                    .is_some()
-                {
-                    Ok(self
-                        .payload
-                        .write_to_bytes()
-                        .expect("Error converting action message to bytes"))
+            Payload_Action::EARN => Ok(ArcherPayload::Earn {
+                name: validate_name(payload.take_name())?,
+                number: require_number(&payload)?,
+                currency: validate_currency(payload.take_currency())?,
+                purchase_amount: validate_amount(
+                    payload.has_purchase_amount(),
+                    payload.get_purchase_amount(),
+                )?,
+                category: payload.take_category(),
+                timestamp: require_timestamp(&payload)?,
+            }),
+            Payload_Action::EXPIRE => Ok(ArcherPayload::Expire {
+                name: validate_name(payload.take_name())?,
+                number: require_number(&payload)?,
+                currency: validate_currency(payload.take_currency())?,
+                timestamp: require_timestamp(&payload)?,
+            }),
+            Payload_Action::SET_CATALOG_ITEM => {
+                if !payload.has_catalog_item() {
+                    return Err(missing_field("catalog_item"));
+                }
+                Ok(ArcherPayload::SetCatalogItem(payload.take_catalog_item()))
+            }
+            Payload_Action::REDEEM => {
+                if !payload.has_item_id() {
+                    return Err(missing_field("item_id"));
+                }
+                let quantity = if payload.has_quantity() {
+                    payload.get_quantity()
                 } else {
-                    Err(ApplyError::InvalidTransaction(String::from(
-                        "Action does not match payload data",
-                    )))
+                    1
+                };
+                if quantity == 0 {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Quantity must be positive",
+                    )));
                 }
+                Ok(ArcherPayload::Redeem {
+                    item_id: validate_id(payload.take_item_id())?,
+                    quantity,
+                    debit: make_debit(&mut payload)?,
+                })
             }
-            Payload_Action::UPDATE_NUMBER => {
-                if self
-                    .payload
-                    .descriptor()
-                    .get_field_by_name("new_number")
-                    .is_some()
-                {
-                    Ok(self
-                        .payload
-                        .write_to_bytes()
-                        .expect("Error converting action message to bytes"))
+            Payload_Action::ISSUE_VOUCHER => {
+                if !payload.has_voucher_id() {
+                    return Err(missing_field("voucher_id"));
+                }
+                let discount =
+                    required(payload.has_discount(), payload.get_discount(), "discount")?;
+                Ok(ArcherPayload::IssueVoucher {
+                    name: validate_name(payload.take_name())?,
+                    number: require_number(&payload)?,
+                    voucher_id: validate_id(payload.take_voucher_id())?,
+                    discount,
+                    issued_at: require_timestamp(&payload)?,
+                    expires_at: payload.get_expires_at(),
+                })
+            }
+            Payload_Action::CONSUME_VOUCHER => {
+                if !payload.has_voucher_id() {
+                    return Err(missing_field("voucher_id"));
+                }
+                Ok(ArcherPayload::ConsumeVoucher {
+                    name: validate_name(payload.take_name())?,
+                    number: require_number(&payload)?,
+                    voucher_id: validate_id(payload.take_voucher_id())?,
+                    timestamp: require_timestamp(&payload)?,
+                })
+            }
+            Payload_Action::FREEZE => Ok(ArcherPayload::Freeze {
+                name: validate_name(payload.take_name())?,
+                number: require_number(&payload)?,
+            }),
+            Payload_Action::UNFREEZE => Ok(ArcherPayload::Unfreeze {
+                name: validate_name(payload.take_name())?,
+                number: require_number(&payload)?,
+            }),
+            Payload_Action::CLOSE => {
+                let closure_reason = if payload.has_closure_reason() {
+                    Some(validate_reason(payload.take_closure_reason())?)
                 } else {
-                    Err(ApplyError::InvalidTransaction(String::from(
-                        "Action does not match payload data",
-                    )))
+                    None
+                };
+                Ok(ArcherPayload::Close {
+                    name: validate_name(payload.take_name())?,
+                    number: require_number(&payload)?,
+                    closure_reason,
+                })
+            }
+            Payload_Action::ROTATE_MERCHANT_KEY => {
+                if !payload.has_public_key() {
+                    return Err(missing_field("public_key"));
                 }
+                Ok(ArcherPayload::RotateMerchantKey {
+                    new_public_key: payload.take_public_key(),
+                })
             }
-            Payload_Action::ADD_ACCOUNT => Ok(self
-                .payload
-                .write_to_bytes()
-                .expect("Error converting action message to bytes")),
-            Payload_Action::ADD_MERCHANT => Ok(self
-                .payload
-                .write_to_bytes()
-                .expect("Error converting action message to bytes")),
         }
     }
 
-    pub fn action(&self) -> Payload_Action {
-        self.action
+    /// Actions only merchants may submit
+    pub fn requires_merchant(&self) -> bool {
+        matches!(
+            self,
+            ArcherPayload::Deposit { .. }
+                | ArcherPayload::UpdateNumber { .. }
+                | ArcherPayload::AddAccount { .. }
+                | ArcherPayload::SetEarnRule(_)
+                | ArcherPayload::Earn { .. }
+                | ArcherPayload::SetCatalogItem(_)
+                | ArcherPayload::Redeem { .. }
+                | ArcherPayload::IssueVoucher { .. }
+                | ArcherPayload::ConsumeVoucher { .. }
+                | ArcherPayload::RotateMerchantKey { .. }
+        )
+    }
+
+    /// Points leaving an account through a merchant, or through its owner signing for
+    /// themselves
+    pub fn owner_debit(&self) -> Option<&Debit> {
+        match self {
+            ArcherPayload::Withdraw(debit) | ArcherPayload::Transfer { debit, .. } => Some(debit),
+            _ => None,
+        }
+    }
+
+    /// Sweeps are dated by the signer, so strangers cannot expire points early
+    pub fn requires_merchant_or_admin(&self) -> bool {
+        matches!(self, ArcherPayload::Expire { .. })
+    }
+
+    /// Account lifecycle is left to the administrators listed in the settings
+    pub fn requires_admin(&self) -> bool {
+        matches!(
+            self,
+            ArcherPayload::Freeze { .. }
+                | ArcherPayload::Unfreeze { .. }
+                | ArcherPayload::Close { .. }
+        )
+    }
+}
+
+fn make_debit(payload: &mut PayloadPB) -> Result<Debit, ApplyError> {
+    let owner_signature = if payload.has_owner_signature() {
+        // The timestamp is what keeps a co-signature from being replayed
+        if !payload.has_timestamp() {
+            return Err(missing_field("timestamp"));
+        }
+        Some(payload.take_owner_signature())
+    } else {
+        None
+    };
+    Ok(Debit {
+        name: validate_name(payload.take_name())?,
+        number: require_number(payload)?,
+        amount: require_amount(payload)?,
+        timestamp: payload.get_timestamp(),
+        owner_signature,
+        currency: validate_currency(payload.take_currency())?,
+    })
+}
+
+fn require_number(payload: &PayloadPB) -> Result<u32, ApplyError> {
+    required(payload.has_number(), payload.get_number(), "number")
+}
+
+fn require_timestamp(payload: &PayloadPB) -> Result<i64, ApplyError> {
+    required(
+        payload.has_timestamp(),
+        payload.get_timestamp(),
+        "timestamp",
+    )
+}
+
+fn required<T>(present: bool, value: T, field: &str) -> Result<T, ApplyError> {
+    if present {
+        Ok(value)
+    } else {
+        Err(missing_field(field))
+    }
+}
+
+fn missing_field(field: &str) -> ApplyError {
+    ApplyError::InvalidTransaction(format!("Payload is missing the {} field", field))
+}
+
+/// Clients from before amounts were widened send them zigzag-encoded on the legacy tag
+fn require_amount(payload: &PayloadPB) -> Result<u64, ApplyError> {
+    if !payload.has_amount() && payload.has_legacy_amount() {
+        let amount = u64::try_from(payload.get_legacy_amount()).unwrap_or(0);
+        return validate_amount(true, amount);
     }
+    validate_amount(payload.has_amount(), payload.get_amount())
+}
 
-    pub fn name(&self) -> String {
-        String::from(self.payload.get_name())
+fn validate_amount(present: bool, amount: u64) -> Result<u64, ApplyError> {
+    match required(present, amount, "amount")? {
+        0 => Err(ApplyError::InvalidTransaction(String::from(
+            "Amount must be positive",
+        ))),
+        amount => Ok(amount),
     }
+}
 
-    pub fn number(&self) -> u32 {
-        self.payload.get_number()
+fn validate_name(name: String) -> Result<String, ApplyError> {
+    if name.trim().is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Name cannot be empty",
+        )));
     }
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Name cannot be longer than {} bytes",
+            MAX_NAME_LENGTH
+        )));
+    }
+    if name.trim() != name || name.chars().any(char::is_control) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Invalid name {:?}",
+            name
+        )));
+    }
+    Ok(name)
+}
+
+fn validate_id(id: String) -> Result<String, ApplyError> {
+    if id.is_empty() || id.len() > MAX_ID_LENGTH || id.chars().any(char::is_control) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Invalid id {:?}",
+            id
+        )));
+    }
+    Ok(id)
+}
+
+fn validate_currency(currency: String) -> Result<String, ApplyError> {
+    if currency.len() > MAX_CURRENCY_LENGTH || currency.chars().any(char::is_control) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Invalid currency {:?}",
+            currency
+        )));
+    }
+    Ok(currency)
+}
+
+fn validate_reason(reason: String) -> Result<String, ApplyError> {
+    if reason.trim().is_empty() || reason.len() > MAX_REASON_LENGTH {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Closure reason must be between 1 and {} bytes",
+            MAX_REASON_LENGTH
+        )));
+    }
+    Ok(reason)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+    use protobuf::{Message, ProtobufEnum};
+
+    fn make_payload(action: Payload_Action) -> PayloadPB {
+        let mut payload = PayloadPB::new();
+        payload.set_action(action);
+        payload.set_name(String::from("John Doe"));
+        payload.set_number(12345);
+        payload
+    }
+
+    fn decode(payload: &PayloadPB) -> Result<ArcherPayload, ApplyError> {
+        ArcherPayload::new(&payload.write_to_bytes().unwrap(), FamilyVersion::V1)
+    }
+
+    #[test]
+    fn decodes_amounts_of_baseline_clients() {
+        // DEPOSIT of 50 to John Doe 12345 at 10000, amount being a sint32 on tag 5
+        let data = b"\x08\x00\x12\x08John Doe\x18\xb9\x60\x28\x64\x38\xa0\x9c\x01";
+        match ArcherPayload::new(data, FamilyVersion::V1).unwrap() {
+            ArcherPayload::Deposit {
+                amount, earned_at, ..
+            } => {
+                assert_eq!(amount, 50);
+                assert_eq!(earned_at, 10000);
+            }
+            _ => panic!("Expected a deposit"),
+        }
+
+        // DEPOSIT without a timestamp, as baseline clients sent them
+        let data = b"\x08\x00\x12\x08John Doe\x18\xb9\x60\x28\x64";
+        match ArcherPayload::new(data, FamilyVersion::V1).unwrap() {
+            ArcherPayload::Deposit {
+                amount, earned_at, ..
+            } => {
+                assert_eq!(amount, 50);
+                assert_eq!(earned_at, 0);
+            }
+            _ => panic!("Expected a deposit"),
+        }
+        let mut envelope = PayloadEnvelope::new();
+        envelope
+            .mut_payloads()
+            .push(parse_from_bytes(data).unwrap());
+        let data_v2 = envelope.write_to_bytes().unwrap();
+        assert!(ArcherPayload::new(&data_v2, FamilyVersion::V2).is_err());
+
+        // WITHDRAW of -5
+        let data = b"\x08\x01\x12\x08John Doe\x18\xb9\x60\x28\x09";
+        assert!(ArcherPayload::new(data, FamilyVersion::V1).is_err());
+
+        let mut payload = make_payload(Payload_Action::WITHDRAW);
+        payload.set_legacy_amount(20);
+        payload.set_amount(30);
+        match decode(&payload).unwrap() {
+            ArcherPayload::Withdraw(debit) => assert_eq!(debit.amount, 30),
+            _ => panic!("Expected a withdrawal"),
+        }
+    }
+
+    #[test]
+    fn decodes_deposits() {
+        let mut payload = make_payload(Payload_Action::DEPOSIT);
+        payload.set_amount(50);
+        payload.set_timestamp(10003456);
+        match decode(&payload).unwrap() {
+            ArcherPayload::Deposit {
+                name,
+                number,
+                currency,
+                amount,
+                earned_at,
+                expires_at,
+            } => {
+                assert_eq!(name, "John Doe");
+                assert_eq!(number, 12345);
+                assert_eq!(currency, "");
+                assert_eq!(amount, 50);
+                assert_eq!(earned_at, 10003456);
+                assert_eq!(expires_at, 0);
+            }
+            _ => panic!("Expected a deposit"),
+        }
+    }
+
+    #[test]
+    fn rejects_missing_amounts() {
+        let mut payload = make_payload(Payload_Action::DEPOSIT);
+        payload.set_timestamp(10003456);
+        assert!(decode(&payload).is_err());
+        payload.set_amount(0);
+        assert!(decode(&payload).is_err());
+
+        let payload = make_payload(Payload_Action::WITHDRAW);
+        assert!(decode(&payload).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_currencies() {
+        let mut payload = make_payload(Payload_Action::WITHDRAW);
+        payload.set_amount(50);
+        payload.set_currency("a".repeat(MAX_CURRENCY_LENGTH + 1));
+        assert!(decode(&payload).is_err());
+        payload.set_currency(String::from("shop\n"));
+        assert!(decode(&payload).is_err());
+        payload.set_currency("a".repeat(MAX_CURRENCY_LENGTH));
+        match decode(&payload).unwrap() {
+            ArcherPayload::Withdraw(debit) => assert_eq!(debit.currency.len(), MAX_CURRENCY_LENGTH),
+            _ => panic!("Expected a withdrawal"),
+        }
+    }
+
+    #[test]
+    fn rejects_missing_fields() {
+        let payload = make_payload(Payload_Action::UPDATE_NUMBER);
+        assert!(decode(&payload).is_err());
+
+        let mut payload = make_payload(Payload_Action::UPDATE_NUMBER);
+        payload.set_new_number(12345);
+        assert!(decode(&payload).is_err());
+
+        let mut payload = make_payload(Payload_Action::TRANSFER);
+        payload.set_amount(50);
+        payload.set_recipient_name(String::from("Jane Doe"));
+        assert!(decode(&payload).is_err());
+
+        let mut payload = make_payload(Payload_Action::WITHDRAW);
+        payload.set_amount(50);
+        payload.set_owner_signature(String::from("signature"));
+        assert!(decode(&payload).is_err());
+
+        let mut payload = make_payload(Payload_Action::ADD_ACCOUNT);
+        payload.clear_number();
+        assert!(decode(&payload).is_err());
+
+        assert!(decode(&make_payload(Payload_Action::SET_EARN_RULE)).is_err());
+        assert!(decode(&make_payload(Payload_Action::SET_CATALOG_ITEM)).is_err());
+
+        let mut payload = make_payload(Payload_Action::REDEEM);
+        payload.set_amount(500);
+        assert!(decode(&payload).is_err());
+        payload.set_item_id(String::from("mug"));
+        assert!(decode(&payload).is_ok());
+        payload.set_quantity(0);
+        assert!(decode(&payload).is_err());
+
+        let mut payload = make_payload(Payload_Action::ISSUE_VOUCHER);
+        payload.set_timestamp(10003456);
+        payload.set_voucher_id(String::from("spring-10"));
+        assert!(decode(&payload).is_err());
+        payload.set_discount(1000);
+        assert!(decode(&payload).is_ok());
+        payload.set_voucher_id(String::new());
+        assert!(decode(&payload).is_err());
+
+        let mut payload = make_payload(Payload_Action::CONSUME_VOUCHER);
+        payload.set_voucher_id(String::from("spring-10"));
+        assert!(decode(&payload).is_err());
+
+        let mut payload = make_payload(Payload_Action::CLOSE);
+        payload.set_number(12345);
+        assert!(decode(&payload).is_ok());
+        payload.set_closure_reason(String::from(" "));
+        assert!(decode(&payload).is_err());
+        assert!(decode(&make_payload(Payload_Action::EXPIRE)).is_err());
+
+        let mut payload = make_payload(Payload_Action::ROTATE_MERCHANT_KEY);
+        assert!(decode(&payload).is_err());
+        payload.set_public_key(String::from("02ab"));
+        match decode(&payload).unwrap() {
+            ArcherPayload::RotateMerchantKey { new_public_key } => {
+                assert_eq!(new_public_key, "02ab")
+            }
+            _ => panic!("Expected a key rotation"),
+        }
+    }
 
-    
+    #[test]
+    fn validates_names() {
+        assert!(validate_name(String::from("John Doe")).is_ok());
+        assert!(validate_name(String::from("")).is_err());
+        assert!(validate_name(String::from("   ")).is_err());
+        assert!(validate_name(String::from(" John Doe")).is_err());
+        assert!(validate_name(String::from("John\nDoe")).is_err());
+        assert!(validate_name("a".repeat(MAX_NAME_LENGTH + 1)).is_err());
+
+        let mut payload = make_payload(Payload_Action::ADD_ACCOUNT);
+        payload.set_name(String::from(""));
+        assert!(decode(&payload).is_err());
+    }
+
+    #[test]
+    fn decodes_each_family_version() {
+        let mut payload = make_payload(Payload_Action::EXPIRE);
+        payload.set_timestamp(10003456);
+        let mut envelope = PayloadEnvelope::new();
+        envelope.mut_payloads().push(payload.clone());
+        let envelope_bytes = envelope.write_to_bytes().unwrap();
+
+        for (data, version) in [
+            (payload.write_to_bytes().unwrap(), FamilyVersion::V1),
+            (envelope_bytes.clone(), FamilyVersion::V2),
+        ]
+        .iter()
+        {
+            match ArcherPayload::new(data, *version).unwrap() {
+                ArcherPayload::Expire {
+                    name, timestamp, ..
+                } => {
+                    assert_eq!(name, "John Doe");
+                    assert_eq!(timestamp, 10003456);
+                }
+                _ => panic!("Expected an expiry"),
+            }
+        }
+
+        assert!(ArcherPayload::new(&payload.write_to_bytes().unwrap(), FamilyVersion::V2).is_err());
+        assert!(ArcherPayload::new(&envelope_bytes, FamilyVersion::V1).is_err());
+    }
+
+    #[test]
+    fn decodes_atomic_operations() {
+        let mut deposit = make_payload(Payload_Action::DEPOSIT);
+        deposit.set_amount(50);
+        deposit.set_timestamp(10003456);
+        let mut withdrawal = make_payload(Payload_Action::WITHDRAW);
+        withdrawal.set_amount(20);
+        let mut envelope = PayloadEnvelope::new();
+        envelope.mut_payloads().push(deposit);
+        envelope.mut_payloads().push(withdrawal.clone());
+
+        let data = envelope.write_to_bytes().unwrap();
+        match ArcherPayload::new(&data, FamilyVersion::V2).unwrap() {
+            ArcherPayload::Atomic(operations) => {
+                assert_eq!(operations.len(), 2);
+                assert!(matches!(operations[0], ArcherPayload::Deposit { .. }));
+                assert!(matches!(operations[1], ArcherPayload::Withdraw(_)));
+            }
+            _ => panic!("Expected atomic operations"),
+        }
+
+        withdrawal.clear_amount();
+        envelope.mut_payloads().push(withdrawal);
+        let data = envelope.write_to_bytes().unwrap();
+        assert!(ArcherPayload::new(&data, FamilyVersion::V2).is_err());
+
+        let data = PayloadEnvelope::new().write_to_bytes().unwrap();
+        assert!(ArcherPayload::new(&data, FamilyVersion::V2).is_err());
+
+        let mut envelope = PayloadEnvelope::new();
+        for _ in 0..=MAX_OPERATIONS {
+            envelope
+                .mut_payloads()
+                .push(make_payload(Payload_Action::EXPIRE));
+        }
+        let data = envelope.write_to_bytes().unwrap();
+        assert!(ArcherPayload::new(&data, FamilyVersion::V2).is_err());
+    }
+
+    #[test]
+    fn decodes_optional_owner_keys() {
+        match decode(&make_payload(Payload_Action::ADD_ACCOUNT)).unwrap() {
+            ArcherPayload::AddAccount {
+                owner_public_key, ..
+            } => assert_eq!(owner_public_key, None),
+            _ => panic!("Expected an account"),
+        }
+    }
+
+    fn payload() -> impl Strategy<Value = PayloadPB> {
+        (
+            prop::sample::select(Payload_Action::values()),
+            ".{0,140}",
+            prop::option::of(any::<u32>()),
+            prop::option::of(any::<u64>()),
+            prop::option::of(any::<i64>()),
+            ".{0,80}",
+        )
+            .prop_map(|(action, name, number, amount, timestamp, text)| {
+                let mut payload = PayloadPB::new();
+                payload.set_action(action);
+                payload.set_name(name);
+                if let Some(number) = number {
+                    payload.set_number(number);
+                }
+                if let Some(amount) = amount {
+                    payload.set_amount(amount);
+                }
+                if let Some(timestamp) = timestamp {
+                    payload.set_timestamp(timestamp);
+                }
+                // Optional text fields share the value, each is set only for some actions
+                if !text.is_empty() {
+                    payload.set_public_key(text.clone());
+                    payload.set_recipient_name(text.clone());
+                    payload.set_item_id(text.clone());
+                    payload.set_voucher_id(text.clone());
+                    payload.set_closure_reason(text.clone());
+                    payload.set_currency(text);
+                }
+                payload
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn decodes_arbitrary_bytes(data in prop::collection::vec(any::<u8>(), 0..512)) {
+            for family_version in FamilyVersion::ALL.iter() {
+                let _ = ArcherPayload::new(&data, *family_version);
+            }
+        }
+
+        #[test]
+        fn decodes_arbitrary_payloads(payloads in prop::collection::vec(payload(), 1..4)) {
+            let _ = decode(&payloads[0]);
+            let mut envelope = PayloadEnvelope::new();
+            envelope.set_payloads(protobuf::RepeatedField::from_vec(payloads));
+            let data = envelope.write_to_bytes().unwrap();
+            let _ = ArcherPayload::new(&data, FamilyVersion::V2);
+        }
+    }
 }
@@ -0,0 +1,99 @@
+use sawtooth_sdk::processor::handler::ApplyError;
+use std::collections::BTreeMap;
+
+use archer_protobuf::account::Lot as LotPB;
+use archer_protobuf::settlement::{Settlement as SettlementPB, SettlementContainer};
+
+/// Points of other merchants among the consumed lots, summed per issuer. Points the
+/// redeemer issued itself and points without an issuer settle nothing.
+pub fn settlement_obligations(redeemer: &str, consumed: &[LotPB]) -> Vec<(String, u64)> {
+    // Consumed lots never add up to more than the balance they came from
+    let mut obligations: BTreeMap<&str, u64> = BTreeMap::new();
+    for lot in consumed {
+        let issuer = lot.get_issuer();
+        if issuer.is_empty() || issuer == redeemer {
+            continue;
+        }
+        *obligations.entry(issuer).or_insert(0) += lot.get_amount();
+    }
+    obligations
+        .into_iter()
+        .map(|(issuer, amount)| (String::from(issuer), amount))
+        .collect()
+}
+
+/// Adds to what the issuer owes the redeemer for the period
+pub fn accrue_settlement(
+    container: &mut SettlementContainer,
+    issuer: &str,
+    redeemer: &str,
+    period_start: i64,
+    amount: u64,
+) -> Result<(), ApplyError> {
+    let position = container.entries.iter().position(|entry| {
+        entry.get_issuer() == issuer
+            && entry.get_redeemer() == redeemer
+            && entry.get_period_start() == period_start
+    });
+    let settlement: &mut SettlementPB = match position {
+        Some(position) => &mut container.entries[position],
+        None => {
+            let mut settlement = SettlementPB::new();
+            settlement.set_issuer(String::from(issuer));
+            settlement.set_redeemer(String::from(redeemer));
+            settlement.set_period_start(period_start);
+            settlement.set_amount(0);
+            container.entries.push(settlement);
+            container.entries.last_mut().unwrap()
+        }
+    };
+    let total = settlement
+        .get_amount()
+        .checked_add(amount)
+        .ok_or_else(|| ApplyError::InvalidTransaction(String::from("Settlement would overflow")))?;
+    settlement.set_amount(total);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_lot(issuer: &str, amount: u64) -> LotPB {
+        let mut lot = LotPB::new();
+        lot.set_amount(amount);
+        if !issuer.is_empty() {
+            lot.set_issuer(String::from(issuer));
+        }
+        lot
+    }
+
+    #[test]
+    fn sums_obligations_per_issuer() {
+        let consumed = vec![
+            make_lot("", 10),
+            make_lot("b", 20),
+            make_lot("a", 30),
+            make_lot("b", 40),
+            make_lot("c", 50),
+        ];
+        assert_eq!(
+            settlement_obligations("c", &consumed),
+            vec![(String::from("a"), 30), (String::from("b"), 60)]
+        );
+        assert!(settlement_obligations("c", &[make_lot("c", 10)]).is_empty());
+    }
+
+    #[test]
+    fn accrues_settlements() {
+        let mut container = SettlementContainer::new();
+        accrue_settlement(&mut container, "a", "b", 0, 30).unwrap();
+        accrue_settlement(&mut container, "a", "b", 0, 20).unwrap();
+        accrue_settlement(&mut container, "a", "b", 86400, 5).unwrap();
+        assert_eq!(container.get_entries().len(), 2);
+        assert_eq!(container.get_entries()[0].get_amount(), 50);
+        assert_eq!(container.get_entries()[1].get_amount(), 5);
+        assert!(accrue_settlement(&mut container, "a", "b", 0, u64::MAX).is_err());
+        assert_eq!(container.get_entries()[0].get_amount(), 50);
+    }
+}
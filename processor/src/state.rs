@@ -1,58 +1,115 @@
 use protobuf::{parse_from_bytes, Message};
-use sawtooth_sdk::processor::handler::{ApplyError, TransactionContext};
-use std::collections::HashMap;
+use sawtooth_sdk::messages::setting::Setting;
+use sawtooth_sdk::processor::handler::{ApplyError, ContextError, TransactionContext};
+use std::convert::TryFrom;
 
-use archer::{calculate_account_address, calculate_merchant_address};
-use archer_protobuf::account::{Account as AccountPB, AccountContainer};
+use super::auth::{validate_public_key, verify_signature};
+use super::calculator::{calculate_expiry, calculate_points, validate_earn_rule};
+use super::catalog::{calculate_redemption_cost, validate_catalog_item};
+use super::lots::{add_lot, consume_lots, currency_balance, expire_lots, record_activity};
+use super::payload::Debit;
+use super::settlement::{accrue_settlement, settlement_obligations};
+use super::tiers::{apply_tier_multiplier, current_tier, parse_tiers, record_earnings, Tier};
+use super::velocity::{
+    check_velocity, find_limit, parse_velocity_limits, Direction, Scope, VelocityLimit,
+};
+use super::voucher::{check_consumable, validate_voucher};
+use archer::{
+    calculate_account_address, calculate_catalog_item_address, calculate_earn_rule_address,
+    calculate_merchant_address, calculate_setting_address, calculate_settlement_address,
+    calculate_voucher_address, make_owner_authorization, settlement_period_start, ArcherEvent,
+    DebitAction, ADMIN_KEYS_SETTING, TIERS_SETTING, VELOCITY_LIMITS_SETTING,
+};
+use archer_protobuf::account::{
+    Account as AccountPB, AccountContainer, Account_Status, Lot as LotPB,
+};
+use archer_protobuf::catalog::{CatalogItem as CatalogItemPB, CatalogItemContainer};
+use archer_protobuf::earn_rule::{EarnRule as EarnRulePB, EarnRuleContainer};
 use archer_protobuf::merchant::{Merchant as MerchantPB, MerchantContainer};
+use archer_protobuf::receipt::Receipt as ReceiptPB;
+use archer_protobuf::settlement::SettlementContainer;
+use archer_protobuf::voucher::{Voucher as VoucherPB, VoucherContainer};
+
+/// Deposits open a lot with the given timestamps, withdrawals consume the oldest lots
+/// and settle those of other merchants in the period of the timestamp. Earnings are
+/// deposits multiplied by the tier of the account, which they count toward.
+pub enum BalanceChange {
+    Deposit {
+        amount: u64,
+        earned_at: i64,
+        expires_at: i64,
+    },
+    Earning {
+        points: u64,
+        earned_at: i64,
+        expires_at: i64,
+        tiers: Vec<Tier>,
+    },
+    Withdrawal {
+        amount: u64,
+        timestamp: i64,
+    },
+}
 
 pub struct ArcherState<'a> {
     context: &'a mut dyn TransactionContext,
-    _address_map: HashMap<String, Option<String>>,
 }
 
 impl<'a> ArcherState<'a> {
     pub fn new(context: &'a mut dyn TransactionContext) -> ArcherState {
-        ArcherState {
-            context: context,
-            _address_map: HashMap::new(),
-        }
+        ArcherState { context }
     }
 
-    pub fn set_account(&mut self, name: &str, number: u32) -> Result<(), ApplyError> {
+    pub fn set_account(
+        &mut self,
+        name: &str,
+        number: u32,
+        owner_public_key: Option<&str>,
+    ) -> Result<(), ApplyError> {
         let address: String = calculate_account_address(name);
         let mut account: AccountPB = AccountPB::new();
         account.set_name(String::from(name));
         account.set_number(number);
         account.set_balance(0);
+        if let Some(owner_public_key) = owner_public_key {
+            validate_public_key(owner_public_key)?;
+            account.set_owner_public_key(String::from(owner_public_key));
+        }
 
-        let mut container: AccountContainer;
-
-        let state_entries = self.context.get_state_entries(&[address.clone()]);
-        match state_entries {
-            Ok(entries) => {
-                container = parse_from_bytes(&entries[0].1)
-                    .expect("Error converting state entry to account container");
-            }
-            Err(_) => {
-                container = AccountContainer::new();
-            }
+        let mut container: AccountContainer = match self._get_state_entry(&address)? {
+            Some(data) => parse_accounts(&data, &address)?,
+            None => AccountContainer::new(),
         };
 
-        container.entries.push(account);
+        if container
+            .entries
+            .iter()
+            .any(|entry| entry.get_name() == name && entry.get_number() == number)
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Account already exists for {} {}",
+                name, number
+            )));
+        }
+        // The read model closes the rows of numbers an account gave up, so they stay retired
+        if container
+            .entries
+            .iter()
+            .any(|entry| entry.get_name() == name && entry.get_previous_numbers().contains(&number))
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Account number {} has already been used for {}",
+                number, name
+            )));
+        }
 
-        let data = container
-            .write_to_bytes()
-            .expect("Error serializing container");
+        container.entries.push(account);
 
-        self.context
-            .set_state_entry(address, data)
-            .expect("Error setting state entry");
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
 
         Ok(())
     }
 
-    // TODO implement
     pub fn set_merchant(
         &mut self,
         public_key: &str,
@@ -65,28 +122,197 @@ impl<'a> ArcherState<'a> {
         merchant.set_name(String::from(name));
         merchant.set_timestamp(timestamp);
 
-        let mut container: MerchantContainer;
+        let mut container: MerchantContainer = self._load_merchants(&address)?;
 
-        let state_entries = self.context.get_state_entries(&[address.clone()]);
-        match state_entries {
-            Ok(entries) => {
-                container = parse_from_bytes(&entries[0].1)
-                    .expect("Error converting state entry to merchant container");
-            }
-            Err(_) => {
-                container = MerchantContainer::new();
-            }
-        };
+        if container
+            .entries
+            .iter()
+            .any(|entry| entry.get_public_key() == public_key)
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Merchant already exists for {}",
+                public_key
+            )));
+        }
 
         container.entries.push(merchant);
 
-        let data = container
-            .write_to_bytes()
-            .expect("Error serializing container");
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
 
-        self.context
-            .set_state_entry(address, data)
-            .expect("Error setting state entry");
+        Ok(())
+    }
+
+    /// Rotated keys are left as tombstones, which no longer count as merchants
+    pub fn is_merchant(&mut self, public_key: &str) -> Result<bool, ApplyError> {
+        let address: String = calculate_merchant_address(public_key);
+        let container: MerchantContainer = self._load_merchants(&address)?;
+        Ok(container
+            .entries
+            .iter()
+            .any(|entry| entry.get_public_key() == public_key && !entry.has_successor_public_key()))
+    }
+
+    pub fn is_owner(
+        &mut self,
+        public_key: &str,
+        name: &str,
+        number: u32,
+    ) -> Result<bool, ApplyError> {
+        let address: String = calculate_account_address(name);
+        let mut container = self._load_accounts(&address)?;
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        Ok(!public_key.is_empty() && account.get_owner_public_key() == public_key)
+    }
+
+    /// Key the merchant moved to when this one was rotated, none for current keys and
+    /// keys that never belonged to a merchant
+    pub fn get_successor(&mut self, public_key: &str) -> Result<Option<String>, ApplyError> {
+        let address: String = calculate_merchant_address(public_key);
+        let container: MerchantContainer = self._load_merchants(&address)?;
+        Ok(container
+            .entries
+            .into_iter()
+            .find(|entry| entry.get_public_key() == public_key && entry.has_successor_public_key())
+            .map(|mut entry| entry.take_successor_public_key()))
+    }
+
+    /// Moves the merchant to the address of its new key and leaves a tombstone naming the
+    /// successor behind, which keeps the old key from signing for the merchant or
+    /// registering it again. The earn rule moves along, catalog items and vouchers are
+    /// found under the previous keys.
+    pub fn rotate_merchant_key(
+        &mut self,
+        public_key: &str,
+        new_public_key: &str,
+    ) -> Result<(), ApplyError> {
+        validate_public_key(new_public_key)?;
+        if new_public_key == public_key {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "New key must differ from the current one",
+            )));
+        }
+
+        let address: String = calculate_merchant_address(public_key);
+        let new_address: String = calculate_merchant_address(new_public_key);
+        let mut container: MerchantContainer = self._load_merchants(&address)?;
+        let mut new_container: MerchantContainer = if new_address == address {
+            MerchantContainer::new()
+        } else {
+            self._load_merchants(&new_address)?
+        };
+
+        if container
+            .entries
+            .iter()
+            .chain(new_container.entries.iter())
+            .any(|entry| entry.get_public_key() == new_public_key)
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Merchant already exists for {}",
+                new_public_key
+            )));
+        }
+
+        let entry: &mut MerchantPB = container
+            .entries
+            .iter_mut()
+            .find(|entry| entry.get_public_key() == public_key && !entry.has_successor_public_key())
+            .ok_or_else(|| {
+                ApplyError::InvalidTransaction(format!(
+                    "Signer {} is not a registered merchant",
+                    public_key
+                ))
+            })?;
+
+        let mut successor: MerchantPB = entry.clone();
+        successor.set_public_key(String::from(new_public_key));
+        successor
+            .mut_previous_public_keys()
+            .push(String::from(public_key));
+
+        entry.set_successor_public_key(String::from(new_public_key));
+        entry.clear_recent_deposits();
+        entry.clear_recent_withdrawals();
+
+        let mut entries = if new_address == address {
+            container.entries.push(successor);
+            vec![(address, Self::_serialize(&container)?)]
+        } else {
+            new_container.entries.push(successor);
+            vec![
+                (address, Self::_serialize(&container)?),
+                (new_address, Self::_serialize(&new_container)?),
+            ]
+        };
+        entries.extend(self._move_earn_rule(public_key, new_public_key)?);
+        self._set_state_entries(entries)?;
+
+        Ok(())
+    }
+
+    /// Administrators are set through the settings transaction processor, which
+    /// keeps the list out of reach of the family's own transactions
+    pub fn is_admin(&mut self, public_key: &str) -> Result<bool, ApplyError> {
+        Ok(self
+            ._get_setting(ADMIN_KEYS_SETTING)?
+            .map(|value| value.split(',').any(|key| key.trim() == public_key))
+            .unwrap_or(false))
+    }
+
+    /// Tiers are configured through the settings transaction processor as well
+    pub fn get_tiers(&mut self) -> Result<Vec<Tier>, ApplyError> {
+        match self._get_setting(TIERS_SETTING)? {
+            Some(value) => parse_tiers(&value),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Points leave an owned account only when the owner signs the transaction,
+    /// or co-signs the action for the merchant submitting it
+    pub fn authorize_owner(
+        &mut self,
+        signer: &str,
+        action: DebitAction,
+        debit: &Debit,
+        recipient_name: &str,
+        recipient_number: u32,
+    ) -> Result<(), ApplyError> {
+        let name = &debit.name;
+        let number = debit.number;
+        let address: String = calculate_account_address(name);
+        let mut container = self._load_accounts(&address)?;
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+
+        let owner = account.get_owner_public_key();
+        if owner.is_empty() || owner == signer {
+            return Ok(());
+        }
+        let signature = debit.owner_signature.as_ref().ok_or_else(|| {
+            ApplyError::InvalidTransaction(format!(
+                "Account {} {} requires its owner's signature",
+                name, number
+            ))
+        })?;
+        if debit.timestamp <= account.get_last_authorized_at() {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Owner signature has already been used",
+            )));
+        }
+        let message = make_owner_authorization(
+            action,
+            signer,
+            name,
+            number,
+            recipient_name,
+            recipient_number,
+            debit.amount,
+            debit.timestamp,
+            &debit.currency,
+        );
+        verify_signature(owner, signature, &message)?;
+        account.set_last_authorized_at(debit.timestamp);
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
 
         Ok(())
     }
@@ -98,139 +324,951 @@ impl<'a> ArcherState<'a> {
         new_number: u32,
     ) -> Result<u32, ApplyError> {
         let address: String = calculate_account_address(name);
-        let state_entries = self.context.get_state_entries(&[address.clone()]);
-        match state_entries {
-            Ok(entries) => {
-                let container: AccountContainer =
-                    parse_from_bytes(&entries[0].1).expect("Error parsing state entries");
-                let mut accounts = container.get_entries().to_vec();
-                let account: &mut AccountPB = accounts
-                    .iter_mut()
-                    .find(|entry| entry.get_name() == name && entry.get_number() == number)
-                    .expect("Did not found accounts with that name");
-                account.set_number(new_number);
-                Ok(account.get_number())
-            }
-            Err(_) => Err(ApplyError::InvalidTransaction(format!(
-                "Account not found for {}",
-                address
-            ))),
+        let mut container = self._load_accounts(&address)?;
+        if container.entries.iter().any(|entry| {
+            entry.get_name() == name
+                && (entry.get_number() == new_number
+                    || entry.get_previous_numbers().contains(&new_number))
+        }) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Account number {} has already been used for {}",
+                new_number, name
+            )));
         }
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        account.set_number(new_number);
+        account.mut_previous_numbers().push(number);
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
+
+        Ok(new_number)
     }
 
-    pub fn get_balance(&mut self, name: &str, number: u32) -> Result<i32, ApplyError> {
+    /// Freezes an active account or unfreezes a frozen one, closed accounts stay closed
+    pub fn set_status(
+        &mut self,
+        name: &str,
+        number: u32,
+        status: Account_Status,
+    ) -> Result<(), ApplyError> {
         let address: String = calculate_account_address(name);
-        let state_entries = self.context.get_state_entries(&[address.clone()]);
-        match state_entries {
-            Ok(entries) => {
-                let container: AccountContainer =
-                    parse_from_bytes(&entries[0].1).expect("Error parsing state entries");
-                let account: &AccountPB = container
-                    .entries
-                    .iter()
-                    .find(|entry| entry.get_name() == name && entry.get_number() == number)
-                    .expect("Did not found accounts with that name");
-                Ok(account.get_balance())
+        let mut container = self._load_accounts(&address)?;
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        let allowed = match status {
+            Account_Status::ACTIVE => account.get_status() == Account_Status::FROZEN,
+            Account_Status::FROZEN => account.get_status() == Account_Status::ACTIVE,
+            Account_Status::CLOSED => false,
+        };
+        if !allowed {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Account {} {} cannot go from {:?} to {:?}",
+                name,
+                number,
+                account.get_status(),
+                status
+            )));
+        }
+        account.set_status(status);
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
+
+        Ok(())
+    }
+
+    /// Closes the account for good, forfeiting its points. Returns the amount forfeited.
+    pub fn close(
+        &mut self,
+        name: &str,
+        number: u32,
+        closure_reason: Option<&str>,
+    ) -> Result<u64, ApplyError> {
+        let address: String = calculate_account_address(name);
+        let mut container = self._load_accounts(&address)?;
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        if account.get_status() == Account_Status::CLOSED {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Account {} {} is already closed",
+                name, number
+            )));
+        }
+        let forfeited = account
+            .get_currency_balances()
+            .iter()
+            .fold(account.get_balance(), |total, entry| {
+                total.saturating_add(entry.get_balance())
+            });
+        match closure_reason {
+            Some(closure_reason) => account.set_closure_reason(String::from(closure_reason)),
+            None if forfeited > 0 => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Account {} {} still holds {} points, closing it requires a reason",
+                    name, number, forfeited
+                )))
             }
-            Err(_) => Err(ApplyError::InvalidTransaction(format!(
-                "Account not found for {}",
-                address
-            ))),
+            None => {}
+        }
+        account.clear_lots();
+        account.set_balance(0);
+        for entry in account.mut_currency_balances().iter_mut() {
+            entry.set_balance(0);
         }
+        account.set_status(Account_Status::CLOSED);
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
+
+        Ok(forfeited)
     }
 
+    pub fn get_balance(&mut self, name: &str, number: u32) -> Result<u64, ApplyError> {
+        let address: String = calculate_account_address(name);
+        let mut container = self._load_accounts(&address)?;
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        Ok(account.get_balance())
+    }
+
+    pub fn get_velocity_limits(&mut self) -> Result<Vec<VelocityLimit>, ApplyError> {
+        match self._get_setting(VELOCITY_LIMITS_SETTING)? {
+            Some(value) => parse_velocity_limits(&value),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The merchant issues the points it deposits and redeems the points it withdraws.
+    /// Only the merchant a currency is named after, by its current or a previous key,
+    /// deposits into it. The amounts moved
+    /// are held to the velocity limits of both the account and the merchant.
     pub fn update_balance(
         &mut self,
+        merchant: &str,
         name: &str,
         number: u32,
-        amount: i32,
-    ) -> Result<i32, ApplyError> {
+        currency: &str,
+        change: BalanceChange,
+    ) -> Result<ReceiptPB, ApplyError> {
+        // Currencies keep the name of the key the merchant held when it started issuing
+        if !currency.is_empty()
+            && currency != merchant
+            && !matches!(change, BalanceChange::Withdrawal { .. })
+            && !self
+                ._merchant_keys(merchant)?
+                .iter()
+                .any(|key| key == currency)
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Only merchant {} issues points in its currency",
+                currency
+            )));
+        }
+
         let address: String = calculate_account_address(name);
-        let updated = self._update_balance(&address, name, number, amount);
-        match updated {
-            Ok(_) => self.get_balance(name, number),
-            Err(err) => Err(err),
+        let mut container = self._load_accounts(&address)?;
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        check_active(account)?;
+
+        let limits = self.get_velocity_limits()?;
+        let mut credited = None;
+        let (lot_ids, settlements, (direction, moved, timestamp)) = match change {
+            BalanceChange::Deposit {
+                amount,
+                earned_at,
+                expires_at,
+            } => (
+                vec![add_lot(
+                    account, currency, amount, earned_at, expires_at, merchant,
+                )?],
+                Vec::new(),
+                (Direction::Deposit, amount, earned_at),
+            ),
+            BalanceChange::Earning {
+                points,
+                earned_at,
+                expires_at,
+                tiers,
+            } => {
+                let points =
+                    apply_tier_multiplier(points, current_tier(&tiers, account, earned_at))?;
+                let lot_id = add_lot(account, currency, points, earned_at, expires_at, merchant)?;
+                let tier_changed = record_earnings(account, &tiers, points, earned_at);
+                credited = Some((points, tier_changed));
+                (
+                    vec![lot_id],
+                    Vec::new(),
+                    (Direction::Deposit, points, earned_at),
+                )
+            }
+            BalanceChange::Withdrawal { amount, timestamp } => {
+                let consumed = consume_lots(account, currency, amount, timestamp)?;
+                // Owners withdrawing for themselves leave no merchant to settle with
+                let settlements = if self.is_merchant(merchant)? {
+                    self._settle(merchant, &consumed, timestamp)?
+                } else {
+                    Vec::new()
+                };
+                (
+                    lot_ids(&consumed),
+                    settlements,
+                    (Direction::Withdrawal, amount, timestamp),
+                )
+            }
+        };
+        check_account_velocity(account, &limits, direction, moved, timestamp)?;
+        record_activity(account, timestamp);
+        let mut receipt = make_receipt(account, currency, lot_ids);
+        if let Some((points, tier_changed)) = credited {
+            receipt.set_points(points);
+            receipt.set_tier_changed(tier_changed);
         }
+
+        let mut entries = vec![(address, Self::_serialize(&container)?)];
+        entries.extend(settlements);
+        entries
+            .extend(self._check_merchant_velocity(&limits, merchant, direction, moved, timestamp)?);
+        self._set_state_entries(entries)?;
+
+        Ok(receipt)
+    }
+
+    /// Returns the amount that expired along with the receipt of the account
+    pub fn expire(
+        &mut self,
+        name: &str,
+        number: u32,
+        currency: &str,
+        timestamp: i64,
+    ) -> Result<(u64, ReceiptPB), ApplyError> {
+        let address: String = calculate_account_address(name);
+        let mut container = self._load_accounts(&address)?;
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        check_active(account)?;
+        let expired = expire_lots(account, currency, timestamp)?;
+        let receipt = make_receipt(account, currency, Vec::new());
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
+
+        Ok((expired, receipt))
     }
 
-    fn _update_balance(
+    /// The points leaving the account are held to the withdrawal limits of both the
+    /// account and the merchant moving them
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer(
         &mut self,
-        address: &str,
+        merchant: &str,
         name: &str,
         number: u32,
-        amount: i32,
+        recipient_name: &str,
+        recipient_number: u32,
+        currency: &str,
+        amount: u64,
+        timestamp: i64,
+    ) -> Result<(ReceiptPB, ReceiptPB), ApplyError> {
+        if amount == 0 {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Invalid transfer amount",
+            )));
+        }
+        if name == recipient_name && number == recipient_number {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Cannot transfer points to the same account",
+            )));
+        }
+
+        let address: String = calculate_account_address(name);
+        let recipient_address: String = calculate_account_address(recipient_name);
+
+        let limits = self.get_velocity_limits()?;
+        let mut container = self._load_accounts(&address)?;
+        // Accounts sharing a name live in the same container, so only load it once
+        let mut recipient_container = if recipient_address == address {
+            None
+        } else {
+            Some(self._load_accounts(&recipient_address)?)
+        };
+
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        check_active(account)?;
+        let consumed = consume_lots(account, currency, amount, timestamp)?;
+        check_account_velocity(account, &limits, Direction::Withdrawal, amount, timestamp)?;
+        record_activity(account, timestamp);
+        let receipt = make_receipt(account, currency, lot_ids(&consumed));
+
+        let recipient: &mut AccountPB = find_account(
+            recipient_container.as_mut().unwrap_or(&mut container),
+            recipient_name,
+            recipient_number,
+        )?;
+        check_active(recipient)?;
+        // Transferred points keep their age, expiry and currency
+        let mut recipient_lot_ids = Vec::with_capacity(consumed.len());
+        for lot in consumed {
+            recipient_lot_ids.push(add_lot(
+                recipient,
+                currency,
+                lot.get_amount(),
+                lot.get_earned_at(),
+                lot.get_expires_at(),
+                lot.get_issuer(),
+            )?);
+        }
+        record_activity(recipient, timestamp);
+        let recipient_receipt = make_receipt(recipient, currency, recipient_lot_ids);
+
+        // Both containers are written in a single call so the transfer is applied as a whole
+        let mut entries = vec![(address, Self::_serialize(&container)?)];
+        if let Some(recipient_container) = recipient_container {
+            entries.push((recipient_address, Self::_serialize(&recipient_container)?));
+        }
+        entries.extend(self._check_merchant_velocity(
+            &limits,
+            merchant,
+            Direction::Withdrawal,
+            amount,
+            timestamp,
+        )?);
+        self._set_state_entries(entries)?;
+
+        Ok((receipt, recipient_receipt))
+    }
+
+    pub fn set_earn_rule(&mut self, public_key: &str, rule: &EarnRulePB) -> Result<(), ApplyError> {
+        validate_earn_rule(rule)?;
+
+        let address: String = calculate_earn_rule_address(public_key);
+        let mut earn_rule: EarnRulePB = rule.clone();
+        earn_rule.set_public_key(String::from(public_key));
+
+        let mut container = self._load_earn_rules(&address)?;
+
+        // A merchant has a single rule, publishing a new one replaces it
+        container
+            .entries
+            .retain(|entry| entry.get_public_key() != public_key);
+        container.entries.push(earn_rule);
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
+
+        Ok(())
+    }
+
+    pub fn get_earn_rule(&mut self, public_key: &str) -> Result<Option<EarnRulePB>, ApplyError> {
+        let address: String = calculate_earn_rule_address(public_key);
+        let container = self._load_earn_rules(&address)?;
+        Ok(container
+            .entries
+            .into_iter()
+            .find(|entry| entry.get_public_key() == public_key))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn earn(
+        &mut self,
+        public_key: &str,
+        name: &str,
+        number: u32,
+        currency: &str,
+        purchase_amount: u64,
+        category: &str,
+        earned_at: i64,
+    ) -> Result<ReceiptPB, ApplyError> {
+        let rule = self.get_earn_rule(public_key)?.ok_or_else(|| {
+            ApplyError::InvalidTransaction(format!(
+                "No earn rule published by merchant {}",
+                public_key
+            ))
+        })?;
+        let points = calculate_points(&rule, purchase_amount, category)?;
+        if points == 0 {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Purchase does not earn any points",
+            )));
+        }
+        let expires_at = calculate_expiry(&rule, earned_at)?;
+        let tiers = self.get_tiers()?;
+
+        self.update_balance(
+            public_key,
+            name,
+            number,
+            currency,
+            BalanceChange::Earning {
+                points,
+                earned_at,
+                expires_at,
+                tiers,
+            },
+        )
+    }
+
+    pub fn set_catalog_item(
+        &mut self,
+        public_key: &str,
+        item: &CatalogItemPB,
     ) -> Result<(), ApplyError> {
-        let state_entries = self.context.get_state_entries(&[String::from(address)]);
-        match state_entries {
-            Ok(entries) => {
-                let container: AccountContainer =
-                    parse_from_bytes(&entries[0].1).expect("Error parsing state entries");
-                let mut accounts = container.get_entries().to_vec();
-                let account: &mut AccountPB = accounts
-                    .iter_mut()
-                    .find(|entry| entry.get_name() == name && entry.get_number() == number)
-                    .expect("Did not found accounts with that name");
-
-                let balance = account.get_balance();
-                // If a withdrawal is request, ensure the amount is not greater than the balance
-                if amount < 0 && (balance + amount) >= 0 {
-                    account.set_balance(balance + amount);
-                // If a deposit is requested, simply deposit the amount
-                } else if amount > 0 {
-                    account.set_balance(balance + amount);
-                } else {
-                    return Err(ApplyError::InvalidTransaction(String::from(
-                        "Invalid withdrawal amount",
-                    )));
-                }
-                // data = container.SerializeToString()
-                // updated_state = {}
-                // updated_state[address] = data
-                // self._context.set_state(updated_state, timeout=self._timeout)
-                let data = container
-                    .write_to_bytes()
-                    .expect("Could not serialize container");
-                self.context.set_state_entry(String::from(address), data)?;
-                Ok(())
+        validate_catalog_item(item)?;
+
+        let address: String = calculate_catalog_item_address(public_key, item.get_id());
+        let mut catalog_item: CatalogItemPB = item.clone();
+        catalog_item.set_public_key(String::from(public_key));
+
+        let mut container = self._load_catalog_items(&address)?;
+        // Publishing an item again replaces it, which is how merchants restock or reprice
+        container.entries.retain(|entry| {
+            entry.get_public_key() != public_key || entry.get_id() != item.get_id()
+        });
+        container.entries.push(catalog_item);
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
+
+        Ok(())
+    }
+
+    /// Debits the account and takes the units out of stock, `points` is the cost
+    /// the caller expects so a repriced item is not redeemed unknowingly. The cost is
+    /// held to the withdrawal limits of both the account and the merchant.
+    #[allow(clippy::too_many_arguments)]
+    pub fn redeem(
+        &mut self,
+        public_key: &str,
+        name: &str,
+        number: u32,
+        currency: &str,
+        item_id: &str,
+        quantity: u64,
+        points: u64,
+        timestamp: i64,
+    ) -> Result<ReceiptPB, ApplyError> {
+        let (item_address, mut items, position) = self
+            ._find_catalog_item(public_key, item_id)?
+            .ok_or_else(|| {
+                ApplyError::InvalidTransaction(format!(
+                    "Catalog item {} not found for merchant {}",
+                    item_id, public_key
+                ))
+            })?;
+        let item: &mut CatalogItemPB = &mut items.entries[position];
+        let cost = calculate_redemption_cost(item, quantity)?;
+        if cost != points {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Redeeming {} of {} costs {} points, not {}",
+                quantity, item_id, cost, points
+            )));
+        }
+        item.set_stock(item.get_stock() - quantity);
+
+        let limits = self.get_velocity_limits()?;
+        let address: String = calculate_account_address(name);
+        let mut container = self._load_accounts(&address)?;
+        let account: &mut AccountPB = find_account(&mut container, name, number)?;
+        check_active(account)?;
+        let consumed = consume_lots(account, currency, cost, timestamp)?;
+        check_account_velocity(account, &limits, Direction::Withdrawal, cost, timestamp)?;
+        record_activity(account, timestamp);
+        let receipt = make_receipt(account, currency, lot_ids(&consumed));
+        let settlements = self._settle(public_key, &consumed, timestamp)?;
+
+        let mut entries = vec![
+            (address, Self::_serialize(&container)?),
+            (item_address, Self::_serialize(&items)?),
+        ];
+        entries.extend(settlements);
+        entries.extend(self._check_merchant_velocity(
+            &limits,
+            public_key,
+            Direction::Withdrawal,
+            cost,
+            timestamp,
+        )?);
+        self._set_state_entries(entries)?;
+
+        Ok(receipt)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn issue_voucher(
+        &mut self,
+        public_key: &str,
+        name: &str,
+        number: u32,
+        voucher_id: &str,
+        discount: u64,
+        issued_at: i64,
+        expires_at: i64,
+    ) -> Result<(), ApplyError> {
+        let mut voucher: VoucherPB = VoucherPB::new();
+        voucher.set_public_key(String::from(public_key));
+        voucher.set_id(String::from(voucher_id));
+        voucher.set_name(String::from(name));
+        voucher.set_number(number);
+        voucher.set_discount(discount);
+        voucher.set_issued_at(issued_at);
+        voucher.set_expires_at(expires_at);
+        validate_voucher(&voucher)?;
+
+        let mut accounts = self._load_accounts(&calculate_account_address(name))?;
+        check_active(find_account(&mut accounts, name, number)?)?;
+
+        // Consumed vouchers stay in state so their ids cannot be issued again, not even
+        // under a new key of the merchant
+        if self._find_voucher(public_key, voucher_id)?.is_some() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Voucher {} has already been issued",
+                voucher_id
+            )));
+        }
+        let address: String = calculate_voucher_address(public_key, voucher_id);
+        let mut container = self._load_vouchers(&address)?;
+        container.entries.push(voucher);
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
+
+        Ok(())
+    }
+
+    pub fn consume_voucher(
+        &mut self,
+        public_key: &str,
+        name: &str,
+        number: u32,
+        voucher_id: &str,
+        timestamp: i64,
+    ) -> Result<u64, ApplyError> {
+        let mut accounts = self._load_accounts(&calculate_account_address(name))?;
+        check_active(find_account(&mut accounts, name, number)?)?;
+
+        let (address, mut container, position) =
+            self._find_voucher(public_key, voucher_id)?.ok_or_else(|| {
+                ApplyError::InvalidTransaction(format!(
+                    "Voucher {} not found for merchant {}",
+                    voucher_id, public_key
+                ))
+            })?;
+        let voucher: &mut VoucherPB = &mut container.entries[position];
+        check_consumable(voucher, name, number, timestamp)?;
+        voucher.set_consumed_at(timestamp);
+        let discount = voucher.get_discount();
+
+        self._set_state_entry(address, Self::_serialize(&container)?)?;
+
+        Ok(discount)
+    }
+
+    pub fn add_event(
+        &self,
+        event: ArcherEvent,
+        attributes: Vec<(String, String)>,
+    ) -> Result<(), ApplyError> {
+        self.context
+            .add_event(String::from(event.as_str()), attributes, &[])
+            .map_err(map_context_error)
+    }
+
+    pub fn add_receipt(&self, receipt: &ReceiptPB) -> Result<(), ApplyError> {
+        self.context
+            .add_receipt_data(&Self::_serialize(receipt)?)
+            .map_err(map_context_error)
+    }
+
+    /// Holds the amount to the velocity limit of the merchant, returning its entry for the
+    /// caller to write along with the accounts. Merchants are only written to when they
+    /// are limited.
+    fn _check_merchant_velocity(
+        &mut self,
+        limits: &[VelocityLimit],
+        merchant: &str,
+        direction: Direction,
+        amount: u64,
+        timestamp: i64,
+    ) -> Result<Option<(String, Vec<u8>)>, ApplyError> {
+        let limit = match find_limit(limits, Scope::Merchant, direction) {
+            Some(limit) => limit,
+            None => return Ok(None),
+        };
+        let merchant_address = calculate_merchant_address(merchant);
+        let mut merchants = self._load_merchants(&merchant_address)?;
+        // Owners moving their own points are only held to the limits of the account
+        let entry: &mut MerchantPB =
+            match merchants.entries.iter_mut().find(|entry| {
+                entry.get_public_key() == merchant && !entry.has_successor_public_key()
+            }) {
+                Some(entry) => entry,
+                None => return Ok(None),
+            };
+        let recent = match direction {
+            Direction::Deposit => entry.mut_recent_deposits(),
+            Direction::Withdrawal => entry.mut_recent_withdrawals(),
+        };
+        check_velocity(
+            recent,
+            Some(limit),
+            &format!("Merchant {}", merchant),
+            amount,
+            timestamp,
+        )?;
+        Ok(Some((merchant_address, Self::_serialize(&merchants)?)))
+    }
+
+    /// Settlement entries recording what the issuers of the consumed points owe the
+    /// redeemer, for the caller to write along with the account
+    fn _settle(
+        &mut self,
+        redeemer: &str,
+        consumed: &[LotPB],
+        timestamp: i64,
+    ) -> Result<Vec<(String, Vec<u8>)>, ApplyError> {
+        // Points issued under rotated keys are owed by the merchant's current key, and
+        // are not owed at all when the redeemer issued them itself
+        let mut consumed = consumed.to_vec();
+        for lot in consumed.iter_mut() {
+            if !lot.get_issuer().is_empty() && lot.get_issuer() != redeemer {
+                let issuer = self._current_merchant_key(lot.get_issuer())?;
+                lot.set_issuer(issuer);
+            }
+        }
+        let obligations = settlement_obligations(redeemer, &consumed);
+        if !obligations.is_empty() && timestamp == 0 {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "A timestamp is required to settle points issued by other merchants",
+            )));
+        }
+
+        let period_start = settlement_period_start(timestamp);
+        let mut entries = Vec::with_capacity(obligations.len());
+        for (issuer, amount) in obligations {
+            let address = calculate_settlement_address(&issuer, redeemer, period_start);
+            let mut container = self._load_settlements(&address)?;
+            accrue_settlement(&mut container, &issuer, redeemer, period_start, amount)?;
+            entries.push((address, Self::_serialize(&container)?));
+        }
+        Ok(entries)
+    }
+
+    /// Keys the merchant signed with, current first, so what it published under earlier
+    /// keys is still found after a rotation
+    fn _merchant_keys(&mut self, public_key: &str) -> Result<Vec<String>, ApplyError> {
+        let address: String = calculate_merchant_address(public_key);
+        let container: MerchantContainer = self._load_merchants(&address)?;
+        let mut keys = vec![String::from(public_key)];
+        if let Some(entry) = container
+            .entries
+            .iter()
+            .find(|entry| entry.get_public_key() == public_key && !entry.has_successor_public_key())
+        {
+            keys.extend(entry.get_previous_public_keys().iter().rev().cloned());
+        }
+        Ok(keys)
+    }
+
+    /// Follows the tombstones of rotated keys to the key the merchant signs with now.
+    /// Rotations never reuse a key, so the chain ends.
+    fn _current_merchant_key(&mut self, public_key: &str) -> Result<String, ApplyError> {
+        let mut current = String::from(public_key);
+        while let Some(successor) = self.get_successor(&current)? {
+            current = successor;
+        }
+        Ok(current)
+    }
+
+    fn _move_earn_rule(
+        &mut self,
+        public_key: &str,
+        new_public_key: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>, ApplyError> {
+        let address: String = calculate_earn_rule_address(public_key);
+        let mut container = self._load_earn_rules(&address)?;
+        let position = match container
+            .entries
+            .iter()
+            .position(|entry| entry.get_public_key() == public_key)
+        {
+            Some(position) => position,
+            None => return Ok(Vec::new()),
+        };
+        let mut rule: EarnRulePB = container.entries.remove(position);
+        rule.set_public_key(String::from(new_public_key));
+
+        let new_address: String = calculate_earn_rule_address(new_public_key);
+        if new_address == address {
+            container.entries.push(rule);
+            return Ok(vec![(address, Self::_serialize(&container)?)]);
+        }
+        let mut new_container = self._load_earn_rules(&new_address)?;
+        new_container.entries.push(rule);
+        Ok(vec![
+            (address, Self::_serialize(&container)?),
+            (new_address, Self::_serialize(&new_container)?),
+        ])
+    }
+
+    /// Address and container of the item along with its position in the container
+    fn _find_catalog_item(
+        &mut self,
+        public_key: &str,
+        item_id: &str,
+    ) -> Result<Option<(String, CatalogItemContainer, usize)>, ApplyError> {
+        for key in self._merchant_keys(public_key)? {
+            let address: String = calculate_catalog_item_address(&key, item_id);
+            let container = self._load_catalog_items(&address)?;
+            if let Some(position) = container
+                .entries
+                .iter()
+                .position(|entry| entry.get_public_key() == key && entry.get_id() == item_id)
+            {
+                return Ok(Some((address, container, position)));
             }
-            Err(_) => Err(ApplyError::InvalidTransaction(format!(
+        }
+        Ok(None)
+    }
+
+    /// Address and container of the voucher along with its position in the container
+    fn _find_voucher(
+        &mut self,
+        public_key: &str,
+        voucher_id: &str,
+    ) -> Result<Option<(String, VoucherContainer, usize)>, ApplyError> {
+        for key in self._merchant_keys(public_key)? {
+            let address: String = calculate_voucher_address(&key, voucher_id);
+            let container = self._load_vouchers(&address)?;
+            if let Some(position) = container
+                .entries
+                .iter()
+                .position(|entry| entry.get_public_key() == key && entry.get_id() == voucher_id)
+            {
+                return Ok(Some((address, container, position)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn _load_accounts(&mut self, address: &str) -> Result<AccountContainer, ApplyError> {
+        match self._get_state_entry(address)? {
+            Some(data) => parse_accounts(&data, address),
+            None => Err(ApplyError::InvalidTransaction(format!(
                 "Account not found for {}",
                 address
             ))),
         }
     }
 
-    // fn _load_accounts(&mut self, name: &str) -> Result<HashMap<String, AccountPB>, ApplyError> {
-    //     let address: String = calculate_account_address(name);
-    //     let mut accounts = HashMap::new();
+    fn _load_merchants(&mut self, address: &str) -> Result<MerchantContainer, ApplyError> {
+        match self._get_state_entry(address)? {
+            Some(data) => parse_from_bytes(&data).map_err(|_| {
+                ApplyError::InternalError(format!("Invalid merchant container at {}", address))
+            }),
+            None => Ok(MerchantContainer::new()),
+        }
+    }
+
+    fn _load_earn_rules(&mut self, address: &str) -> Result<EarnRuleContainer, ApplyError> {
+        match self._get_state_entry(address)? {
+            Some(data) => parse_from_bytes(&data).map_err(|_| {
+                ApplyError::InternalError(format!("Invalid earn rule container at {}", address))
+            }),
+            None => Ok(EarnRuleContainer::new()),
+        }
+    }
+
+    fn _load_catalog_items(&mut self, address: &str) -> Result<CatalogItemContainer, ApplyError> {
+        match self._get_state_entry(address)? {
+            Some(data) => parse_from_bytes(&data).map_err(|_| {
+                ApplyError::InternalError(format!("Invalid catalog item container at {}", address))
+            }),
+            None => Ok(CatalogItemContainer::new()),
+        }
+    }
+
+    fn _load_vouchers(&mut self, address: &str) -> Result<VoucherContainer, ApplyError> {
+        match self._get_state_entry(address)? {
+            Some(data) => parse_from_bytes(&data).map_err(|_| {
+                ApplyError::InternalError(format!("Invalid voucher container at {}", address))
+            }),
+            None => Ok(VoucherContainer::new()),
+        }
+    }
+
+    fn _load_settlements(&mut self, address: &str) -> Result<SettlementContainer, ApplyError> {
+        match self._get_state_entry(address)? {
+            Some(data) => parse_from_bytes(&data).map_err(|_| {
+                ApplyError::InternalError(format!("Invalid settlement container at {}", address))
+            }),
+            None => Ok(SettlementContainer::new()),
+        }
+    }
+
+    fn _get_setting(&mut self, key: &str) -> Result<Option<String>, ApplyError> {
+        let address: String = calculate_setting_address(key);
+        let setting: Setting = match self._get_state_entry(&address)? {
+            Some(data) => parse_from_bytes(&data).map_err(|_| {
+                ApplyError::InternalError(format!("Invalid setting at {}", address))
+            })?,
+            None => return Ok(None),
+        };
+        Ok(setting
+            .get_entries()
+            .iter()
+            .find(|entry| entry.get_key() == key)
+            .map(|entry| String::from(entry.get_value())))
+    }
+
+    fn _get_state_entry(&self, address: &str) -> Result<Option<Vec<u8>>, ApplyError> {
+        self.context
+            .get_state_entry(address)
+            .map_err(map_context_error)
+    }
+
+    fn _set_state_entry(&self, address: String, data: Vec<u8>) -> Result<(), ApplyError> {
+        self.context
+            .set_state_entry(address, data)
+            .map_err(map_context_error)
+    }
+
+    fn _set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ApplyError> {
+        self.context
+            .set_state_entries(entries)
+            .map_err(map_context_error)
+    }
+
+    fn _serialize<M: Message>(container: &M) -> Result<Vec<u8>, ApplyError> {
+        container
+            .write_to_bytes()
+            .map_err(|_| ApplyError::InternalError(String::from("Error serializing container")))
+    }
+}
+
+/// Accounts written before balances were widened hold them zigzag-encoded on the legacy
+/// tag, they move to the widened one and are written back there
+fn parse_accounts(data: &[u8], address: &str) -> Result<AccountContainer, ApplyError> {
+    let mut container: AccountContainer = parse_from_bytes(data).map_err(|_| {
+        ApplyError::InternalError(format!("Invalid account container at {}", address))
+    })?;
+    for account in container.entries.iter_mut() {
+        if account.has_legacy_balance() {
+            let balance = u64::try_from(account.get_legacy_balance()).map_err(|_| {
+                ApplyError::InternalError(format!("Negative balance at {}", address))
+            })?;
+            account.clear_legacy_balance();
+            if !account.has_balance() {
+                account.set_balance(balance);
+            }
+        }
+    }
+    Ok(container)
+}
+
+fn find_account<'c>(
+    container: &'c mut AccountContainer,
+    name: &str,
+    number: u32,
+) -> Result<&'c mut AccountPB, ApplyError> {
+    container
+        .entries
+        .iter_mut()
+        .find(|entry| entry.get_name() == name && entry.get_number() == number)
+        .ok_or_else(|| {
+            ApplyError::InvalidTransaction(format!("Account not found for {} {}", name, number))
+        })
+}
 
-    //     if self.address_map.contains_key(&address) {
-    //         if let Some(ref serialized_games) = self.address_map[&address] {
+fn make_receipt(account: &AccountPB, currency: &str, lot_ids: Vec<u64>) -> ReceiptPB {
+    let mut receipt = ReceiptPB::new();
+    receipt.set_name(String::from(account.get_name()));
+    receipt.set_number(account.get_number());
+    receipt.set_balance(currency_balance(account, currency));
+    receipt.set_lot_ids(lot_ids);
+    if !currency.is_empty() {
+        receipt.set_currency(String::from(currency));
+    }
+    if account.has_tier() {
+        receipt.set_tier(String::from(account.get_tier()));
+    }
+    receipt
+}
 
-    //         }
-    //     }
-    // }
+fn lot_ids(lots: &[LotPB]) -> Vec<u64> {
+    lots.iter().map(|lot| lot.get_id()).collect()
+}
 
-    /*
-    def get_agent(self, public_key):
-        """Gets the agent associated with the public_key
+fn check_account_velocity(
+    account: &mut AccountPB,
+    limits: &[VelocityLimit],
+    direction: Direction,
+    amount: u64,
+    timestamp: i64,
+) -> Result<(), ApplyError> {
+    let subject = format!("Account {} {}", account.get_name(), account.get_number());
+    let recent = match direction {
+        Direction::Deposit => account.mut_recent_deposits(),
+        Direction::Withdrawal => account.mut_recent_withdrawals(),
+    };
+    check_velocity(
+        recent,
+        find_limit(limits, Scope::Account, direction),
+        &subject,
+        amount,
+        timestamp,
+    )
+}
 
-        Args:
-            public_key (str): The public key of the agent
+fn check_active(account: &AccountPB) -> Result<(), ApplyError> {
+    match account.get_status() {
+        Account_Status::ACTIVE => Ok(()),
+        status => Err(ApplyError::InvalidTransaction(format!(
+            "Account {} {} is {:?}",
+            account.get_name(),
+            account.get_number(),
+            status
+        ))),
+    }
+}
 
-        Returns:
-            agent_pb2.Agent: Agent with the provided public_key
-        """
-        address = addresser.get_agent_address(public_key)
-        state_entries = self._context.get_state(
-            addresses=[address], timeout=self._timeout)
-        if state_entries:
-            container = agent_pb2.AgentContainer()
-            container.ParseFromString(state_entries[0].data)
-            for agent in container.entries:
-                if agent.public_key == public_key:
-                    return agent
+/// The validator refuses addresses the transaction did not declare, which is the
+/// transaction's fault. Any other failure is on our side and the transaction can be retried.
+fn map_context_error(err: ContextError) -> ApplyError {
+    match err {
+        ContextError::AuthorizationError(_) => ApplyError::InvalidTransaction(format!("{}", err)),
+        _ => ApplyError::InternalError(format!("{}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        return None
-    */
+    #[test]
+    fn maps_context_errors() {
+        let unauthorized = ContextError::AuthorizationError(String::from("Tried to get address"));
+        assert!(matches!(
+            map_context_error(unauthorized),
+            ApplyError::InvalidTransaction(_)
+        ));
+        let unreachable = ContextError::ResponseAttributeError(String::from("No response"));
+        assert!(matches!(
+            map_context_error(unreachable),
+            ApplyError::InternalError(_)
+        ));
+    }
+
+    #[test]
+    fn finds_accounts() {
+        let mut account = AccountPB::new();
+        account.set_name(String::from("John Doe"));
+        account.set_number(12345);
+        let mut container = AccountContainer::new();
+        container.entries.push(account);
+
+        assert!(find_account(&mut container, "John Doe", 12345).is_ok());
+        assert!(matches!(
+            find_account(&mut container, "John Doe", 54321),
+            Err(ApplyError::InvalidTransaction(_))
+        ));
+    }
+
+    #[test]
+    fn checks_active_accounts() {
+        let mut account = AccountPB::new();
+        assert!(check_active(&account).is_ok());
+        account.set_status(Account_Status::FROZEN);
+        assert!(check_active(&account).is_err());
+        account.set_status(Account_Status::CLOSED);
+        assert!(check_active(&account).is_err());
+    }
 }
@@ -0,0 +1,333 @@
+//! In-memory stand-in for the validator side of the handler. The transaction context keeps
+//! state, events and receipt data where tests can inspect them, holds the handler to the
+//! addresses the request declares, and the key pairs sign process requests the way
+//! clients do.
+
+use openssl::sha::sha512;
+use protobuf::{parse_from_bytes, Message, RepeatedField};
+use sawtooth_sdk::messages::processor::TpProcessRequest;
+use sawtooth_sdk::messages::transaction::TransactionHeader;
+use sawtooth_sdk::processor::handler::{
+    ApplyError, ContextError, TransactionContext, TransactionHandler,
+};
+use sawtooth_sdk::signing::secp256k1::{Secp256k1Context, Secp256k1PrivateKey};
+use sawtooth_sdk::signing::Context;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::handler::ArcherTransactionHandler;
+use archer::addresses::*;
+use archer::{to_hex_string, FamilyVersion, NAME};
+use archer_protobuf::payload::{Payload as PayloadPB, PayloadEnvelope, Payload_Action};
+
+/// Event type and attributes
+pub type RecordedEvent = (String, Vec<(String, String)>);
+
+#[derive(Default)]
+pub struct MockTransactionContext {
+    pub state: RefCell<HashMap<String, Vec<u8>>>,
+    pub events: RefCell<Vec<RecordedEvent>>,
+    pub receipts: RefCell<Vec<Vec<u8>>>,
+    /// Inputs and outputs of the request being processed, none outside of `process`
+    declared: RefCell<Option<Addresses>>,
+}
+
+impl MockTransactionContext {
+    /// Applies the request the way the validator does, dropping whatever a failed
+    /// transaction wrote
+    pub fn process(&mut self, request: &TpProcessRequest) -> Result<(), ApplyError> {
+        let state = self.state.borrow().clone();
+        let events = self.events.borrow().len();
+        let receipts = self.receipts.borrow().len();
+
+        let header = request.get_header();
+        self.declared.replace(Some((
+            header.get_inputs().to_vec(),
+            header.get_outputs().to_vec(),
+        )));
+        let result = ArcherTransactionHandler::new(NAME).apply(request, self);
+        self.declared.replace(None);
+        if result.is_err() {
+            self.state.replace(state);
+            self.events.borrow_mut().truncate(events);
+            self.receipts.borrow_mut().truncate(receipts);
+        }
+        result
+    }
+
+    /// Rejects addresses outside of the declared inputs or outputs, as the validator does
+    fn check_declared(
+        &self,
+        addresses: &[String],
+        select: fn(&Addresses) -> &Vec<String>,
+        kind: &str,
+    ) -> Result<(), ContextError> {
+        if let Some(declared) = self.declared.borrow().as_ref() {
+            let prefixes = select(declared);
+            if let Some(address) = addresses
+                .iter()
+                .find(|address| !prefixes.iter().any(|prefix| address.starts_with(prefix)))
+            {
+                return Err(ContextError::AuthorizationError(format!(
+                    "Address {} is not declared as an {}",
+                    address, kind
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TransactionContext for MockTransactionContext {
+    fn get_state_entries(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+        self.check_declared(addresses, |declared| &declared.0, "input")?;
+        let state = self.state.borrow();
+        Ok(addresses
+            .iter()
+            .filter_map(|address| {
+                state
+                    .get(address)
+                    .map(|data| (address.clone(), data.clone()))
+            })
+            .collect())
+    }
+
+    fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+        let addresses: Vec<String> = entries.iter().map(|(address, _)| address.clone()).collect();
+        self.check_declared(&addresses, |declared| &declared.1, "output")?;
+        let mut state = self.state.borrow_mut();
+        for (address, data) in entries {
+            state.insert(address, data);
+        }
+        Ok(())
+    }
+
+    fn delete_state_entries(&self, addresses: &[String]) -> Result<Vec<String>, ContextError> {
+        self.check_declared(addresses, |declared| &declared.1, "output")?;
+        let mut state = self.state.borrow_mut();
+        Ok(addresses
+            .iter()
+            .filter(|address| state.remove(*address).is_some())
+            .cloned()
+            .collect())
+    }
+
+    fn add_receipt_data(&self, data: &[u8]) -> Result<(), ContextError> {
+        self.receipts.borrow_mut().push(data.to_vec());
+        Ok(())
+    }
+
+    fn add_event(
+        &self,
+        event_type: String,
+        attributes: Vec<(String, String)>,
+        _data: &[u8],
+    ) -> Result<(), ContextError> {
+        self.events.borrow_mut().push((event_type, attributes));
+        Ok(())
+    }
+}
+
+pub struct KeyPair {
+    pub private_key: &'static str,
+    pub public_key: &'static str,
+}
+
+pub const MERCHANT: KeyPair = KeyPair {
+    private_key: "d9845323f07cb76835fad56a00d2f382a0a3bd4757c8c5c11d42fd660d1db089",
+    public_key: "0267ab07f645480ca8828d600838148c7d6807bca4c80c761fc02eb73c179c59b1",
+};
+pub const OTHER_MERCHANT: KeyPair = KeyPair {
+    private_key: "a30acf54c0a26b93e45228eda0288c3b693a804be9b21b53c7ade24d70aa4a14",
+    public_key: "02f11d00e6287783979b45aabe09e29204d4f00049269b0ad98ec4caf40f432719",
+};
+pub const STRANGER: KeyPair = KeyPair {
+    private_key: "9dfe55166e88f6f5cf94217262bbc4be7b1d79d75afba32642b6720c3fd145ce",
+    public_key: "02e604a77babd2d865516fa0561a0fb8951b3cd849adca8eec10dc3032f6f4d0e7",
+};
+pub const ADMIN: KeyPair = KeyPair {
+    private_key: "f9a1cfc59e7840f6fb0907ef9e87505eb6e5bbd732429476973942801c1aa0f4",
+    public_key: "02164d7b7176c5b60652451a4a17cfdb18c6e325d176ad76c45626d1303aeaf95e",
+};
+pub const OWNER: KeyPair = KeyPair {
+    private_key: "2f1e7b7a130d7ba9da0068b3bb0ba1d79e7e77110302c9f746c3c2a63fe40088",
+    public_key: "026a2c795a9776f75464aa3bda3534c3154a6e91b357b1181d3f515110f84b67c5",
+};
+
+const KEY_PAIRS: [&KeyPair; 5] = [&MERCHANT, &OTHER_MERCHANT, &STRANGER, &ADMIN, &OWNER];
+
+static NONCE: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    // Setting up a context is far slower than signing with it
+    static SIGNING_CONTEXT: Secp256k1Context = Secp256k1Context::new();
+}
+
+impl KeyPair {
+    /// Key pair of a public key, so tests can name signers by their key
+    pub fn find(public_key: &str) -> &'static KeyPair {
+        KEY_PAIRS
+            .iter()
+            .find(|key_pair| key_pair.public_key == public_key)
+            .copied()
+            .unwrap_or_else(|| panic!("No test key pair for {}", public_key))
+    }
+
+    pub fn sign(&self, message: &[u8]) -> String {
+        let private_key = Secp256k1PrivateKey::from_hex(self.private_key).unwrap();
+        SIGNING_CONTEXT.with(|context| context.sign(message, &private_key).unwrap())
+    }
+
+    /// Request for the payload as the validator hands it over, with a header signed by
+    /// this key and declaring the given addresses
+    pub fn make_request(
+        &self,
+        family_version: FamilyVersion,
+        payload_data: Vec<u8>,
+        (inputs, outputs): Addresses,
+    ) -> TpProcessRequest {
+        let mut header = TransactionHeader::new();
+        header.set_family_name(String::from(NAME));
+        header.set_family_version(String::from(family_version.as_str()));
+        header.set_signer_public_key(String::from(self.public_key));
+        header.set_batcher_public_key(String::from(self.public_key));
+        header.set_nonce(NONCE.fetch_add(1, Ordering::Relaxed).to_string());
+        header.set_inputs(RepeatedField::from_vec(inputs));
+        header.set_outputs(RepeatedField::from_vec(outputs));
+        header.set_payload_sha512(to_hex_string(&sha512(&payload_data)));
+
+        let mut request = TpProcessRequest::new();
+        request.set_signature(self.sign(&header.write_to_bytes().unwrap()));
+        request.set_header(header);
+        request.set_payload(payload_data);
+        request
+    }
+}
+
+/// Addresses a client declares for the payload, from the same lists the API builds its
+/// transactions with. Payloads that do not parse only declare the signer.
+pub fn payload_addresses(
+    signer: &str,
+    family_version: FamilyVersion,
+    payload_data: &[u8],
+) -> Addresses {
+    let payloads: Vec<PayloadPB> = match family_version {
+        FamilyVersion::V1 => parse_from_bytes(payload_data)
+            .map(|payload| vec![payload])
+            .unwrap_or_default(),
+        FamilyVersion::V2 => parse_from_bytes(payload_data)
+            .map(|mut envelope: PayloadEnvelope| envelope.take_payloads().into_vec())
+            .unwrap_or_default(),
+    };
+    let operations = payloads
+        .iter()
+        .map(|payload| operation_addresses(signer, payload))
+        .collect();
+    transaction_addresses(signer, operations)
+}
+
+fn operation_addresses(signer: &str, payload: &PayloadPB) -> Addresses {
+    let name = payload.get_name();
+    match payload.get_action() {
+        Payload_Action::DEPOSIT => deposit_addresses(signer, name),
+        Payload_Action::WITHDRAW => withdraw_addresses(signer, name),
+        Payload_Action::UPDATE_NUMBER => update_number_addresses(signer, name),
+        Payload_Action::ADD_ACCOUNT => add_account_addresses(signer, name),
+        Payload_Action::ADD_MERCHANT => add_merchant_addresses(signer),
+        Payload_Action::TRANSFER => transfer_addresses(signer, name, payload.get_recipient_name()),
+        Payload_Action::SET_EARN_RULE => set_earn_rule_addresses(signer),
+        Payload_Action::EARN => earn_addresses(signer, name),
+        Payload_Action::EXPIRE => expire_addresses(signer, name),
+        Payload_Action::SET_CATALOG_ITEM => {
+            set_catalog_item_addresses(signer, payload.get_catalog_item().get_id())
+        }
+        Payload_Action::REDEEM => redeem_addresses(signer, name),
+        Payload_Action::ISSUE_VOUCHER => {
+            issue_voucher_addresses(signer, name, payload.get_voucher_id())
+        }
+        Payload_Action::CONSUME_VOUCHER => consume_voucher_addresses(signer, name),
+        Payload_Action::FREEZE | Payload_Action::UNFREEZE | Payload_Action::CLOSE => {
+            status_addresses(name)
+        }
+        Payload_Action::ROTATE_MERCHANT_KEY => {
+            rotate_merchant_key_addresses(signer, payload.get_public_key())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sawtooth_sdk::signing::secp256k1::Secp256k1PublicKey;
+
+    #[test]
+    fn key_pairs_match() {
+        let context = Secp256k1Context::new();
+        for key_pair in KEY_PAIRS.iter() {
+            let private_key = Secp256k1PrivateKey::from_hex(key_pair.private_key).unwrap();
+            assert_eq!(
+                context.get_public_key(&private_key).unwrap().as_hex(),
+                key_pair.public_key
+            );
+        }
+    }
+
+    #[test]
+    fn signs_requests() {
+        let payload_data = vec![1, 2, 3];
+        let request = MERCHANT.make_request(
+            FamilyVersion::V2,
+            payload_data.clone(),
+            (Vec::new(), Vec::new()),
+        );
+        let header = request.get_header();
+        assert_eq!(header.get_signer_public_key(), MERCHANT.public_key);
+        assert_eq!(
+            header.get_payload_sha512(),
+            to_hex_string(&sha512(&payload_data))
+        );
+
+        let public_key = Secp256k1PublicKey::from_hex(MERCHANT.public_key).unwrap();
+        let header_bytes = header.write_to_bytes().unwrap();
+        let context = Secp256k1Context::new();
+        assert!(context
+            .verify(request.get_signature(), &header_bytes, &public_key)
+            .unwrap());
+        assert!(!context
+            .verify(request.get_signature(), &payload_data, &public_key)
+            .unwrap());
+    }
+
+    #[test]
+    fn rejects_undeclared_addresses() {
+        let mut payload = PayloadPB::new();
+        payload.set_action(Payload_Action::ADD_MERCHANT);
+        payload.set_name(String::from("Shop"));
+        payload.set_timestamp(10000);
+        let payload_data = payload.write_to_bytes().unwrap();
+
+        let mut context = MockTransactionContext::default();
+        let (inputs, _) = payload_addresses(MERCHANT.public_key, FamilyVersion::V1, &payload_data);
+        let request = MERCHANT.make_request(
+            FamilyVersion::V1,
+            payload_data.clone(),
+            (inputs, Vec::new()),
+        );
+        match context.process(&request) {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert!(message.contains("not declared as an output"))
+            }
+            result => panic!("Undeclared output was accepted: {:?}", result),
+        }
+        assert!(context.state.borrow().is_empty());
+
+        let addresses = payload_addresses(MERCHANT.public_key, FamilyVersion::V1, &payload_data);
+        let request = MERCHANT.make_request(FamilyVersion::V1, payload_data, addresses);
+        context.process(&request).unwrap();
+        assert_eq!(context.state.borrow().len(), 1);
+    }
+}
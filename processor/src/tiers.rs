@@ -0,0 +1,274 @@
+use sawtooth_sdk::processor::handler::ApplyError;
+use std::convert::TryFrom;
+
+use super::lots::MAX_BALANCE;
+use archer_protobuf::account::{Account as AccountPB, EarningPeriod as EarningPeriodPB};
+
+// Rolling earnings are counted over the last twelve 30-day periods, the current one included
+pub const EARNING_PERIOD: i64 = 30 * 86400;
+pub const ROLLING_PERIODS: i64 = 12;
+
+// Multipliers are expressed in percent, like those of earn rules
+const BASE_MULTIPLIER: u32 = 100;
+
+/// Tier of the `archer.tiers` setting, reached once both thresholds are met
+#[derive(Debug, PartialEq)]
+pub struct Tier {
+    pub name: String,
+    pub lifetime_threshold: u64,
+    pub rolling_threshold: u64,
+    pub multiplier: u32,
+}
+
+/// Parses the comma-separated `name:lifetime_threshold:rolling_threshold:multiplier`
+/// entries of the setting, an empty value configures no tiers
+pub fn parse_tiers(value: &str) -> Result<Vec<Tier>, ApplyError> {
+    let mut tiers: Vec<Tier> = Vec::new();
+    for entry in value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let invalid = || ApplyError::InvalidTransaction(format!("Invalid tier entry {}", entry));
+        let parts: Vec<&str> = entry.split(':').map(str::trim).collect();
+        if parts.len() != 4 || parts[0].is_empty() {
+            return Err(invalid());
+        }
+        let tier = Tier {
+            name: String::from(parts[0]),
+            lifetime_threshold: parts[1].parse().map_err(|_| invalid())?,
+            rolling_threshold: parts[2].parse().map_err(|_| invalid())?,
+            multiplier: parts[3].parse().map_err(|_| invalid())?,
+        };
+        if tier.multiplier == 0 {
+            return Err(invalid());
+        }
+        if tiers.iter().any(|other| other.name == tier.name) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Duplicate tier {}",
+                tier.name
+            )));
+        }
+        tiers.push(tier);
+    }
+    Ok(tiers)
+}
+
+pub fn earning_period_start(timestamp: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(EARNING_PERIOD)
+}
+
+// Periods starting at or before this one have left the rolling window
+fn rolling_cutoff(timestamp: i64) -> i64 {
+    earning_period_start(timestamp).saturating_sub(ROLLING_PERIODS * EARNING_PERIOD)
+}
+
+/// Points earned within the rolling twelve months as of the timestamp
+pub fn rolling_earnings(account: &AccountPB, timestamp: i64) -> u64 {
+    let cutoff = rolling_cutoff(timestamp);
+    account
+        .get_earning_periods()
+        .iter()
+        .filter(|period| period.get_period_start() > cutoff)
+        .fold(0, |total, period| total.saturating_add(period.get_amount()))
+}
+
+/// Highest tier the earnings qualify for, tiers being listed lowest first
+pub fn qualifying_tier(tiers: &[Tier], lifetime: u64, rolling: u64) -> Option<&Tier> {
+    tiers
+        .iter()
+        .rev()
+        .find(|tier| lifetime >= tier.lifetime_threshold && rolling >= tier.rolling_threshold)
+}
+
+/// Tier of the account as of the timestamp, earnings that left the window no longer count
+pub fn current_tier<'t>(
+    tiers: &'t [Tier],
+    account: &AccountPB,
+    timestamp: i64,
+) -> Option<&'t Tier> {
+    qualifying_tier(
+        tiers,
+        account.get_lifetime_earned(),
+        rolling_earnings(account, timestamp),
+    )
+}
+
+pub fn apply_tier_multiplier(points: u64, tier: Option<&Tier>) -> Result<u64, ApplyError> {
+    let multiplier = tier.map(|tier| tier.multiplier).unwrap_or(BASE_MULTIPLIER);
+    let points = u128::from(points) * u128::from(multiplier) / u128::from(BASE_MULTIPLIER);
+    // Multipliers below 100 round small earnings down to nothing
+    if points == 0 {
+        if let Some(tier) = tier {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Purchase earns no points at tier {}",
+                tier.name
+            )));
+        }
+    }
+    u64::try_from(points)
+        .ok()
+        .filter(|points| *points <= MAX_BALANCE)
+        .ok_or_else(|| {
+            ApplyError::InvalidTransaction(String::from(
+                "Purchase earns more points than an account can hold",
+            ))
+        })
+}
+
+/// Counts the points toward the lifetime and rolling totals, dropping the periods that
+/// left the window, then moves the account to the tier they reach. Returns whether the
+/// tier changed.
+pub fn record_earnings(
+    account: &mut AccountPB,
+    tiers: &[Tier],
+    points: u64,
+    timestamp: i64,
+) -> bool {
+    account.set_lifetime_earned(account.get_lifetime_earned().saturating_add(points));
+
+    let cutoff = rolling_cutoff(timestamp);
+    let period_start = earning_period_start(timestamp);
+    let periods = account.mut_earning_periods();
+    periods.retain(|period| period.get_period_start() > cutoff);
+    match periods
+        .iter_mut()
+        .find(|period| period.get_period_start() == period_start)
+    {
+        Some(period) => period.set_amount(period.get_amount().saturating_add(points)),
+        None => {
+            let mut period = EarningPeriodPB::new();
+            period.set_period_start(period_start);
+            period.set_amount(points);
+            periods.push(period);
+            periods.sort_by_key(|period| period.get_period_start());
+        }
+    }
+
+    let tier = current_tier(tiers, account, timestamp).map(|tier| tier.name.as_str());
+    let previous = if account.has_tier() {
+        Some(account.get_tier())
+    } else {
+        None
+    };
+    if tier == previous {
+        return false;
+    }
+    match tier {
+        Some(tier) => account.set_tier(String::from(tier)),
+        None => account.clear_tier(),
+    }
+    account.set_tier_changed_at(timestamp);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_tiers() -> Vec<Tier> {
+        parse_tiers("bronze:0:0:100, silver:1000:500:125, gold:10000:2000:150").unwrap()
+    }
+
+    #[test]
+    fn parses_tier_settings() {
+        let tiers = make_tiers();
+        assert_eq!(tiers.len(), 3);
+        assert_eq!(
+            tiers[1],
+            Tier {
+                name: String::from("silver"),
+                lifetime_threshold: 1000,
+                rolling_threshold: 500,
+                multiplier: 125,
+            }
+        );
+        assert!(parse_tiers("").unwrap().is_empty());
+        assert!(parse_tiers("silver:1000:500").is_err());
+        assert!(parse_tiers(":1000:500:125").is_err());
+        assert!(parse_tiers("silver:-1:500:125").is_err());
+        assert!(parse_tiers("silver:1000:500:0").is_err());
+        assert!(parse_tiers("silver:1000:500:125,silver:2000:500:150").is_err());
+    }
+
+    #[test]
+    fn qualifies_for_highest_tier_reached() {
+        let tiers = make_tiers();
+        let name = |tier: Option<&Tier>| tier.map(|tier| tier.name.clone());
+        assert_eq!(
+            name(qualifying_tier(&tiers, 0, 0)),
+            Some(String::from("bronze"))
+        );
+        assert_eq!(
+            name(qualifying_tier(&tiers, 1000, 500)),
+            Some(String::from("silver"))
+        );
+        // Both thresholds must be met
+        assert_eq!(
+            name(qualifying_tier(&tiers, 50000, 999)),
+            Some(String::from("silver"))
+        );
+        assert_eq!(
+            name(qualifying_tier(&tiers, 10000, 2000)),
+            Some(String::from("gold"))
+        );
+        assert_eq!(qualifying_tier(&[], 10000, 2000), None);
+    }
+
+    #[test]
+    fn applies_tier_multipliers() {
+        let tiers = make_tiers();
+        assert_eq!(apply_tier_multiplier(101, None).unwrap(), 101);
+        assert_eq!(apply_tier_multiplier(101, Some(&tiers[1])).unwrap(), 126);
+        assert_eq!(apply_tier_multiplier(100, Some(&tiers[2])).unwrap(), 150);
+        assert!(apply_tier_multiplier(MAX_BALANCE, Some(&tiers[2])).is_err());
+
+        let tier = Tier {
+            name: String::from("probation"),
+            lifetime_threshold: 0,
+            rolling_threshold: 0,
+            multiplier: 50,
+        };
+        assert_eq!(apply_tier_multiplier(2, Some(&tier)).unwrap(), 1);
+        match apply_tier_multiplier(1, Some(&tier)) {
+            Err(ApplyError::InvalidTransaction(message)) => {
+                assert_eq!(message, "Purchase earns no points at tier probation")
+            }
+            _ => panic!("Earnings rounded down to no points were accepted"),
+        }
+    }
+
+    #[test]
+    fn rolls_earnings_over_twelve_periods() {
+        let tiers = make_tiers();
+        let mut account = AccountPB::new();
+        assert!(record_earnings(&mut account, &tiers, 600, 0));
+        assert_eq!(account.get_tier(), "bronze");
+        assert!(record_earnings(&mut account, &tiers, 600, EARNING_PERIOD));
+        assert_eq!(account.get_tier(), "silver");
+        assert_eq!(account.get_tier_changed_at(), EARNING_PERIOD);
+        assert_eq!(account.get_lifetime_earned(), 1200);
+        assert_eq!(rolling_earnings(&account, EARNING_PERIOD), 1200);
+
+        // The first period leaves the window twelve periods later
+        let timestamp = ROLLING_PERIODS * EARNING_PERIOD;
+        assert_eq!(rolling_earnings(&account, timestamp), 600);
+        assert_eq!(rolling_earnings(&account, timestamp + EARNING_PERIOD), 0);
+
+        assert!(record_earnings(
+            &mut account,
+            &tiers,
+            100,
+            timestamp + EARNING_PERIOD
+        ));
+        assert_eq!(account.get_tier(), "bronze");
+        assert_eq!(account.get_lifetime_earned(), 1300);
+        assert_eq!(account.get_earning_periods().len(), 1);
+        assert!(!record_earnings(
+            &mut account,
+            &tiers,
+            100,
+            timestamp + EARNING_PERIOD
+        ));
+    }
+}
@@ -0,0 +1,273 @@
+use protobuf::RepeatedField;
+use sawtooth_sdk::processor::handler::ApplyError;
+
+use archer_protobuf::velocity::VelocityEntry as VelocityEntryPB;
+
+/// How far past the latest recorded entry a checked amount may be dated, so a forward
+/// timestamp cannot move the window past the entries that count toward it
+pub const MAX_VELOCITY_SKEW: i64 = 3600;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scope {
+    Account,
+    Merchant,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Deposit,
+    Withdrawal,
+}
+
+impl Direction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Deposit => "deposit",
+            Direction::Withdrawal => "withdrawal",
+        }
+    }
+}
+
+/// Limit of the `archer.velocity_limits` setting, a maximum of zero leaves that bound
+/// unchecked
+#[derive(Debug, PartialEq)]
+pub struct VelocityLimit {
+    pub scope: Scope,
+    pub direction: Direction,
+    pub max_per_transaction: u64,
+    pub max_per_window: u64,
+    pub window: i64,
+}
+
+/// Parses the comma-separated `scope.direction:max_per_transaction:max_per_window:window`
+/// entries of the setting, an empty value configures no limits
+pub fn parse_velocity_limits(value: &str) -> Result<Vec<VelocityLimit>, ApplyError> {
+    let mut limits: Vec<VelocityLimit> = Vec::new();
+    for entry in value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let invalid =
+            || ApplyError::InvalidTransaction(format!("Invalid velocity limit entry {}", entry));
+        let parts: Vec<&str> = entry.split(':').map(str::trim).collect();
+        if parts.len() != 4 {
+            return Err(invalid());
+        }
+        let (scope, direction) = match parts[0] {
+            "account.deposit" => (Scope::Account, Direction::Deposit),
+            "account.withdrawal" => (Scope::Account, Direction::Withdrawal),
+            "merchant.deposit" => (Scope::Merchant, Direction::Deposit),
+            "merchant.withdrawal" => (Scope::Merchant, Direction::Withdrawal),
+            _ => return Err(invalid()),
+        };
+        let limit = VelocityLimit {
+            scope,
+            direction,
+            max_per_transaction: parts[1].parse().map_err(|_| invalid())?,
+            max_per_window: parts[2].parse().map_err(|_| invalid())?,
+            window: parts[3].parse().map_err(|_| invalid())?,
+        };
+        if limit.window < 0 || (limit.max_per_window > 0 && limit.window == 0) {
+            return Err(invalid());
+        }
+        if limits
+            .iter()
+            .any(|other| other.scope == scope && other.direction == direction)
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Duplicate velocity limit {}",
+                parts[0]
+            )));
+        }
+        limits.push(limit);
+    }
+    Ok(limits)
+}
+
+pub fn find_limit(
+    limits: &[VelocityLimit],
+    scope: Scope,
+    direction: Direction,
+) -> Option<&VelocityLimit> {
+    limits
+        .iter()
+        .find(|limit| limit.scope == scope && limit.direction == direction)
+}
+
+/// Checks the amount against the limit and records it among the recent entries, which
+/// only keep what still counts toward the window. `subject` names the account or
+/// merchant in the rejection.
+pub fn check_velocity(
+    recent: &mut RepeatedField<VelocityEntryPB>,
+    limit: Option<&VelocityLimit>,
+    subject: &str,
+    amount: u64,
+    timestamp: i64,
+) -> Result<(), ApplyError> {
+    let limit = match limit {
+        Some(limit) => limit,
+        None => {
+            recent.clear();
+            return Ok(());
+        }
+    };
+    let direction = limit.direction.as_str();
+
+    if limit.max_per_transaction > 0 && amount > limit.max_per_transaction {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "{} {} of {} exceeds the limit of {} per transaction",
+            subject, direction, amount, limit.max_per_transaction
+        )));
+    }
+
+    if limit.max_per_window == 0 {
+        recent.clear();
+        return Ok(());
+    }
+    if timestamp == 0 {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "A timestamp is required to check the {} limit of {}",
+            direction, subject
+        )));
+    }
+
+    // Entries are recorded in order, so an older timestamp cannot slip amounts into a
+    // window that has already moved past it, nor a later one empty the window at once
+    if let Some(newest) = recent.iter().map(|entry| entry.get_timestamp()).max() {
+        if timestamp < newest {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Timestamp {} precedes the latest {} of {} at {}",
+                timestamp, direction, subject, newest
+            )));
+        }
+        if timestamp > newest.saturating_add(MAX_VELOCITY_SKEW) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Timestamp {} is too far past the latest {} of {} at {}",
+                timestamp, direction, subject, newest
+            )));
+        }
+    }
+
+    let window_start = timestamp.saturating_sub(limit.window);
+    recent.retain(|entry| entry.get_timestamp() > window_start);
+    let total = recent.iter().fold(amount, |total, entry| {
+        total.saturating_add(entry.get_amount())
+    });
+    if total > limit.max_per_window {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "{} {}s of {} within {} seconds exceed the limit of {}",
+            subject, direction, total, limit.window, limit.max_per_window
+        )));
+    }
+
+    let mut entry = VelocityEntryPB::new();
+    entry.set_timestamp(timestamp);
+    entry.set_amount(amount);
+    recent.push(entry);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_velocity_limits() {
+        let limits = parse_velocity_limits(
+            "account.withdrawal:500:1000:86400, merchant.deposit:0:5000:3600",
+        )
+        .unwrap();
+        assert_eq!(limits.len(), 2);
+        assert_eq!(
+            find_limit(&limits, Scope::Merchant, Direction::Deposit),
+            Some(&VelocityLimit {
+                scope: Scope::Merchant,
+                direction: Direction::Deposit,
+                max_per_transaction: 0,
+                max_per_window: 5000,
+                window: 3600,
+            })
+        );
+        assert_eq!(
+            find_limit(&limits, Scope::Account, Direction::Deposit),
+            None
+        );
+        assert!(parse_velocity_limits("").unwrap().is_empty());
+        assert!(parse_velocity_limits("account.transfer:500:1000:86400").is_err());
+        assert!(parse_velocity_limits("account.deposit:500:1000").is_err());
+        assert!(parse_velocity_limits("account.deposit:500:1000:0").is_err());
+        assert!(parse_velocity_limits("account.deposit:500:0:0").is_ok());
+        assert!(parse_velocity_limits("account.deposit:500:0:0,account.deposit:100:0:0").is_err());
+    }
+
+    #[test]
+    fn limits_amounts_per_transaction() {
+        let limits = parse_velocity_limits("account.withdrawal:500:0:0").unwrap();
+        let limit = find_limit(&limits, Scope::Account, Direction::Withdrawal);
+        let mut recent = RepeatedField::new();
+        check_velocity(&mut recent, limit, "Account", 500, 0).unwrap();
+        assert!(check_velocity(&mut recent, limit, "Account", 501, 0).is_err());
+        assert!(recent.is_empty());
+    }
+
+    #[test]
+    fn limits_totals_within_window() {
+        let limits = parse_velocity_limits("account.withdrawal:0:1000:100").unwrap();
+        let limit = find_limit(&limits, Scope::Account, Direction::Withdrawal);
+        let mut recent = RepeatedField::new();
+        assert!(check_velocity(&mut recent, limit, "Account", 100, 0).is_err());
+        check_velocity(&mut recent, limit, "Account", 600, 1000).unwrap();
+        check_velocity(&mut recent, limit, "Account", 400, 1050).unwrap();
+        assert!(check_velocity(&mut recent, limit, "Account", 1, 1099).is_err());
+        assert_eq!(recent.len(), 2);
+
+        // The first withdrawal leaves the window
+        check_velocity(&mut recent, limit, "Account", 600, 1100).unwrap();
+        assert_eq!(recent.len(), 2);
+
+        check_velocity(&mut recent, None, "Account", 5000, 1200).unwrap();
+        assert!(recent.is_empty());
+    }
+
+    #[test]
+    fn rejects_timestamps_older_than_recorded_entries() {
+        let limits = parse_velocity_limits("account.withdrawal:0:1000:100").unwrap();
+        let limit = find_limit(&limits, Scope::Account, Direction::Withdrawal);
+        let mut recent = RepeatedField::new();
+        check_velocity(&mut recent, limit, "Account", 600, 1000).unwrap();
+        // Backdating past the window start would otherwise drop the first entry
+        assert!(check_velocity(&mut recent, limit, "Account", 600, 899).is_err());
+        assert!(check_velocity(&mut recent, limit, "Account", 100, 999).is_err());
+        assert_eq!(recent.len(), 1);
+
+        check_velocity(&mut recent, limit, "Account", 400, 1000).unwrap();
+        assert_eq!(recent.len(), 2);
+    }
+
+    #[test]
+    fn rejects_timestamps_too_far_past_recorded_entries() {
+        let limits = parse_velocity_limits("account.withdrawal:0:1000:86400").unwrap();
+        let limit = find_limit(&limits, Scope::Account, Direction::Withdrawal);
+        let mut recent = RepeatedField::new();
+        check_velocity(&mut recent, limit, "Account", 600, 1000).unwrap();
+        // Dating past the window would otherwise drop the first entry
+        assert!(check_velocity(&mut recent, limit, "Account", 600, 1000 + 86400).is_err());
+        assert!(
+            check_velocity(&mut recent, limit, "Account", 100, 1001 + MAX_VELOCITY_SKEW).is_err()
+        );
+        assert_eq!(recent.len(), 1);
+
+        // Stepping forward keeps the earlier entries within the window
+        check_velocity(&mut recent, limit, "Account", 400, 1000 + MAX_VELOCITY_SKEW).unwrap();
+        assert!(check_velocity(
+            &mut recent,
+            limit,
+            "Account",
+            1,
+            1000 + 2 * MAX_VELOCITY_SKEW
+        )
+        .is_err());
+        assert_eq!(recent.len(), 2);
+    }
+}
@@ -0,0 +1,88 @@
+use sawtooth_sdk::processor::handler::ApplyError;
+
+use archer_protobuf::voucher::Voucher as VoucherPB;
+
+pub fn validate_voucher(voucher: &VoucherPB) -> Result<(), ApplyError> {
+    if voucher.get_discount() == 0 {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Voucher {} must carry a discount",
+            voucher.get_id()
+        )));
+    }
+    if voucher.get_expires_at() != 0 && voucher.get_expires_at() <= voucher.get_issued_at() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Voucher {} would expire before it is issued",
+            voucher.get_id()
+        )));
+    }
+    Ok(())
+}
+
+/// Vouchers can only be used once, by the account they were issued to, before they expire
+pub fn check_consumable(
+    voucher: &VoucherPB,
+    name: &str,
+    number: u32,
+    timestamp: i64,
+) -> Result<(), ApplyError> {
+    if voucher.get_name() != name || voucher.get_number() != number {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Voucher {} was not issued to {} {}",
+            voucher.get_id(),
+            name,
+            number
+        )));
+    }
+    if voucher.has_consumed_at() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Voucher {} has already been consumed",
+            voucher.get_id()
+        )));
+    }
+    if voucher.get_expires_at() != 0 && timestamp >= voucher.get_expires_at() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Voucher {} has expired",
+            voucher.get_id()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_voucher(expires_at: i64) -> VoucherPB {
+        let mut voucher = VoucherPB::new();
+        voucher.set_id(String::from("spring-10"));
+        voucher.set_name(String::from("John Doe"));
+        voucher.set_number(12345);
+        voucher.set_discount(1000);
+        voucher.set_issued_at(100);
+        voucher.set_expires_at(expires_at);
+        voucher
+    }
+
+    #[test]
+    fn validates_vouchers() {
+        assert!(validate_voucher(&make_voucher(0)).is_ok());
+        assert!(validate_voucher(&make_voucher(200)).is_ok());
+        assert!(validate_voucher(&make_voucher(100)).is_err());
+
+        let mut voucher = make_voucher(0);
+        voucher.set_discount(0);
+        assert!(validate_voucher(&voucher).is_err());
+    }
+
+    #[test]
+    fn checks_consumable_vouchers() {
+        let mut voucher = make_voucher(200);
+        assert!(check_consumable(&voucher, "John Doe", 12345, 150).is_ok());
+        assert!(check_consumable(&voucher, "John Doe", 54321, 150).is_err());
+        assert!(check_consumable(&voucher, "Jane Doe", 12345, 150).is_err());
+        assert!(check_consumable(&voucher, "John Doe", 12345, 200).is_err());
+
+        voucher.set_consumed_at(150);
+        assert!(check_consumable(&voucher, "John Doe", 12345, 160).is_err());
+    }
+}
@@ -5,8 +5,14 @@ fn main() {
         .out_dir("src")
         .inputs(&[
             "../protos/account.proto",
+            "../protos/catalog.proto",
+            "../protos/earn_rule.proto",
             "../protos/merchant.proto",
             "../protos/payload.proto",
+            "../protos/receipt.proto",
+            "../protos/settlement.proto",
+            "../protos/velocity.proto",
+            "../protos/voucher.proto",
         ])
         .include("../protos")
         .run()
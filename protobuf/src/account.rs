@@ -1,4 +1,4 @@
-// This file is generated by rust-protobuf 2.18.1. Do not edit
+// This file is generated by rust-protobuf 2.28.0. Do not edit
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -6,7 +6,7 @@
 #![allow(clippy::all)]
 
 #![allow(unused_attributes)]
-#![rustfmt::skip]
+#![cfg_attr(rustfmt, rustfmt::skip)]
 
 #![allow(box_pointers)]
 #![allow(dead_code)]
@@ -21,89 +21,1300 @@
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_18_1;
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Lot {
+    // message fields
+    id: ::std::option::Option<u64>,
+    amount: ::std::option::Option<u64>,
+    earned_at: ::std::option::Option<i64>,
+    expires_at: ::std::option::Option<i64>,
+    issuer: ::protobuf::SingularField<::std::string::String>,
+    currency: ::protobuf::SingularField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Lot {
+    fn default() -> &'a Lot {
+        <Lot as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Lot {
+    pub fn new() -> Lot {
+        ::std::default::Default::default()
+    }
+
+    // required uint64 id = 1;
+
+
+    pub fn get_id(&self) -> u64 {
+        self.id.unwrap_or(0)
+    }
+    pub fn clear_id(&mut self) {
+        self.id = ::std::option::Option::None;
+    }
+
+    pub fn has_id(&self) -> bool {
+        self.id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_id(&mut self, v: u64) {
+        self.id = ::std::option::Option::Some(v);
+    }
+
+    // required uint64 amount = 2;
+
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount.unwrap_or(0)
+    }
+    pub fn clear_amount(&mut self) {
+        self.amount = ::std::option::Option::None;
+    }
+
+    pub fn has_amount(&self) -> bool {
+        self.amount.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_amount(&mut self, v: u64) {
+        self.amount = ::std::option::Option::Some(v);
+    }
+
+    // required sint64 earned_at = 3;
+
+
+    pub fn get_earned_at(&self) -> i64 {
+        self.earned_at.unwrap_or(0)
+    }
+    pub fn clear_earned_at(&mut self) {
+        self.earned_at = ::std::option::Option::None;
+    }
+
+    pub fn has_earned_at(&self) -> bool {
+        self.earned_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_earned_at(&mut self, v: i64) {
+        self.earned_at = ::std::option::Option::Some(v);
+    }
+
+    // required sint64 expires_at = 4;
+
+
+    pub fn get_expires_at(&self) -> i64 {
+        self.expires_at.unwrap_or(0)
+    }
+    pub fn clear_expires_at(&mut self) {
+        self.expires_at = ::std::option::Option::None;
+    }
+
+    pub fn has_expires_at(&self) -> bool {
+        self.expires_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expires_at(&mut self, v: i64) {
+        self.expires_at = ::std::option::Option::Some(v);
+    }
+
+    // optional string issuer = 5;
+
+
+    pub fn get_issuer(&self) -> &str {
+        match self.issuer.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_issuer(&mut self) {
+        self.issuer.clear();
+    }
+
+    pub fn has_issuer(&self) -> bool {
+        self.issuer.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_issuer(&mut self, v: ::std::string::String) {
+        self.issuer = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_issuer(&mut self) -> &mut ::std::string::String {
+        if self.issuer.is_none() {
+            self.issuer.set_default();
+        }
+        self.issuer.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_issuer(&mut self) -> ::std::string::String {
+        self.issuer.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional string currency = 6;
+
+
+    pub fn get_currency(&self) -> &str {
+        match self.currency.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_currency(&mut self) {
+        self.currency.clear();
+    }
+
+    pub fn has_currency(&self) -> bool {
+        self.currency.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_currency(&mut self, v: ::std::string::String) {
+        self.currency = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_currency(&mut self) -> &mut ::std::string::String {
+        if self.currency.is_none() {
+            self.currency.set_default();
+        }
+        self.currency.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_currency(&mut self) -> ::std::string::String {
+        self.currency.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for Lot {
+    fn is_initialized(&self) -> bool {
+        if self.id.is_none() {
+            return false;
+        }
+        if self.amount.is_none() {
+            return false;
+        }
+        if self.earned_at.is_none() {
+            return false;
+        }
+        if self.expires_at.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.id = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.amount = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.earned_at = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.expires_at = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.issuer)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.currency)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.id {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.amount {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.earned_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(3, v);
+        }
+        if let Some(v) = self.expires_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(4, v);
+        }
+        if let Some(ref v) = self.issuer.as_ref() {
+            my_size += ::protobuf::rt::string_size(5, &v);
+        }
+        if let Some(ref v) = self.currency.as_ref() {
+            my_size += ::protobuf::rt::string_size(6, &v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.id {
+            os.write_uint64(1, v)?;
+        }
+        if let Some(v) = self.amount {
+            os.write_uint64(2, v)?;
+        }
+        if let Some(v) = self.earned_at {
+            os.write_sint64(3, v)?;
+        }
+        if let Some(v) = self.expires_at {
+            os.write_sint64(4, v)?;
+        }
+        if let Some(ref v) = self.issuer.as_ref() {
+            os.write_string(5, &v)?;
+        }
+        if let Some(ref v) = self.currency.as_ref() {
+            os.write_string(6, &v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Lot {
+        Lot::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "id",
+                |m: &Lot| { &m.id },
+                |m: &mut Lot| { &mut m.id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "amount",
+                |m: &Lot| { &m.amount },
+                |m: &mut Lot| { &mut m.amount },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "earned_at",
+                |m: &Lot| { &m.earned_at },
+                |m: &mut Lot| { &mut m.earned_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "expires_at",
+                |m: &Lot| { &m.expires_at },
+                |m: &mut Lot| { &mut m.expires_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "issuer",
+                |m: &Lot| { &m.issuer },
+                |m: &mut Lot| { &mut m.issuer },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "currency",
+                |m: &Lot| { &m.currency },
+                |m: &mut Lot| { &mut m.currency },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Lot>(
+                "Lot",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Lot {
+        static instance: ::protobuf::rt::LazyV2<Lot> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Lot::new)
+    }
+}
+
+impl ::protobuf::Clear for Lot {
+    fn clear(&mut self) {
+        self.id = ::std::option::Option::None;
+        self.amount = ::std::option::Option::None;
+        self.earned_at = ::std::option::Option::None;
+        self.expires_at = ::std::option::Option::None;
+        self.issuer.clear();
+        self.currency.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Lot {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Lot {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct CurrencyBalance {
+    // message fields
+    currency: ::protobuf::SingularField<::std::string::String>,
+    balance: ::std::option::Option<u64>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a CurrencyBalance {
+    fn default() -> &'a CurrencyBalance {
+        <CurrencyBalance as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl CurrencyBalance {
+    pub fn new() -> CurrencyBalance {
+        ::std::default::Default::default()
+    }
+
+    // required string currency = 1;
+
+
+    pub fn get_currency(&self) -> &str {
+        match self.currency.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_currency(&mut self) {
+        self.currency.clear();
+    }
+
+    pub fn has_currency(&self) -> bool {
+        self.currency.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_currency(&mut self, v: ::std::string::String) {
+        self.currency = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_currency(&mut self) -> &mut ::std::string::String {
+        if self.currency.is_none() {
+            self.currency.set_default();
+        }
+        self.currency.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_currency(&mut self) -> ::std::string::String {
+        self.currency.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required uint64 balance = 2;
+
+
+    pub fn get_balance(&self) -> u64 {
+        self.balance.unwrap_or(0)
+    }
+    pub fn clear_balance(&mut self) {
+        self.balance = ::std::option::Option::None;
+    }
+
+    pub fn has_balance(&self) -> bool {
+        self.balance.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_balance(&mut self, v: u64) {
+        self.balance = ::std::option::Option::Some(v);
+    }
+}
+
+impl ::protobuf::Message for CurrencyBalance {
+    fn is_initialized(&self) -> bool {
+        if self.currency.is_none() {
+            return false;
+        }
+        if self.balance.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.currency)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.balance = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.currency.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(v) = self.balance {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.currency.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(v) = self.balance {
+            os.write_uint64(2, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> CurrencyBalance {
+        CurrencyBalance::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "currency",
+                |m: &CurrencyBalance| { &m.currency },
+                |m: &mut CurrencyBalance| { &mut m.currency },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "balance",
+                |m: &CurrencyBalance| { &m.balance },
+                |m: &mut CurrencyBalance| { &mut m.balance },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<CurrencyBalance>(
+                "CurrencyBalance",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static CurrencyBalance {
+        static instance: ::protobuf::rt::LazyV2<CurrencyBalance> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(CurrencyBalance::new)
+    }
+}
+
+impl ::protobuf::Clear for CurrencyBalance {
+    fn clear(&mut self) {
+        self.currency.clear();
+        self.balance = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for CurrencyBalance {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for CurrencyBalance {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct EarningPeriod {
+    // message fields
+    period_start: ::std::option::Option<i64>,
+    amount: ::std::option::Option<u64>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a EarningPeriod {
+    fn default() -> &'a EarningPeriod {
+        <EarningPeriod as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl EarningPeriod {
+    pub fn new() -> EarningPeriod {
+        ::std::default::Default::default()
+    }
+
+    // required sint64 period_start = 1;
+
+
+    pub fn get_period_start(&self) -> i64 {
+        self.period_start.unwrap_or(0)
+    }
+    pub fn clear_period_start(&mut self) {
+        self.period_start = ::std::option::Option::None;
+    }
+
+    pub fn has_period_start(&self) -> bool {
+        self.period_start.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_period_start(&mut self, v: i64) {
+        self.period_start = ::std::option::Option::Some(v);
+    }
+
+    // required uint64 amount = 2;
+
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount.unwrap_or(0)
+    }
+    pub fn clear_amount(&mut self) {
+        self.amount = ::std::option::Option::None;
+    }
+
+    pub fn has_amount(&self) -> bool {
+        self.amount.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_amount(&mut self, v: u64) {
+        self.amount = ::std::option::Option::Some(v);
+    }
+}
+
+impl ::protobuf::Message for EarningPeriod {
+    fn is_initialized(&self) -> bool {
+        if self.period_start.is_none() {
+            return false;
+        }
+        if self.amount.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.period_start = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.amount = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.period_start {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(1, v);
+        }
+        if let Some(v) = self.amount {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.period_start {
+            os.write_sint64(1, v)?;
+        }
+        if let Some(v) = self.amount {
+            os.write_uint64(2, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> EarningPeriod {
+        EarningPeriod::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "period_start",
+                |m: &EarningPeriod| { &m.period_start },
+                |m: &mut EarningPeriod| { &mut m.period_start },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "amount",
+                |m: &EarningPeriod| { &m.amount },
+                |m: &mut EarningPeriod| { &mut m.amount },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<EarningPeriod>(
+                "EarningPeriod",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static EarningPeriod {
+        static instance: ::protobuf::rt::LazyV2<EarningPeriod> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(EarningPeriod::new)
+    }
+}
+
+impl ::protobuf::Clear for EarningPeriod {
+    fn clear(&mut self) {
+        self.period_start = ::std::option::Option::None;
+        self.amount = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for EarningPeriod {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EarningPeriod {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
 
 #[derive(PartialEq,Clone,Default)]
 pub struct Account {
     // message fields
     name: ::protobuf::SingularField<::std::string::String>,
     number: ::std::option::Option<u32>,
-    balance: ::std::option::Option<i32>,
+    legacy_balance: ::std::option::Option<i32>,
+    pub lots: ::protobuf::RepeatedField<Lot>,
+    next_lot_id: ::std::option::Option<u64>,
+    owner_public_key: ::protobuf::SingularField<::std::string::String>,
+    last_authorized_at: ::std::option::Option<i64>,
+    pub previous_numbers: ::std::vec::Vec<u32>,
+    status: ::std::option::Option<Account_Status>,
+    closure_reason: ::protobuf::SingularField<::std::string::String>,
+    pub currency_balances: ::protobuf::RepeatedField<CurrencyBalance>,
+    lifetime_earned: ::std::option::Option<u64>,
+    pub earning_periods: ::protobuf::RepeatedField<EarningPeriod>,
+    tier: ::protobuf::SingularField<::std::string::String>,
+    tier_changed_at: ::std::option::Option<i64>,
+    pub recent_deposits: ::protobuf::RepeatedField<super::velocity::VelocityEntry>,
+    pub recent_withdrawals: ::protobuf::RepeatedField<super::velocity::VelocityEntry>,
+    last_activity_at: ::std::option::Option<i64>,
+    balance: ::std::option::Option<u64>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a Account {
-    fn default() -> &'a Account {
-        <Account as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a Account {
+    fn default() -> &'a Account {
+        <Account as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Account {
+    pub fn new() -> Account {
+        ::std::default::Default::default()
+    }
+
+    // required string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        match self.name.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    pub fn has_name(&self) -> bool {
+        self.name.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        if self.name.is_none() {
+            self.name.set_default();
+        }
+        self.name.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        self.name.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required uint32 number = 2;
+
+
+    pub fn get_number(&self) -> u32 {
+        self.number.unwrap_or(0)
+    }
+    pub fn clear_number(&mut self) {
+        self.number = ::std::option::Option::None;
+    }
+
+    pub fn has_number(&self) -> bool {
+        self.number.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_number(&mut self, v: u32) {
+        self.number = ::std::option::Option::Some(v);
+    }
+
+    // optional sint32 legacy_balance = 3;
+
+
+    pub fn get_legacy_balance(&self) -> i32 {
+        self.legacy_balance.unwrap_or(0)
+    }
+    pub fn clear_legacy_balance(&mut self) {
+        self.legacy_balance = ::std::option::Option::None;
+    }
+
+    pub fn has_legacy_balance(&self) -> bool {
+        self.legacy_balance.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_legacy_balance(&mut self, v: i32) {
+        self.legacy_balance = ::std::option::Option::Some(v);
+    }
+
+    // repeated .Lot lots = 4;
+
+
+    pub fn get_lots(&self) -> &[Lot] {
+        &self.lots
+    }
+    pub fn clear_lots(&mut self) {
+        self.lots.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_lots(&mut self, v: ::protobuf::RepeatedField<Lot>) {
+        self.lots = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_lots(&mut self) -> &mut ::protobuf::RepeatedField<Lot> {
+        &mut self.lots
+    }
+
+    // Take field
+    pub fn take_lots(&mut self) -> ::protobuf::RepeatedField<Lot> {
+        ::std::mem::replace(&mut self.lots, ::protobuf::RepeatedField::new())
+    }
+
+    // optional uint64 next_lot_id = 5;
+
+
+    pub fn get_next_lot_id(&self) -> u64 {
+        self.next_lot_id.unwrap_or(0)
+    }
+    pub fn clear_next_lot_id(&mut self) {
+        self.next_lot_id = ::std::option::Option::None;
+    }
+
+    pub fn has_next_lot_id(&self) -> bool {
+        self.next_lot_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_next_lot_id(&mut self, v: u64) {
+        self.next_lot_id = ::std::option::Option::Some(v);
+    }
+
+    // optional string owner_public_key = 6;
+
+
+    pub fn get_owner_public_key(&self) -> &str {
+        match self.owner_public_key.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_owner_public_key(&mut self) {
+        self.owner_public_key.clear();
+    }
+
+    pub fn has_owner_public_key(&self) -> bool {
+        self.owner_public_key.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_owner_public_key(&mut self, v: ::std::string::String) {
+        self.owner_public_key = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_owner_public_key(&mut self) -> &mut ::std::string::String {
+        if self.owner_public_key.is_none() {
+            self.owner_public_key.set_default();
+        }
+        self.owner_public_key.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_owner_public_key(&mut self) -> ::std::string::String {
+        self.owner_public_key.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional sint64 last_authorized_at = 7;
+
+
+    pub fn get_last_authorized_at(&self) -> i64 {
+        self.last_authorized_at.unwrap_or(0)
+    }
+    pub fn clear_last_authorized_at(&mut self) {
+        self.last_authorized_at = ::std::option::Option::None;
+    }
+
+    pub fn has_last_authorized_at(&self) -> bool {
+        self.last_authorized_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_last_authorized_at(&mut self, v: i64) {
+        self.last_authorized_at = ::std::option::Option::Some(v);
+    }
+
+    // repeated uint32 previous_numbers = 8;
+
+
+    pub fn get_previous_numbers(&self) -> &[u32] {
+        &self.previous_numbers
+    }
+    pub fn clear_previous_numbers(&mut self) {
+        self.previous_numbers.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_previous_numbers(&mut self, v: ::std::vec::Vec<u32>) {
+        self.previous_numbers = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_previous_numbers(&mut self) -> &mut ::std::vec::Vec<u32> {
+        &mut self.previous_numbers
+    }
+
+    // Take field
+    pub fn take_previous_numbers(&mut self) -> ::std::vec::Vec<u32> {
+        ::std::mem::replace(&mut self.previous_numbers, ::std::vec::Vec::new())
+    }
+
+    // optional .Account.Status status = 9;
+
+
+    pub fn get_status(&self) -> Account_Status {
+        self.status.unwrap_or(Account_Status::ACTIVE)
+    }
+    pub fn clear_status(&mut self) {
+        self.status = ::std::option::Option::None;
+    }
+
+    pub fn has_status(&self) -> bool {
+        self.status.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status(&mut self, v: Account_Status) {
+        self.status = ::std::option::Option::Some(v);
+    }
+
+    // optional string closure_reason = 10;
+
+
+    pub fn get_closure_reason(&self) -> &str {
+        match self.closure_reason.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_closure_reason(&mut self) {
+        self.closure_reason.clear();
+    }
+
+    pub fn has_closure_reason(&self) -> bool {
+        self.closure_reason.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_closure_reason(&mut self, v: ::std::string::String) {
+        self.closure_reason = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_closure_reason(&mut self) -> &mut ::std::string::String {
+        if self.closure_reason.is_none() {
+            self.closure_reason.set_default();
+        }
+        self.closure_reason.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_closure_reason(&mut self) -> ::std::string::String {
+        self.closure_reason.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // repeated .CurrencyBalance currency_balances = 11;
+
+
+    pub fn get_currency_balances(&self) -> &[CurrencyBalance] {
+        &self.currency_balances
+    }
+    pub fn clear_currency_balances(&mut self) {
+        self.currency_balances.clear();
     }
-}
 
-impl Account {
-    pub fn new() -> Account {
-        ::std::default::Default::default()
+    // Param is passed by value, moved
+    pub fn set_currency_balances(&mut self, v: ::protobuf::RepeatedField<CurrencyBalance>) {
+        self.currency_balances = v;
     }
 
-    // required string name = 1;
+    // Mutable pointer to the field.
+    pub fn mut_currency_balances(&mut self) -> &mut ::protobuf::RepeatedField<CurrencyBalance> {
+        &mut self.currency_balances
+    }
+
+    // Take field
+    pub fn take_currency_balances(&mut self) -> ::protobuf::RepeatedField<CurrencyBalance> {
+        ::std::mem::replace(&mut self.currency_balances, ::protobuf::RepeatedField::new())
+    }
 
+    // optional uint64 lifetime_earned = 12;
 
-    pub fn get_name(&self) -> &str {
-        match self.name.as_ref() {
+
+    pub fn get_lifetime_earned(&self) -> u64 {
+        self.lifetime_earned.unwrap_or(0)
+    }
+    pub fn clear_lifetime_earned(&mut self) {
+        self.lifetime_earned = ::std::option::Option::None;
+    }
+
+    pub fn has_lifetime_earned(&self) -> bool {
+        self.lifetime_earned.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_lifetime_earned(&mut self, v: u64) {
+        self.lifetime_earned = ::std::option::Option::Some(v);
+    }
+
+    // repeated .EarningPeriod earning_periods = 13;
+
+
+    pub fn get_earning_periods(&self) -> &[EarningPeriod] {
+        &self.earning_periods
+    }
+    pub fn clear_earning_periods(&mut self) {
+        self.earning_periods.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_earning_periods(&mut self, v: ::protobuf::RepeatedField<EarningPeriod>) {
+        self.earning_periods = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_earning_periods(&mut self) -> &mut ::protobuf::RepeatedField<EarningPeriod> {
+        &mut self.earning_periods
+    }
+
+    // Take field
+    pub fn take_earning_periods(&mut self) -> ::protobuf::RepeatedField<EarningPeriod> {
+        ::std::mem::replace(&mut self.earning_periods, ::protobuf::RepeatedField::new())
+    }
+
+    // optional string tier = 14;
+
+
+    pub fn get_tier(&self) -> &str {
+        match self.tier.as_ref() {
             Some(v) => &v,
             None => "",
         }
     }
-    pub fn clear_name(&mut self) {
-        self.name.clear();
+    pub fn clear_tier(&mut self) {
+        self.tier.clear();
     }
 
-    pub fn has_name(&self) -> bool {
-        self.name.is_some()
+    pub fn has_tier(&self) -> bool {
+        self.tier.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_name(&mut self, v: ::std::string::String) {
-        self.name = ::protobuf::SingularField::some(v);
+    pub fn set_tier(&mut self, v: ::std::string::String) {
+        self.tier = ::protobuf::SingularField::some(v);
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_name(&mut self) -> &mut ::std::string::String {
-        if self.name.is_none() {
-            self.name.set_default();
+    pub fn mut_tier(&mut self) -> &mut ::std::string::String {
+        if self.tier.is_none() {
+            self.tier.set_default();
         }
-        self.name.as_mut().unwrap()
+        self.tier.as_mut().unwrap()
     }
 
     // Take field
-    pub fn take_name(&mut self) -> ::std::string::String {
-        self.name.take().unwrap_or_else(|| ::std::string::String::new())
+    pub fn take_tier(&mut self) -> ::std::string::String {
+        self.tier.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
-    // required uint32 number = 2;
+    // optional sint64 tier_changed_at = 15;
 
 
-    pub fn get_number(&self) -> u32 {
-        self.number.unwrap_or(0)
+    pub fn get_tier_changed_at(&self) -> i64 {
+        self.tier_changed_at.unwrap_or(0)
     }
-    pub fn clear_number(&mut self) {
-        self.number = ::std::option::Option::None;
+    pub fn clear_tier_changed_at(&mut self) {
+        self.tier_changed_at = ::std::option::Option::None;
     }
 
-    pub fn has_number(&self) -> bool {
-        self.number.is_some()
+    pub fn has_tier_changed_at(&self) -> bool {
+        self.tier_changed_at.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_number(&mut self, v: u32) {
-        self.number = ::std::option::Option::Some(v);
+    pub fn set_tier_changed_at(&mut self, v: i64) {
+        self.tier_changed_at = ::std::option::Option::Some(v);
+    }
+
+    // repeated .VelocityEntry recent_deposits = 16;
+
+
+    pub fn get_recent_deposits(&self) -> &[super::velocity::VelocityEntry] {
+        &self.recent_deposits
+    }
+    pub fn clear_recent_deposits(&mut self) {
+        self.recent_deposits.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_recent_deposits(&mut self, v: ::protobuf::RepeatedField<super::velocity::VelocityEntry>) {
+        self.recent_deposits = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_recent_deposits(&mut self) -> &mut ::protobuf::RepeatedField<super::velocity::VelocityEntry> {
+        &mut self.recent_deposits
+    }
+
+    // Take field
+    pub fn take_recent_deposits(&mut self) -> ::protobuf::RepeatedField<super::velocity::VelocityEntry> {
+        ::std::mem::replace(&mut self.recent_deposits, ::protobuf::RepeatedField::new())
+    }
+
+    // repeated .VelocityEntry recent_withdrawals = 17;
+
+
+    pub fn get_recent_withdrawals(&self) -> &[super::velocity::VelocityEntry] {
+        &self.recent_withdrawals
+    }
+    pub fn clear_recent_withdrawals(&mut self) {
+        self.recent_withdrawals.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_recent_withdrawals(&mut self, v: ::protobuf::RepeatedField<super::velocity::VelocityEntry>) {
+        self.recent_withdrawals = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_recent_withdrawals(&mut self) -> &mut ::protobuf::RepeatedField<super::velocity::VelocityEntry> {
+        &mut self.recent_withdrawals
+    }
+
+    // Take field
+    pub fn take_recent_withdrawals(&mut self) -> ::protobuf::RepeatedField<super::velocity::VelocityEntry> {
+        ::std::mem::replace(&mut self.recent_withdrawals, ::protobuf::RepeatedField::new())
+    }
+
+    // optional sint64 last_activity_at = 18;
+
+
+    pub fn get_last_activity_at(&self) -> i64 {
+        self.last_activity_at.unwrap_or(0)
+    }
+    pub fn clear_last_activity_at(&mut self) {
+        self.last_activity_at = ::std::option::Option::None;
+    }
+
+    pub fn has_last_activity_at(&self) -> bool {
+        self.last_activity_at.is_some()
     }
 
-    // required sint32 balance = 3;
+    // Param is passed by value, moved
+    pub fn set_last_activity_at(&mut self, v: i64) {
+        self.last_activity_at = ::std::option::Option::Some(v);
+    }
 
+    // optional uint64 balance = 19;
 
-    pub fn get_balance(&self) -> i32 {
+
+    pub fn get_balance(&self) -> u64 {
         self.balance.unwrap_or(0)
     }
     pub fn clear_balance(&mut self) {
@@ -115,7 +1326,7 @@ impl Account {
     }
 
     // Param is passed by value, moved
-    pub fn set_balance(&mut self, v: i32) {
+    pub fn set_balance(&mut self, v: u64) {
         self.balance = ::std::option::Option::Some(v);
     }
 }
@@ -128,9 +1339,31 @@ impl ::protobuf::Message for Account {
         if self.number.is_none() {
             return false;
         }
-        if self.balance.is_none() {
-            return false;
-        }
+        for v in &self.lots {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.currency_balances {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.earning_periods {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.recent_deposits {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.recent_withdrawals {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -153,6 +1386,78 @@ impl ::protobuf::Message for Account {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_sint32()?;
+                    self.legacy_balance = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.lots)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.next_lot_id = ::std::option::Option::Some(tmp);
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.owner_public_key)?;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.last_authorized_at = ::std::option::Option::Some(tmp);
+                },
+                8 => {
+                    ::protobuf::rt::read_repeated_uint32_into(wire_type, is, &mut self.previous_numbers)?;
+                },
+                9 => {
+                    ::protobuf::rt::read_proto2_enum_with_unknown_fields_into(wire_type, is, &mut self.status, 9, &mut self.unknown_fields)?
+                },
+                10 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.closure_reason)?;
+                },
+                11 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.currency_balances)?;
+                },
+                12 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.lifetime_earned = ::std::option::Option::Some(tmp);
+                },
+                13 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.earning_periods)?;
+                },
+                14 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.tier)?;
+                },
+                15 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.tier_changed_at = ::std::option::Option::Some(tmp);
+                },
+                16 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.recent_deposits)?;
+                },
+                17 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.recent_withdrawals)?;
+                },
+                18 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.last_activity_at = ::std::option::Option::Some(tmp);
+                },
+                19 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
                     self.balance = ::std::option::Option::Some(tmp);
                 },
                 _ => {
@@ -173,9 +1478,62 @@ impl ::protobuf::Message for Account {
         if let Some(v) = self.number {
             my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
         }
-        if let Some(v) = self.balance {
+        if let Some(v) = self.legacy_balance {
             my_size += ::protobuf::rt::value_varint_zigzag_size(3, v);
         }
+        for value in &self.lots {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(v) = self.next_lot_id {
+            my_size += ::protobuf::rt::value_size(5, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.owner_public_key.as_ref() {
+            my_size += ::protobuf::rt::string_size(6, &v);
+        }
+        if let Some(v) = self.last_authorized_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(7, v);
+        }
+        for value in &self.previous_numbers {
+            my_size += ::protobuf::rt::value_size(8, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.status {
+            my_size += ::protobuf::rt::enum_size(9, v);
+        }
+        if let Some(ref v) = self.closure_reason.as_ref() {
+            my_size += ::protobuf::rt::string_size(10, &v);
+        }
+        for value in &self.currency_balances {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(v) = self.lifetime_earned {
+            my_size += ::protobuf::rt::value_size(12, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.earning_periods {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(ref v) = self.tier.as_ref() {
+            my_size += ::protobuf::rt::string_size(14, &v);
+        }
+        if let Some(v) = self.tier_changed_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(15, v);
+        }
+        for value in &self.recent_deposits {
+            let len = value.compute_size();
+            my_size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.recent_withdrawals {
+            let len = value.compute_size();
+            my_size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(v) = self.last_activity_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(18, v);
+        }
+        if let Some(v) = self.balance {
+            my_size += ::protobuf::rt::value_size(19, v, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -188,9 +1546,67 @@ impl ::protobuf::Message for Account {
         if let Some(v) = self.number {
             os.write_uint32(2, v)?;
         }
-        if let Some(v) = self.balance {
+        if let Some(v) = self.legacy_balance {
             os.write_sint32(3, v)?;
         }
+        for v in &self.lots {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(v) = self.next_lot_id {
+            os.write_uint64(5, v)?;
+        }
+        if let Some(ref v) = self.owner_public_key.as_ref() {
+            os.write_string(6, &v)?;
+        }
+        if let Some(v) = self.last_authorized_at {
+            os.write_sint64(7, v)?;
+        }
+        for v in &self.previous_numbers {
+            os.write_uint32(8, *v)?;
+        };
+        if let Some(v) = self.status {
+            os.write_enum(9, ::protobuf::ProtobufEnum::value(&v))?;
+        }
+        if let Some(ref v) = self.closure_reason.as_ref() {
+            os.write_string(10, &v)?;
+        }
+        for v in &self.currency_balances {
+            os.write_tag(11, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(v) = self.lifetime_earned {
+            os.write_uint64(12, v)?;
+        }
+        for v in &self.earning_periods {
+            os.write_tag(13, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(ref v) = self.tier.as_ref() {
+            os.write_string(14, &v)?;
+        }
+        if let Some(v) = self.tier_changed_at {
+            os.write_sint64(15, v)?;
+        }
+        for v in &self.recent_deposits {
+            os.write_tag(16, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.recent_withdrawals {
+            os.write_tag(17, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(v) = self.last_activity_at {
+            os.write_sint64(18, v)?;
+        }
+        if let Some(v) = self.balance {
+            os.write_uint64(19, v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -240,6 +1656,86 @@ impl ::protobuf::Message for Account {
                 |m: &mut Account| { &mut m.number },
             ));
             fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint32>(
+                "legacy_balance",
+                |m: &Account| { &m.legacy_balance },
+                |m: &mut Account| { &mut m.legacy_balance },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Lot>>(
+                "lots",
+                |m: &Account| { &m.lots },
+                |m: &mut Account| { &mut m.lots },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "next_lot_id",
+                |m: &Account| { &m.next_lot_id },
+                |m: &mut Account| { &mut m.next_lot_id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "owner_public_key",
+                |m: &Account| { &m.owner_public_key },
+                |m: &mut Account| { &mut m.owner_public_key },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "last_authorized_at",
+                |m: &Account| { &m.last_authorized_at },
+                |m: &mut Account| { &mut m.last_authorized_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "previous_numbers",
+                |m: &Account| { &m.previous_numbers },
+                |m: &mut Account| { &mut m.previous_numbers },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Account_Status>>(
+                "status",
+                |m: &Account| { &m.status },
+                |m: &mut Account| { &mut m.status },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "closure_reason",
+                |m: &Account| { &m.closure_reason },
+                |m: &mut Account| { &mut m.closure_reason },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<CurrencyBalance>>(
+                "currency_balances",
+                |m: &Account| { &m.currency_balances },
+                |m: &mut Account| { &mut m.currency_balances },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "lifetime_earned",
+                |m: &Account| { &m.lifetime_earned },
+                |m: &mut Account| { &mut m.lifetime_earned },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<EarningPeriod>>(
+                "earning_periods",
+                |m: &Account| { &m.earning_periods },
+                |m: &mut Account| { &mut m.earning_periods },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "tier",
+                |m: &Account| { &m.tier },
+                |m: &mut Account| { &mut m.tier },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "tier_changed_at",
+                |m: &Account| { &m.tier_changed_at },
+                |m: &mut Account| { &mut m.tier_changed_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<super::velocity::VelocityEntry>>(
+                "recent_deposits",
+                |m: &Account| { &m.recent_deposits },
+                |m: &mut Account| { &mut m.recent_deposits },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<super::velocity::VelocityEntry>>(
+                "recent_withdrawals",
+                |m: &Account| { &m.recent_withdrawals },
+                |m: &mut Account| { &mut m.recent_withdrawals },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "last_activity_at",
+                |m: &Account| { &m.last_activity_at },
+                |m: &mut Account| { &mut m.last_activity_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
                 "balance",
                 |m: &Account| { &m.balance },
                 |m: &mut Account| { &mut m.balance },
@@ -262,6 +1758,22 @@ impl ::protobuf::Clear for Account {
     fn clear(&mut self) {
         self.name.clear();
         self.number = ::std::option::Option::None;
+        self.legacy_balance = ::std::option::Option::None;
+        self.lots.clear();
+        self.next_lot_id = ::std::option::Option::None;
+        self.owner_public_key.clear();
+        self.last_authorized_at = ::std::option::Option::None;
+        self.previous_numbers.clear();
+        self.status = ::std::option::Option::None;
+        self.closure_reason.clear();
+        self.currency_balances.clear();
+        self.lifetime_earned = ::std::option::Option::None;
+        self.earning_periods.clear();
+        self.tier.clear();
+        self.tier_changed_at = ::std::option::Option::None;
+        self.recent_deposits.clear();
+        self.recent_withdrawals.clear();
+        self.last_activity_at = ::std::option::Option::None;
         self.balance = ::std::option::Option::None;
         self.unknown_fields.clear();
     }
@@ -279,6 +1791,59 @@ impl ::protobuf::reflect::ProtobufValue for Account {
     }
 }
 
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Account_Status {
+    ACTIVE = 0,
+    FROZEN = 1,
+    CLOSED = 2,
+}
+
+impl ::protobuf::ProtobufEnum for Account_Status {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Account_Status> {
+        match value {
+            0 => ::std::option::Option::Some(Account_Status::ACTIVE),
+            1 => ::std::option::Option::Some(Account_Status::FROZEN),
+            2 => ::std::option::Option::Some(Account_Status::CLOSED),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [Account_Status] = &[
+            Account_Status::ACTIVE,
+            Account_Status::FROZEN,
+            Account_Status::CLOSED,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<Account_Status>("Account.Status", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for Account_Status {
+}
+
+impl ::std::default::Default for Account_Status {
+    fn default() -> Self {
+        Account_Status::ACTIVE
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Account_Status {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 pub struct AccountContainer {
     // message fields
@@ -446,17 +2011,44 @@ impl ::protobuf::reflect::ProtobufValue for AccountContainer {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\raccount.proto\"W\n\x07Account\x12\x14\n\x04name\x18\x01\x20\x02(\tR\
-    \x04nameB\0\x12\x18\n\x06number\x18\x02\x20\x02(\rR\x06numberB\0\x12\x1a\
-    \n\x07balance\x18\x03\x20\x02(\x11R\x07balanceB\0:\0\":\n\x10AccountCont\
-    ainer\x12$\n\x07entries\x18\x01\x20\x03(\x0b2\x08.AccountR\x07entriesB\0\
-    :\0B\0b\x06proto2\
+    \n\raccount.proto\x1a\x0evelocity.proto\"\xab\x01\n\x03Lot\x12\x10\n\x02\
+    id\x18\x01\x20\x02(\x04R\x02idB\0\x12\x18\n\x06amount\x18\x02\x20\x02(\
+    \x04R\x06amountB\0\x12\x1d\n\tearned_at\x18\x03\x20\x02(\x12R\x08earnedA\
+    tB\0\x12\x1f\n\nexpires_at\x18\x04\x20\x02(\x12R\texpiresAtB\0\x12\x18\n\
+    \x06issuer\x18\x05\x20\x01(\tR\x06issuerB\0\x12\x1c\n\x08currency\x18\
+    \x06\x20\x01(\tR\x08currencyB\0:\0\"M\n\x0fCurrencyBalance\x12\x1c\n\x08\
+    currency\x18\x01\x20\x02(\tR\x08currencyB\0\x12\x1a\n\x07balance\x18\x02\
+    \x20\x02(\x04R\x07balanceB\0:\0\"P\n\rEarningPeriod\x12#\n\x0cperiod_sta\
+    rt\x18\x01\x20\x02(\x12R\x0bperiodStartB\0\x12\x18\n\x06amount\x18\x02\
+    \x20\x02(\x04R\x06amountB\0:\0\"\xe2\x06\n\x07Account\x12\x14\n\x04name\
+    \x18\x01\x20\x02(\tR\x04nameB\0\x12\x18\n\x06number\x18\x02\x20\x02(\rR\
+    \x06numberB\0\x12'\n\x0elegacy_balance\x18\x03\x20\x01(\x11R\rlegacyBala\
+    nceB\0\x12\x1a\n\x04lots\x18\x04\x20\x03(\x0b2\x04.LotR\x04lotsB\0\x12\
+    \x20\n\x0bnext_lot_id\x18\x05\x20\x01(\x04R\tnextLotIdB\0\x12*\n\x10owne\
+    r_public_key\x18\x06\x20\x01(\tR\x0eownerPublicKeyB\0\x12.\n\x12last_aut\
+    horized_at\x18\x07\x20\x01(\x12R\x10lastAuthorizedAtB\0\x12+\n\x10previo\
+    us_numbers\x18\x08\x20\x03(\rR\x0fpreviousNumbersB\0\x121\n\x06status\
+    \x18\t\x20\x01(\x0e2\x0f.Account.Status:\x06ACTIVER\x06statusB\0\x12'\n\
+    \x0eclosure_reason\x18\n\x20\x01(\tR\rclosureReasonB\0\x12?\n\x11currenc\
+    y_balances\x18\x0b\x20\x03(\x0b2\x10.CurrencyBalanceR\x10currencyBalance\
+    sB\0\x12)\n\x0flifetime_earned\x18\x0c\x20\x01(\x04R\x0elifetimeEarnedB\
+    \0\x129\n\x0fearning_periods\x18\r\x20\x03(\x0b2\x0e.EarningPeriodR\x0ee\
+    arningPeriodsB\0\x12\x14\n\x04tier\x18\x0e\x20\x01(\tR\x04tierB\0\x12(\n\
+    \x0ftier_changed_at\x18\x0f\x20\x01(\x12R\rtierChangedAtB\0\x129\n\x0fre\
+    cent_deposits\x18\x10\x20\x03(\x0b2\x0e.VelocityEntryR\x0erecentDeposits\
+    B\0\x12?\n\x12recent_withdrawals\x18\x11\x20\x03(\x0b2\x0e.VelocityEntry\
+    R\x11recentWithdrawalsB\0\x12*\n\x10last_activity_at\x18\x12\x20\x01(\
+    \x12R\x0elastActivityAtB\0\x12\x1a\n\x07balance\x18\x13\x20\x01(\x04R\
+    \x07balanceB\0\".\n\x06Status\x12\n\n\x06ACTIVE\x10\0\x12\n\n\x06FROZEN\
+    \x10\x01\x12\n\n\x06CLOSED\x10\x02\x1a\0:\0\":\n\x10AccountContainer\x12\
+    $\n\x07entries\x18\x01\x20\x03(\x0b2\x08.AccountR\x07entriesB\0:\0B\0b\
+    \x06proto2\
 ";
 
 static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
 
 fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
-    ::protobuf::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
 }
 
 pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
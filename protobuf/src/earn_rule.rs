@@ -0,0 +1,866 @@
+// This file is generated by rust-protobuf 2.28.0. Do not edit
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+//! Generated file from `earn_rule.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
+
+#[derive(PartialEq,Clone,Default)]
+pub struct EarnRule {
+    // message fields
+    public_key: ::protobuf::SingularField<::std::string::String>,
+    points_per_unit: ::std::option::Option<u32>,
+    rounding: ::std::option::Option<EarnRule_Rounding>,
+    minimum_purchase: ::std::option::Option<u64>,
+    pub multipliers: ::protobuf::RepeatedField<EarnRule_CategoryMultiplier>,
+    points_lifetime: ::std::option::Option<i64>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a EarnRule {
+    fn default() -> &'a EarnRule {
+        <EarnRule as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl EarnRule {
+    pub fn new() -> EarnRule {
+        ::std::default::Default::default()
+    }
+
+    // required string public_key = 1;
+
+
+    pub fn get_public_key(&self) -> &str {
+        match self.public_key.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_public_key(&mut self) {
+        self.public_key.clear();
+    }
+
+    pub fn has_public_key(&self) -> bool {
+        self.public_key.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_public_key(&mut self, v: ::std::string::String) {
+        self.public_key = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_public_key(&mut self) -> &mut ::std::string::String {
+        if self.public_key.is_none() {
+            self.public_key.set_default();
+        }
+        self.public_key.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_public_key(&mut self) -> ::std::string::String {
+        self.public_key.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required uint32 points_per_unit = 2;
+
+
+    pub fn get_points_per_unit(&self) -> u32 {
+        self.points_per_unit.unwrap_or(0)
+    }
+    pub fn clear_points_per_unit(&mut self) {
+        self.points_per_unit = ::std::option::Option::None;
+    }
+
+    pub fn has_points_per_unit(&self) -> bool {
+        self.points_per_unit.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_points_per_unit(&mut self, v: u32) {
+        self.points_per_unit = ::std::option::Option::Some(v);
+    }
+
+    // required .EarnRule.Rounding rounding = 3;
+
+
+    pub fn get_rounding(&self) -> EarnRule_Rounding {
+        self.rounding.unwrap_or(EarnRule_Rounding::FLOOR)
+    }
+    pub fn clear_rounding(&mut self) {
+        self.rounding = ::std::option::Option::None;
+    }
+
+    pub fn has_rounding(&self) -> bool {
+        self.rounding.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rounding(&mut self, v: EarnRule_Rounding) {
+        self.rounding = ::std::option::Option::Some(v);
+    }
+
+    // required uint64 minimum_purchase = 4;
+
+
+    pub fn get_minimum_purchase(&self) -> u64 {
+        self.minimum_purchase.unwrap_or(0)
+    }
+    pub fn clear_minimum_purchase(&mut self) {
+        self.minimum_purchase = ::std::option::Option::None;
+    }
+
+    pub fn has_minimum_purchase(&self) -> bool {
+        self.minimum_purchase.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_minimum_purchase(&mut self, v: u64) {
+        self.minimum_purchase = ::std::option::Option::Some(v);
+    }
+
+    // repeated .EarnRule.CategoryMultiplier multipliers = 5;
+
+
+    pub fn get_multipliers(&self) -> &[EarnRule_CategoryMultiplier] {
+        &self.multipliers
+    }
+    pub fn clear_multipliers(&mut self) {
+        self.multipliers.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_multipliers(&mut self, v: ::protobuf::RepeatedField<EarnRule_CategoryMultiplier>) {
+        self.multipliers = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_multipliers(&mut self) -> &mut ::protobuf::RepeatedField<EarnRule_CategoryMultiplier> {
+        &mut self.multipliers
+    }
+
+    // Take field
+    pub fn take_multipliers(&mut self) -> ::protobuf::RepeatedField<EarnRule_CategoryMultiplier> {
+        ::std::mem::replace(&mut self.multipliers, ::protobuf::RepeatedField::new())
+    }
+
+    // optional sint64 points_lifetime = 6;
+
+
+    pub fn get_points_lifetime(&self) -> i64 {
+        self.points_lifetime.unwrap_or(0)
+    }
+    pub fn clear_points_lifetime(&mut self) {
+        self.points_lifetime = ::std::option::Option::None;
+    }
+
+    pub fn has_points_lifetime(&self) -> bool {
+        self.points_lifetime.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_points_lifetime(&mut self, v: i64) {
+        self.points_lifetime = ::std::option::Option::Some(v);
+    }
+}
+
+impl ::protobuf::Message for EarnRule {
+    fn is_initialized(&self) -> bool {
+        if self.public_key.is_none() {
+            return false;
+        }
+        if self.points_per_unit.is_none() {
+            return false;
+        }
+        if self.rounding.is_none() {
+            return false;
+        }
+        if self.minimum_purchase.is_none() {
+            return false;
+        }
+        for v in &self.multipliers {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.public_key)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.points_per_unit = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    ::protobuf::rt::read_proto2_enum_with_unknown_fields_into(wire_type, is, &mut self.rounding, 3, &mut self.unknown_fields)?
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.minimum_purchase = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.multipliers)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.points_lifetime = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.public_key.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(v) = self.points_per_unit {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.rounding {
+            my_size += ::protobuf::rt::enum_size(3, v);
+        }
+        if let Some(v) = self.minimum_purchase {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.multipliers {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(v) = self.points_lifetime {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(6, v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.public_key.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(v) = self.points_per_unit {
+            os.write_uint32(2, v)?;
+        }
+        if let Some(v) = self.rounding {
+            os.write_enum(3, ::protobuf::ProtobufEnum::value(&v))?;
+        }
+        if let Some(v) = self.minimum_purchase {
+            os.write_uint64(4, v)?;
+        }
+        for v in &self.multipliers {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(v) = self.points_lifetime {
+            os.write_sint64(6, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> EarnRule {
+        EarnRule::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "public_key",
+                |m: &EarnRule| { &m.public_key },
+                |m: &mut EarnRule| { &mut m.public_key },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "points_per_unit",
+                |m: &EarnRule| { &m.points_per_unit },
+                |m: &mut EarnRule| { &mut m.points_per_unit },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<EarnRule_Rounding>>(
+                "rounding",
+                |m: &EarnRule| { &m.rounding },
+                |m: &mut EarnRule| { &mut m.rounding },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "minimum_purchase",
+                |m: &EarnRule| { &m.minimum_purchase },
+                |m: &mut EarnRule| { &mut m.minimum_purchase },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<EarnRule_CategoryMultiplier>>(
+                "multipliers",
+                |m: &EarnRule| { &m.multipliers },
+                |m: &mut EarnRule| { &mut m.multipliers },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "points_lifetime",
+                |m: &EarnRule| { &m.points_lifetime },
+                |m: &mut EarnRule| { &mut m.points_lifetime },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<EarnRule>(
+                "EarnRule",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static EarnRule {
+        static instance: ::protobuf::rt::LazyV2<EarnRule> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(EarnRule::new)
+    }
+}
+
+impl ::protobuf::Clear for EarnRule {
+    fn clear(&mut self) {
+        self.public_key.clear();
+        self.points_per_unit = ::std::option::Option::None;
+        self.rounding = ::std::option::Option::None;
+        self.minimum_purchase = ::std::option::Option::None;
+        self.multipliers.clear();
+        self.points_lifetime = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for EarnRule {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EarnRule {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct EarnRule_CategoryMultiplier {
+    // message fields
+    category: ::protobuf::SingularField<::std::string::String>,
+    multiplier: ::std::option::Option<u32>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a EarnRule_CategoryMultiplier {
+    fn default() -> &'a EarnRule_CategoryMultiplier {
+        <EarnRule_CategoryMultiplier as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl EarnRule_CategoryMultiplier {
+    pub fn new() -> EarnRule_CategoryMultiplier {
+        ::std::default::Default::default()
+    }
+
+    // required string category = 1;
+
+
+    pub fn get_category(&self) -> &str {
+        match self.category.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_category(&mut self) {
+        self.category.clear();
+    }
+
+    pub fn has_category(&self) -> bool {
+        self.category.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_category(&mut self, v: ::std::string::String) {
+        self.category = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_category(&mut self) -> &mut ::std::string::String {
+        if self.category.is_none() {
+            self.category.set_default();
+        }
+        self.category.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_category(&mut self) -> ::std::string::String {
+        self.category.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required uint32 multiplier = 2;
+
+
+    pub fn get_multiplier(&self) -> u32 {
+        self.multiplier.unwrap_or(0)
+    }
+    pub fn clear_multiplier(&mut self) {
+        self.multiplier = ::std::option::Option::None;
+    }
+
+    pub fn has_multiplier(&self) -> bool {
+        self.multiplier.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_multiplier(&mut self, v: u32) {
+        self.multiplier = ::std::option::Option::Some(v);
+    }
+}
+
+impl ::protobuf::Message for EarnRule_CategoryMultiplier {
+    fn is_initialized(&self) -> bool {
+        if self.category.is_none() {
+            return false;
+        }
+        if self.multiplier.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.category)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.multiplier = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.category.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(v) = self.multiplier {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.category.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(v) = self.multiplier {
+            os.write_uint32(2, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> EarnRule_CategoryMultiplier {
+        EarnRule_CategoryMultiplier::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "category",
+                |m: &EarnRule_CategoryMultiplier| { &m.category },
+                |m: &mut EarnRule_CategoryMultiplier| { &mut m.category },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "multiplier",
+                |m: &EarnRule_CategoryMultiplier| { &m.multiplier },
+                |m: &mut EarnRule_CategoryMultiplier| { &mut m.multiplier },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<EarnRule_CategoryMultiplier>(
+                "EarnRule.CategoryMultiplier",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static EarnRule_CategoryMultiplier {
+        static instance: ::protobuf::rt::LazyV2<EarnRule_CategoryMultiplier> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(EarnRule_CategoryMultiplier::new)
+    }
+}
+
+impl ::protobuf::Clear for EarnRule_CategoryMultiplier {
+    fn clear(&mut self) {
+        self.category.clear();
+        self.multiplier = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for EarnRule_CategoryMultiplier {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EarnRule_CategoryMultiplier {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum EarnRule_Rounding {
+    FLOOR = 0,
+    CEIL = 1,
+    NEAREST = 2,
+}
+
+impl ::protobuf::ProtobufEnum for EarnRule_Rounding {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<EarnRule_Rounding> {
+        match value {
+            0 => ::std::option::Option::Some(EarnRule_Rounding::FLOOR),
+            1 => ::std::option::Option::Some(EarnRule_Rounding::CEIL),
+            2 => ::std::option::Option::Some(EarnRule_Rounding::NEAREST),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [EarnRule_Rounding] = &[
+            EarnRule_Rounding::FLOOR,
+            EarnRule_Rounding::CEIL,
+            EarnRule_Rounding::NEAREST,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<EarnRule_Rounding>("EarnRule.Rounding", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for EarnRule_Rounding {
+}
+
+impl ::std::default::Default for EarnRule_Rounding {
+    fn default() -> Self {
+        EarnRule_Rounding::FLOOR
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EarnRule_Rounding {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct EarnRuleContainer {
+    // message fields
+    pub entries: ::protobuf::RepeatedField<EarnRule>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a EarnRuleContainer {
+    fn default() -> &'a EarnRuleContainer {
+        <EarnRuleContainer as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl EarnRuleContainer {
+    pub fn new() -> EarnRuleContainer {
+        ::std::default::Default::default()
+    }
+
+    // repeated .EarnRule entries = 1;
+
+
+    pub fn get_entries(&self) -> &[EarnRule] {
+        &self.entries
+    }
+    pub fn clear_entries(&mut self) {
+        self.entries.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_entries(&mut self, v: ::protobuf::RepeatedField<EarnRule>) {
+        self.entries = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_entries(&mut self) -> &mut ::protobuf::RepeatedField<EarnRule> {
+        &mut self.entries
+    }
+
+    // Take field
+    pub fn take_entries(&mut self) -> ::protobuf::RepeatedField<EarnRule> {
+        ::std::mem::replace(&mut self.entries, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for EarnRuleContainer {
+    fn is_initialized(&self) -> bool {
+        for v in &self.entries {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.entries)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.entries {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.entries {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> EarnRuleContainer {
+        EarnRuleContainer::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<EarnRule>>(
+                "entries",
+                |m: &EarnRuleContainer| { &m.entries },
+                |m: &mut EarnRuleContainer| { &mut m.entries },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<EarnRuleContainer>(
+                "EarnRuleContainer",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static EarnRuleContainer {
+        static instance: ::protobuf::rt::LazyV2<EarnRuleContainer> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(EarnRuleContainer::new)
+    }
+}
+
+impl ::protobuf::Clear for EarnRuleContainer {
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for EarnRuleContainer {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EarnRuleContainer {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\x0fearn_rule.proto\"\xab\x03\n\x08EarnRule\x12\x1f\n\npublic_key\x18\
+    \x01\x20\x02(\tR\tpublicKeyB\0\x12(\n\x0fpoints_per_unit\x18\x02\x20\x02\
+    (\rR\rpointsPerUnitB\0\x120\n\x08rounding\x18\x03\x20\x02(\x0e2\x12.Earn\
+    Rule.RoundingR\x08roundingB\0\x12+\n\x10minimum_purchase\x18\x04\x20\x02\
+    (\x04R\x0fminimumPurchaseB\0\x12@\n\x0bmultipliers\x18\x05\x20\x03(\x0b2\
+    \x1c.EarnRule.CategoryMultiplierR\x0bmultipliersB\0\x12)\n\x0fpoints_lif\
+    etime\x18\x06\x20\x01(\x12R\x0epointsLifetimeB\0\x1aV\n\x12CategoryMulti\
+    plier\x12\x1c\n\x08category\x18\x01\x20\x02(\tR\x08categoryB\0\x12\x20\n\
+    \nmultiplier\x18\x02\x20\x02(\rR\nmultiplierB\0:\0\".\n\x08Rounding\x12\
+    \t\n\x05FLOOR\x10\0\x12\x08\n\x04CEIL\x10\x01\x12\x0b\n\x07NEAREST\x10\
+    \x02\x1a\0:\0\"<\n\x11EarnRuleContainer\x12%\n\x07entries\x18\x01\x20\
+    \x03(\x0b2\t.EarnRuleR\x07entriesB\0:\0B\0b\x06proto2\
+";
+
+static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    file_descriptor_proto_lazy.get(|| {
+        parse_descriptor_proto()
+    })
+}
@@ -1,12 +1,25 @@
-use archer::{get_address_type, Account, ArcherStructs, ArcherTypes, Merchant};
+use archer::{
+    get_address_type, Account, AccountStatus, ArcherStructs, ArcherTypes, CatalogItem, EarnRule,
+    Lot, Merchant, Settlement, Voucher,
+};
 use protobuf::{parse_from_bytes, Message};
 
 pub mod account;
+pub mod catalog;
+pub mod earn_rule;
 pub mod merchant;
 pub mod payload;
+pub mod receipt;
+pub mod settlement;
+pub mod velocity;
+pub mod voucher;
 
-use account::{Account as AccountPB, AccountContainer};
+use account::{Account as AccountPB, AccountContainer, Account_Status};
+use catalog::{CatalogItem as CatalogItemPB, CatalogItemContainer};
+use earn_rule::{EarnRule as EarnRulePB, EarnRuleContainer};
 use merchant::{Merchant as MerchantPB, MerchantContainer};
+use settlement::{Settlement as SettlementPB, SettlementContainer};
+use voucher::{Voucher as VoucherPB, VoucherContainer};
 
 // TODO ! what do I do with this?
 enum _Containers {
@@ -14,12 +27,18 @@ enum _Containers {
     MerchantContainer(Box<dyn Message>),
 }
 
-pub fn deserialize_data(address: &str, data: Vec<u8>) -> (ArcherTypes, Vec<ArcherStructs>) {
-    let data_type = get_address_type(address).expect("Invalid address infix");
+/// Decodes the container stored at an address. State changes are read off the network,
+/// so malformed addresses and bytes are reported rather than trusted.
+pub fn deserialize_data(
+    address: &str,
+    data: Vec<u8>,
+) -> Result<(ArcherTypes, Vec<ArcherStructs>), String> {
+    let data_type =
+        get_address_type(address).ok_or_else(|| format!("Invalid address infix in {}", address))?;
 
     let resources = match data_type {
         ArcherTypes::Account => {
-            let entries = parse_accounts_from_proto(data);
+            let entries = parse_accounts_from_proto(data)?;
             entries
                 .iter()
                 .map(|entry| {
@@ -28,7 +47,7 @@ pub fn deserialize_data(address: &str, data: Vec<u8>) -> (ArcherTypes, Vec<Arche
                 .collect()
         }
         ArcherTypes::Merchant => {
-            let entries = parse_merchants_from_proto(data);
+            let entries = parse_merchants_from_proto(data)?;
             entries
                 .iter()
                 .map(|entry| {
@@ -36,21 +55,81 @@ pub fn deserialize_data(address: &str, data: Vec<u8>) -> (ArcherTypes, Vec<Arche
                 })
                 .collect()
         }
+        ArcherTypes::EarnRule => {
+            let entries = parse_earn_rules_from_proto(data)?;
+            entries
+                .iter()
+                .map(|entry| {
+                    convert_proto_to_earn_rule(data_type, entry).expect("Invalid struct found")
+                })
+                .collect()
+        }
+        ArcherTypes::CatalogItem => {
+            let entries = parse_catalog_items_from_proto(data)?;
+            entries
+                .iter()
+                .map(|entry| {
+                    convert_proto_to_catalog_item(data_type, entry).expect("Invalid struct found")
+                })
+                .collect()
+        }
+        ArcherTypes::Voucher => {
+            let entries = parse_vouchers_from_proto(data)?;
+            entries
+                .iter()
+                .map(|entry| {
+                    convert_proto_to_voucher(data_type, entry).expect("Invalid struct found")
+                })
+                .collect()
+        }
+        ArcherTypes::Settlement => {
+            let entries = parse_settlements_from_proto(data)?;
+            entries
+                .iter()
+                .map(|entry| {
+                    convert_proto_to_settlement(data_type, entry).expect("Invalid struct found")
+                })
+                .collect()
+        }
     };
 
-    (data_type, resources)
+    Ok((data_type, resources))
 }
 
-pub fn parse_accounts_from_proto(data: Vec<u8>) -> Vec<AccountPB> {
+pub fn parse_accounts_from_proto(data: Vec<u8>) -> Result<Vec<AccountPB>, String> {
     let deserialized = parse_from_bytes::<AccountContainer>(&data)
-        .expect("Error parsing account from bytes");
-    deserialized.get_entries().to_vec()
+        .map_err(|err| format!("Error parsing account from bytes: {}", err))?;
+    Ok(deserialized.get_entries().to_vec())
 }
 
-pub fn parse_merchants_from_proto(data: Vec<u8>) -> Vec<MerchantPB> {
+pub fn parse_merchants_from_proto(data: Vec<u8>) -> Result<Vec<MerchantPB>, String> {
     let deserialized = parse_from_bytes::<MerchantContainer>(&data)
-        .expect("Error parsing merchant from bytes");
-    deserialized.get_entries().to_vec()
+        .map_err(|err| format!("Error parsing merchant from bytes: {}", err))?;
+    Ok(deserialized.get_entries().to_vec())
+}
+
+pub fn parse_earn_rules_from_proto(data: Vec<u8>) -> Result<Vec<EarnRulePB>, String> {
+    let deserialized = parse_from_bytes::<EarnRuleContainer>(&data)
+        .map_err(|err| format!("Error parsing earn rule from bytes: {}", err))?;
+    Ok(deserialized.get_entries().to_vec())
+}
+
+pub fn parse_catalog_items_from_proto(data: Vec<u8>) -> Result<Vec<CatalogItemPB>, String> {
+    let deserialized = parse_from_bytes::<CatalogItemContainer>(&data)
+        .map_err(|err| format!("Error parsing catalog item from bytes: {}", err))?;
+    Ok(deserialized.get_entries().to_vec())
+}
+
+pub fn parse_vouchers_from_proto(data: Vec<u8>) -> Result<Vec<VoucherPB>, String> {
+    let deserialized = parse_from_bytes::<VoucherContainer>(&data)
+        .map_err(|err| format!("Error parsing voucher from bytes: {}", err))?;
+    Ok(deserialized.get_entries().to_vec())
+}
+
+pub fn parse_settlements_from_proto(data: Vec<u8>) -> Result<Vec<SettlementPB>, String> {
+    let deserialized = parse_from_bytes::<SettlementContainer>(&data)
+        .map_err(|err| format!("Error parsing settlement from bytes: {}", err))?;
+    Ok(deserialized.get_entries().to_vec())
 }
 
 pub fn convert_proto_to_account(
@@ -63,12 +142,68 @@ pub fn convert_proto_to_account(
                 name: String::from(entry.get_name()),
                 number: entry.get_number(),
                 balance: entry.get_balance(),
+                currency_balances: entry
+                    .get_currency_balances()
+                    .iter()
+                    .map(|balance| (String::from(balance.get_currency()), balance.get_balance()))
+                    .collect(),
+                lots: entry
+                    .get_lots()
+                    .iter()
+                    .map(|lot| Lot {
+                        id: lot.get_id(),
+                        amount: lot.get_amount(),
+                        earned_at: lot.get_earned_at(),
+                        expires_at: match lot.get_expires_at() {
+                            0 => None,
+                            expires_at => Some(expires_at),
+                        },
+                        issuer: match lot.get_issuer() {
+                            "" => None,
+                            issuer => Some(String::from(issuer)),
+                        },
+                        currency: match lot.get_currency() {
+                            "" => None,
+                            currency => Some(String::from(currency)),
+                        },
+                    })
+                    .collect(),
+                owner_public_key: match entry.get_owner_public_key() {
+                    "" => None,
+                    owner_public_key => Some(String::from(owner_public_key)),
+                },
+                previous_numbers: entry.get_previous_numbers().to_vec(),
+                status: match entry.get_status() {
+                    Account_Status::ACTIVE => AccountStatus::Active,
+                    Account_Status::FROZEN => AccountStatus::Frozen,
+                    Account_Status::CLOSED => AccountStatus::Closed,
+                },
+                closure_reason: if entry.has_closure_reason() {
+                    Some(String::from(entry.get_closure_reason()))
+                } else {
+                    None
+                },
+                lifetime_earned: entry.get_lifetime_earned(),
+                tier: if entry.has_tier() {
+                    Some(String::from(entry.get_tier()))
+                } else {
+                    None
+                },
+                tier_changed_at: if entry.has_tier_changed_at() {
+                    Some(entry.get_tier_changed_at())
+                } else {
+                    None
+                },
                 start_block_num: None,
                 end_block_num: None,
             };
             Some(ArcherStructs::Account(account))
         }
         ArcherTypes::Merchant => None,
+        ArcherTypes::EarnRule => None,
+        ArcherTypes::CatalogItem => None,
+        ArcherTypes::Voucher => None,
+        ArcherTypes::Settlement => None,
     }
 }
 
@@ -83,11 +218,131 @@ pub fn convert_proto_to_merchant(
                 public_key: String::from(entry.get_public_key()),
                 name: String::from(entry.get_name()),
                 timestamp: entry.get_timestamp(),
+                successor_public_key: if entry.has_successor_public_key() {
+                    Some(String::from(entry.get_successor_public_key()))
+                } else {
+                    None
+                },
+                previous_public_keys: entry.get_previous_public_keys().to_vec(),
                 start_block_num: None,
                 end_block_num: None,
             };
             Some(ArcherStructs::Merchant(merchant))
         }
+        ArcherTypes::EarnRule => None,
+        ArcherTypes::CatalogItem => None,
+        ArcherTypes::Voucher => None,
+        ArcherTypes::Settlement => None,
+    }
+}
+
+pub fn convert_proto_to_earn_rule(
+    data_type: ArcherTypes,
+    entry: &EarnRulePB,
+) -> Option<ArcherStructs> {
+    match data_type {
+        ArcherTypes::Account => None,
+        ArcherTypes::Merchant => None,
+        ArcherTypes::EarnRule => {
+            let earn_rule = EarnRule {
+                public_key: String::from(entry.get_public_key()),
+                points_per_unit: entry.get_points_per_unit(),
+                rounding: format!("{:?}", entry.get_rounding()),
+                minimum_purchase: entry.get_minimum_purchase(),
+                multipliers: entry
+                    .get_multipliers()
+                    .iter()
+                    .map(|multiplier| {
+                        (
+                            String::from(multiplier.get_category()),
+                            multiplier.get_multiplier(),
+                        )
+                    })
+                    .collect(),
+                points_lifetime: match entry.get_points_lifetime() {
+                    0 => None,
+                    points_lifetime => Some(points_lifetime),
+                },
+                start_block_num: None,
+                end_block_num: None,
+            };
+            Some(ArcherStructs::EarnRule(earn_rule))
+        }
+        ArcherTypes::CatalogItem => None,
+        ArcherTypes::Voucher => None,
+        ArcherTypes::Settlement => None,
+    }
+}
+
+pub fn convert_proto_to_catalog_item(
+    data_type: ArcherTypes,
+    entry: &CatalogItemPB,
+) -> Option<ArcherStructs> {
+    match data_type {
+        ArcherTypes::CatalogItem => {
+            let catalog_item = CatalogItem {
+                public_key: String::from(entry.get_public_key()),
+                id: String::from(entry.get_id()),
+                description: String::from(entry.get_description()),
+                point_cost: entry.get_point_cost(),
+                stock: entry.get_stock(),
+                start_block_num: None,
+                end_block_num: None,
+            };
+            Some(ArcherStructs::CatalogItem(catalog_item))
+        }
+        _ => None,
+    }
+}
+
+pub fn convert_proto_to_voucher(
+    data_type: ArcherTypes,
+    entry: &VoucherPB,
+) -> Option<ArcherStructs> {
+    match data_type {
+        ArcherTypes::Voucher => {
+            let voucher = Voucher {
+                public_key: String::from(entry.get_public_key()),
+                id: String::from(entry.get_id()),
+                name: String::from(entry.get_name()),
+                number: entry.get_number(),
+                discount: entry.get_discount(),
+                issued_at: entry.get_issued_at(),
+                expires_at: match entry.get_expires_at() {
+                    0 => None,
+                    expires_at => Some(expires_at),
+                },
+                consumed_at: if entry.has_consumed_at() {
+                    Some(entry.get_consumed_at())
+                } else {
+                    None
+                },
+                start_block_num: None,
+                end_block_num: None,
+            };
+            Some(ArcherStructs::Voucher(voucher))
+        }
+        _ => None,
+    }
+}
+
+pub fn convert_proto_to_settlement(
+    data_type: ArcherTypes,
+    entry: &SettlementPB,
+) -> Option<ArcherStructs> {
+    match data_type {
+        ArcherTypes::Settlement => {
+            let settlement = Settlement {
+                issuer: String::from(entry.get_issuer()),
+                redeemer: String::from(entry.get_redeemer()),
+                period_start: entry.get_period_start(),
+                amount: entry.get_amount(),
+                start_block_num: None,
+                end_block_num: None,
+            };
+            Some(ArcherStructs::Settlement(settlement))
+        }
+        _ => None,
     }
 }
 
@@ -105,10 +360,43 @@ mod tests {
         entry.set_name(name.to_string());
         entry.set_number(number);
         entry.set_balance(1000);
+        let mut lot = account::Lot::default();
+        lot.set_id(0);
+        lot.set_amount(1000);
+        lot.set_earned_at(10003456);
+        lot.set_expires_at(0);
+        entry.mut_lots().push(lot);
         entry.compute_size();
         let account = convert_proto_to_account(data_type, &entry).unwrap();
         let result = account.account().unwrap();
         assert_eq!(&result.name, name);
+        assert_eq!(result.lots.len(), 1);
+        assert_eq!(result.lots[0].expires_at, None);
+        assert_eq!(result.lots[0].issuer, None);
+        assert_eq!(result.lots[0].currency, None);
+        assert!(result.currency_balances.is_empty());
+        assert_eq!(result.owner_public_key, None);
+        assert_eq!(result.status, AccountStatus::Active);
+        assert_eq!(result.tier, None);
+        assert_eq!(result.tier_changed_at, None);
+
+        let mut currency_balance = account::CurrencyBalance::default();
+        currency_balance.set_currency(String::from("shop"));
+        currency_balance.set_balance(250);
+        entry.mut_currency_balances().push(currency_balance);
+        entry.set_status(Account_Status::CLOSED);
+        entry.set_closure_reason(String::from("Fraud"));
+        entry.set_lifetime_earned(5000);
+        entry.set_tier(String::from("gold"));
+        entry.set_tier_changed_at(10003456);
+        let account = convert_proto_to_account(data_type, &entry).unwrap();
+        let result = account.account().unwrap();
+        assert_eq!(result.status, AccountStatus::Closed);
+        assert_eq!(result.closure_reason, Some(String::from("Fraud")));
+        assert_eq!(result.currency_balances, vec![(String::from("shop"), 250)]);
+        assert_eq!(result.lifetime_earned, 5000);
+        assert_eq!(result.tier, Some(String::from("gold")));
+        assert_eq!(result.tier_changed_at, Some(10003456));
     }
 
     #[test]
@@ -119,9 +407,107 @@ mod tests {
         entry.set_public_key("abcdefghijklmnopqrstuvwxyz1234567890".to_string());
         entry.set_name(name.to_string());
         entry.set_timestamp(10003456);
+        entry.set_successor_public_key("successor".to_string());
+        entry
+            .mut_previous_public_keys()
+            .push("predecessor".to_string());
         entry.compute_size();
         let merchant = convert_proto_to_merchant(data_type, &entry).unwrap();
         let result = merchant.merchant().unwrap();
         assert_eq!(&result.name, name);
+        assert_eq!(result.successor_public_key.as_deref(), Some("successor"));
+        assert_eq!(
+            result.previous_public_keys,
+            vec![String::from("predecessor")]
+        );
+    }
+
+    #[test]
+    fn proto_to_earn_rule() {
+        let data_type = ArcherTypes::EarnRule;
+        let mut multiplier = earn_rule::EarnRule_CategoryMultiplier::default();
+        multiplier.set_category("dessert".to_string());
+        multiplier.set_multiplier(200);
+        let mut entry = EarnRulePB::default();
+        entry.set_public_key("abcdefghijklmnopqrstuvwxyz1234567890".to_string());
+        entry.set_points_per_unit(10);
+        entry.set_rounding(earn_rule::EarnRule_Rounding::NEAREST);
+        entry.set_minimum_purchase(500);
+        entry.mut_multipliers().push(multiplier);
+        let earn_rule = convert_proto_to_earn_rule(data_type, &entry).unwrap();
+        let result = earn_rule.earn_rule().unwrap();
+        assert_eq!(result.points_per_unit, 10);
+        assert_eq!(&result.rounding, "NEAREST");
+        assert_eq!(result.multipliers, vec![(String::from("dessert"), 200)]);
+    }
+
+    #[test]
+    fn proto_to_catalog_item() {
+        let mut entry = CatalogItemPB::default();
+        entry.set_public_key("abcdefghijklmnopqrstuvwxyz1234567890".to_string());
+        entry.set_id("mug".to_string());
+        entry.set_description("Coffee mug".to_string());
+        entry.set_point_cost(500);
+        entry.set_stock(20);
+        assert!(convert_proto_to_catalog_item(ArcherTypes::Account, &entry).is_none());
+        let catalog_item = convert_proto_to_catalog_item(ArcherTypes::CatalogItem, &entry).unwrap();
+        let result = catalog_item.catalog_item().unwrap();
+        assert_eq!(&result.id, "mug");
+        assert_eq!(result.point_cost, 500);
+        assert_eq!(result.stock, 20);
+    }
+
+    #[test]
+    fn proto_to_voucher() {
+        let mut entry = VoucherPB::default();
+        entry.set_public_key("abcdefghijklmnopqrstuvwxyz1234567890".to_string());
+        entry.set_id("spring-10".to_string());
+        entry.set_name("John Doe".to_string());
+        entry.set_number(12345);
+        entry.set_discount(1000);
+        entry.set_issued_at(10003456);
+        entry.set_expires_at(0);
+        let voucher = convert_proto_to_voucher(ArcherTypes::Voucher, &entry).unwrap();
+        let result = voucher.voucher().unwrap();
+        assert_eq!(&result.id, "spring-10");
+        assert_eq!(result.expires_at, None);
+        assert_eq!(result.consumed_at, None);
+
+        entry.set_consumed_at(10004000);
+        let voucher = convert_proto_to_voucher(ArcherTypes::Voucher, &entry).unwrap();
+        assert_eq!(voucher.voucher().unwrap().consumed_at, Some(10004000));
+    }
+
+    #[test]
+    fn proto_to_settlement() {
+        let mut entry = SettlementPB::default();
+        entry.set_issuer("abcdefghijklmnopqrstuvwxyz1234567890".to_string());
+        entry.set_redeemer("0987654321zyxwvutsrqponmlkjihgfedcba".to_string());
+        entry.set_period_start(1_599_955_200);
+        entry.set_amount(250);
+        assert!(convert_proto_to_settlement(ArcherTypes::Voucher, &entry).is_none());
+        let settlement = convert_proto_to_settlement(ArcherTypes::Settlement, &entry).unwrap();
+        let result = settlement.settlement().unwrap();
+        assert_eq!(result.period_start, 1_599_955_200);
+        assert_eq!(result.amount, 250);
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        let address = archer::calculate_merchant_address("abcdef");
+        let mut container = MerchantContainer::new();
+        let mut entry = MerchantPB::default();
+        entry.set_public_key("abcdef".to_string());
+        entry.set_name("Bob's Poutine".to_string());
+        entry.set_timestamp(10003456);
+        container.mut_entries().push(entry);
+        let data = container.write_to_bytes().unwrap();
+
+        let (data_type, resources) = deserialize_data(&address, data.clone()).unwrap();
+        assert_eq!(data_type, ArcherTypes::Merchant);
+        assert_eq!(resources.len(), 1);
+        assert!(deserialize_data(&address, vec![0xff, 0xff, 0xff]).is_err());
+        assert!(deserialize_data(&address, data[..data.len() - 1].to_vec()).is_err());
+        assert!(deserialize_data("9abef4", data).is_err());
     }
 }
@@ -1,4 +1,4 @@
-// This file is generated by rust-protobuf 2.18.1. Do not edit
+// This file is generated by rust-protobuf 2.28.0. Do not edit
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -6,7 +6,7 @@
 #![allow(clippy::all)]
 
 #![allow(unused_attributes)]
-#![rustfmt::skip]
+#![cfg_attr(rustfmt, rustfmt::skip)]
 
 #![allow(box_pointers)]
 #![allow(dead_code)]
@@ -21,7 +21,7 @@
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_18_1;
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
 
 #[derive(PartialEq,Clone,Default)]
 pub struct Merchant {
@@ -29,6 +29,10 @@ pub struct Merchant {
     public_key: ::protobuf::SingularField<::std::string::String>,
     name: ::protobuf::SingularField<::std::string::String>,
     timestamp: ::std::option::Option<i64>,
+    pub recent_deposits: ::protobuf::RepeatedField<super::velocity::VelocityEntry>,
+    pub recent_withdrawals: ::protobuf::RepeatedField<super::velocity::VelocityEntry>,
+    successor_public_key: ::protobuf::SingularField<::std::string::String>,
+    pub previous_public_keys: ::protobuf::RepeatedField<::std::string::String>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -135,6 +139,117 @@ impl Merchant {
     pub fn set_timestamp(&mut self, v: i64) {
         self.timestamp = ::std::option::Option::Some(v);
     }
+
+    // repeated .VelocityEntry recent_deposits = 4;
+
+
+    pub fn get_recent_deposits(&self) -> &[super::velocity::VelocityEntry] {
+        &self.recent_deposits
+    }
+    pub fn clear_recent_deposits(&mut self) {
+        self.recent_deposits.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_recent_deposits(&mut self, v: ::protobuf::RepeatedField<super::velocity::VelocityEntry>) {
+        self.recent_deposits = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_recent_deposits(&mut self) -> &mut ::protobuf::RepeatedField<super::velocity::VelocityEntry> {
+        &mut self.recent_deposits
+    }
+
+    // Take field
+    pub fn take_recent_deposits(&mut self) -> ::protobuf::RepeatedField<super::velocity::VelocityEntry> {
+        ::std::mem::replace(&mut self.recent_deposits, ::protobuf::RepeatedField::new())
+    }
+
+    // repeated .VelocityEntry recent_withdrawals = 5;
+
+
+    pub fn get_recent_withdrawals(&self) -> &[super::velocity::VelocityEntry] {
+        &self.recent_withdrawals
+    }
+    pub fn clear_recent_withdrawals(&mut self) {
+        self.recent_withdrawals.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_recent_withdrawals(&mut self, v: ::protobuf::RepeatedField<super::velocity::VelocityEntry>) {
+        self.recent_withdrawals = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_recent_withdrawals(&mut self) -> &mut ::protobuf::RepeatedField<super::velocity::VelocityEntry> {
+        &mut self.recent_withdrawals
+    }
+
+    // Take field
+    pub fn take_recent_withdrawals(&mut self) -> ::protobuf::RepeatedField<super::velocity::VelocityEntry> {
+        ::std::mem::replace(&mut self.recent_withdrawals, ::protobuf::RepeatedField::new())
+    }
+
+    // optional string successor_public_key = 6;
+
+
+    pub fn get_successor_public_key(&self) -> &str {
+        match self.successor_public_key.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_successor_public_key(&mut self) {
+        self.successor_public_key.clear();
+    }
+
+    pub fn has_successor_public_key(&self) -> bool {
+        self.successor_public_key.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_successor_public_key(&mut self, v: ::std::string::String) {
+        self.successor_public_key = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_successor_public_key(&mut self) -> &mut ::std::string::String {
+        if self.successor_public_key.is_none() {
+            self.successor_public_key.set_default();
+        }
+        self.successor_public_key.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_successor_public_key(&mut self) -> ::std::string::String {
+        self.successor_public_key.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // repeated string previous_public_keys = 7;
+
+
+    pub fn get_previous_public_keys(&self) -> &[::std::string::String] {
+        &self.previous_public_keys
+    }
+    pub fn clear_previous_public_keys(&mut self) {
+        self.previous_public_keys.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_previous_public_keys(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.previous_public_keys = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_previous_public_keys(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.previous_public_keys
+    }
+
+    // Take field
+    pub fn take_previous_public_keys(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.previous_public_keys, ::protobuf::RepeatedField::new())
+    }
 }
 
 impl ::protobuf::Message for Merchant {
@@ -148,6 +263,16 @@ impl ::protobuf::Message for Merchant {
         if self.timestamp.is_none() {
             return false;
         }
+        for v in &self.recent_deposits {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.recent_withdrawals {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -168,6 +293,18 @@ impl ::protobuf::Message for Merchant {
                     let tmp = is.read_sint64()?;
                     self.timestamp = ::std::option::Option::Some(tmp);
                 },
+                4 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.recent_deposits)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.recent_withdrawals)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.successor_public_key)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.previous_public_keys)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -189,6 +326,20 @@ impl ::protobuf::Message for Merchant {
         if let Some(v) = self.timestamp {
             my_size += ::protobuf::rt::value_varint_zigzag_size(3, v);
         }
+        for value in &self.recent_deposits {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.recent_withdrawals {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let Some(ref v) = self.successor_public_key.as_ref() {
+            my_size += ::protobuf::rt::string_size(6, &v);
+        }
+        for value in &self.previous_public_keys {
+            my_size += ::protobuf::rt::string_size(7, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -204,6 +355,22 @@ impl ::protobuf::Message for Merchant {
         if let Some(v) = self.timestamp {
             os.write_sint64(3, v)?;
         }
+        for v in &self.recent_deposits {
+            os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.recent_withdrawals {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let Some(ref v) = self.successor_public_key.as_ref() {
+            os.write_string(6, &v)?;
+        }
+        for v in &self.previous_public_keys {
+            os.write_string(7, &v)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -257,6 +424,26 @@ impl ::protobuf::Message for Merchant {
                 |m: &Merchant| { &m.timestamp },
                 |m: &mut Merchant| { &mut m.timestamp },
             ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<super::velocity::VelocityEntry>>(
+                "recent_deposits",
+                |m: &Merchant| { &m.recent_deposits },
+                |m: &mut Merchant| { &mut m.recent_deposits },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<super::velocity::VelocityEntry>>(
+                "recent_withdrawals",
+                |m: &Merchant| { &m.recent_withdrawals },
+                |m: &mut Merchant| { &mut m.recent_withdrawals },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "successor_public_key",
+                |m: &Merchant| { &m.successor_public_key },
+                |m: &mut Merchant| { &mut m.successor_public_key },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "previous_public_keys",
+                |m: &Merchant| { &m.previous_public_keys },
+                |m: &mut Merchant| { &mut m.previous_public_keys },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<Merchant>(
                 "Merchant",
                 fields,
@@ -276,6 +463,10 @@ impl ::protobuf::Clear for Merchant {
         self.public_key.clear();
         self.name.clear();
         self.timestamp = ::std::option::Option::None;
+        self.recent_deposits.clear();
+        self.recent_withdrawals.clear();
+        self.successor_public_key.clear();
+        self.previous_public_keys.clear();
         self.unknown_fields.clear();
     }
 }
@@ -459,17 +650,22 @@ impl ::protobuf::reflect::ProtobufValue for MerchantContainer {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x0emerchant.proto\"c\n\x08Merchant\x12\x1f\n\npublic_key\x18\x01\x20\
-    \x02(\tR\tpublicKeyB\0\x12\x14\n\x04name\x18\x02\x20\x02(\tR\x04nameB\0\
-    \x12\x1e\n\ttimestamp\x18\x03\x20\x02(\x12R\ttimestampB\0:\0\"<\n\x11Mer\
-    chantContainer\x12%\n\x07entries\x18\x01\x20\x03(\x0b2\t.MerchantR\x07en\
-    triesB\0:\0B\0b\x06proto2\
+    \n\x0emerchant.proto\x1a\x0evelocity.proto\"\xc7\x02\n\x08Merchant\x12\
+    \x1f\n\npublic_key\x18\x01\x20\x02(\tR\tpublicKeyB\0\x12\x14\n\x04name\
+    \x18\x02\x20\x02(\tR\x04nameB\0\x12\x1e\n\ttimestamp\x18\x03\x20\x02(\
+    \x12R\ttimestampB\0\x129\n\x0frecent_deposits\x18\x04\x20\x03(\x0b2\x0e.\
+    VelocityEntryR\x0erecentDepositsB\0\x12?\n\x12recent_withdrawals\x18\x05\
+    \x20\x03(\x0b2\x0e.VelocityEntryR\x11recentWithdrawalsB\0\x122\n\x14succ\
+    essor_public_key\x18\x06\x20\x01(\tR\x12successorPublicKeyB\0\x122\n\x14\
+    previous_public_keys\x18\x07\x20\x03(\tR\x12previousPublicKeysB\0:\0\"<\
+    \n\x11MerchantContainer\x12%\n\x07entries\x18\x01\x20\x03(\x0b2\t.Mercha\
+    ntR\x07entriesB\0:\0B\0b\x06proto2\
 ";
 
 static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
 
 fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
-    ::protobuf::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
 }
 
 pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
@@ -1,4 +1,4 @@
-// This file is generated by rust-protobuf 2.18.1. Do not edit
+// This file is generated by rust-protobuf 2.28.0. Do not edit
 // @generated
 
 // https://github.com/rust-lang/rust-clippy/issues/702
@@ -6,7 +6,7 @@
 #![allow(clippy::all)]
 
 #![allow(unused_attributes)]
-#![rustfmt::skip]
+#![cfg_attr(rustfmt, rustfmt::skip)]
 
 #![allow(box_pointers)]
 #![allow(dead_code)]
@@ -21,7 +21,7 @@
 
 /// Generated files are compatible only with the same version
 /// of protobuf runtime.
-// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_18_1;
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
 
 #[derive(PartialEq,Clone,Default)]
 pub struct Payload {
@@ -29,10 +29,25 @@ pub struct Payload {
     action: ::std::option::Option<Payload_Action>,
     name: ::protobuf::SingularField<::std::string::String>,
     number: ::std::option::Option<u32>,
-    amount: ::std::option::Option<i32>,
+    legacy_amount: ::std::option::Option<i32>,
     new_number: ::std::option::Option<u32>,
     timestamp: ::std::option::Option<i64>,
     public_key: ::protobuf::SingularField<::std::string::String>,
+    recipient_name: ::protobuf::SingularField<::std::string::String>,
+    recipient_number: ::std::option::Option<u32>,
+    pub earn_rule: ::protobuf::SingularPtrField<super::earn_rule::EarnRule>,
+    purchase_amount: ::std::option::Option<u64>,
+    category: ::protobuf::SingularField<::std::string::String>,
+    expires_at: ::std::option::Option<i64>,
+    owner_signature: ::protobuf::SingularField<::std::string::String>,
+    pub catalog_item: ::protobuf::SingularPtrField<super::catalog::CatalogItem>,
+    item_id: ::protobuf::SingularField<::std::string::String>,
+    quantity: ::std::option::Option<u64>,
+    voucher_id: ::protobuf::SingularField<::std::string::String>,
+    discount: ::std::option::Option<u64>,
+    closure_reason: ::protobuf::SingularField<::std::string::String>,
+    currency: ::protobuf::SingularField<::std::string::String>,
+    amount: ::std::option::Option<u64>,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -123,23 +138,23 @@ impl Payload {
         self.number = ::std::option::Option::Some(v);
     }
 
-    // optional sint32 amount = 5;
+    // optional sint32 legacy_amount = 5;
 
 
-    pub fn get_amount(&self) -> i32 {
-        self.amount.unwrap_or(0)
+    pub fn get_legacy_amount(&self) -> i32 {
+        self.legacy_amount.unwrap_or(0)
     }
-    pub fn clear_amount(&mut self) {
-        self.amount = ::std::option::Option::None;
+    pub fn clear_legacy_amount(&mut self) {
+        self.legacy_amount = ::std::option::Option::None;
     }
 
-    pub fn has_amount(&self) -> bool {
-        self.amount.is_some()
+    pub fn has_legacy_amount(&self) -> bool {
+        self.legacy_amount.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_amount(&mut self, v: i32) {
-        self.amount = ::std::option::Option::Some(v);
+    pub fn set_legacy_amount(&mut self, v: i32) {
+        self.legacy_amount = ::std::option::Option::Some(v);
     }
 
     // optional uint32 new_number = 6;
@@ -215,6 +230,438 @@ impl Payload {
     pub fn take_public_key(&mut self) -> ::std::string::String {
         self.public_key.take().unwrap_or_else(|| ::std::string::String::new())
     }
+
+    // optional string recipient_name = 9;
+
+
+    pub fn get_recipient_name(&self) -> &str {
+        match self.recipient_name.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_recipient_name(&mut self) {
+        self.recipient_name.clear();
+    }
+
+    pub fn has_recipient_name(&self) -> bool {
+        self.recipient_name.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_recipient_name(&mut self, v: ::std::string::String) {
+        self.recipient_name = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_recipient_name(&mut self) -> &mut ::std::string::String {
+        if self.recipient_name.is_none() {
+            self.recipient_name.set_default();
+        }
+        self.recipient_name.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_recipient_name(&mut self) -> ::std::string::String {
+        self.recipient_name.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional uint32 recipient_number = 10;
+
+
+    pub fn get_recipient_number(&self) -> u32 {
+        self.recipient_number.unwrap_or(0)
+    }
+    pub fn clear_recipient_number(&mut self) {
+        self.recipient_number = ::std::option::Option::None;
+    }
+
+    pub fn has_recipient_number(&self) -> bool {
+        self.recipient_number.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_recipient_number(&mut self, v: u32) {
+        self.recipient_number = ::std::option::Option::Some(v);
+    }
+
+    // optional .EarnRule earn_rule = 11;
+
+
+    pub fn get_earn_rule(&self) -> &super::earn_rule::EarnRule {
+        self.earn_rule.as_ref().unwrap_or_else(|| <super::earn_rule::EarnRule as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_earn_rule(&mut self) {
+        self.earn_rule.clear();
+    }
+
+    pub fn has_earn_rule(&self) -> bool {
+        self.earn_rule.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_earn_rule(&mut self, v: super::earn_rule::EarnRule) {
+        self.earn_rule = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_earn_rule(&mut self) -> &mut super::earn_rule::EarnRule {
+        if self.earn_rule.is_none() {
+            self.earn_rule.set_default();
+        }
+        self.earn_rule.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_earn_rule(&mut self) -> super::earn_rule::EarnRule {
+        self.earn_rule.take().unwrap_or_else(|| super::earn_rule::EarnRule::new())
+    }
+
+    // optional uint64 purchase_amount = 12;
+
+
+    pub fn get_purchase_amount(&self) -> u64 {
+        self.purchase_amount.unwrap_or(0)
+    }
+    pub fn clear_purchase_amount(&mut self) {
+        self.purchase_amount = ::std::option::Option::None;
+    }
+
+    pub fn has_purchase_amount(&self) -> bool {
+        self.purchase_amount.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_purchase_amount(&mut self, v: u64) {
+        self.purchase_amount = ::std::option::Option::Some(v);
+    }
+
+    // optional string category = 13;
+
+
+    pub fn get_category(&self) -> &str {
+        match self.category.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_category(&mut self) {
+        self.category.clear();
+    }
+
+    pub fn has_category(&self) -> bool {
+        self.category.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_category(&mut self, v: ::std::string::String) {
+        self.category = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_category(&mut self) -> &mut ::std::string::String {
+        if self.category.is_none() {
+            self.category.set_default();
+        }
+        self.category.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_category(&mut self) -> ::std::string::String {
+        self.category.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional sint64 expires_at = 14;
+
+
+    pub fn get_expires_at(&self) -> i64 {
+        self.expires_at.unwrap_or(0)
+    }
+    pub fn clear_expires_at(&mut self) {
+        self.expires_at = ::std::option::Option::None;
+    }
+
+    pub fn has_expires_at(&self) -> bool {
+        self.expires_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expires_at(&mut self, v: i64) {
+        self.expires_at = ::std::option::Option::Some(v);
+    }
+
+    // optional string owner_signature = 15;
+
+
+    pub fn get_owner_signature(&self) -> &str {
+        match self.owner_signature.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_owner_signature(&mut self) {
+        self.owner_signature.clear();
+    }
+
+    pub fn has_owner_signature(&self) -> bool {
+        self.owner_signature.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_owner_signature(&mut self, v: ::std::string::String) {
+        self.owner_signature = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_owner_signature(&mut self) -> &mut ::std::string::String {
+        if self.owner_signature.is_none() {
+            self.owner_signature.set_default();
+        }
+        self.owner_signature.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_owner_signature(&mut self) -> ::std::string::String {
+        self.owner_signature.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional .CatalogItem catalog_item = 16;
+
+
+    pub fn get_catalog_item(&self) -> &super::catalog::CatalogItem {
+        self.catalog_item.as_ref().unwrap_or_else(|| <super::catalog::CatalogItem as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_catalog_item(&mut self) {
+        self.catalog_item.clear();
+    }
+
+    pub fn has_catalog_item(&self) -> bool {
+        self.catalog_item.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_catalog_item(&mut self, v: super::catalog::CatalogItem) {
+        self.catalog_item = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_catalog_item(&mut self) -> &mut super::catalog::CatalogItem {
+        if self.catalog_item.is_none() {
+            self.catalog_item.set_default();
+        }
+        self.catalog_item.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_catalog_item(&mut self) -> super::catalog::CatalogItem {
+        self.catalog_item.take().unwrap_or_else(|| super::catalog::CatalogItem::new())
+    }
+
+    // optional string item_id = 17;
+
+
+    pub fn get_item_id(&self) -> &str {
+        match self.item_id.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_item_id(&mut self) {
+        self.item_id.clear();
+    }
+
+    pub fn has_item_id(&self) -> bool {
+        self.item_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_item_id(&mut self, v: ::std::string::String) {
+        self.item_id = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_item_id(&mut self) -> &mut ::std::string::String {
+        if self.item_id.is_none() {
+            self.item_id.set_default();
+        }
+        self.item_id.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_item_id(&mut self) -> ::std::string::String {
+        self.item_id.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional uint64 quantity = 18;
+
+
+    pub fn get_quantity(&self) -> u64 {
+        self.quantity.unwrap_or(0)
+    }
+    pub fn clear_quantity(&mut self) {
+        self.quantity = ::std::option::Option::None;
+    }
+
+    pub fn has_quantity(&self) -> bool {
+        self.quantity.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_quantity(&mut self, v: u64) {
+        self.quantity = ::std::option::Option::Some(v);
+    }
+
+    // optional string voucher_id = 19;
+
+
+    pub fn get_voucher_id(&self) -> &str {
+        match self.voucher_id.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_voucher_id(&mut self) {
+        self.voucher_id.clear();
+    }
+
+    pub fn has_voucher_id(&self) -> bool {
+        self.voucher_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_voucher_id(&mut self, v: ::std::string::String) {
+        self.voucher_id = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_voucher_id(&mut self) -> &mut ::std::string::String {
+        if self.voucher_id.is_none() {
+            self.voucher_id.set_default();
+        }
+        self.voucher_id.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_voucher_id(&mut self) -> ::std::string::String {
+        self.voucher_id.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional uint64 discount = 20;
+
+
+    pub fn get_discount(&self) -> u64 {
+        self.discount.unwrap_or(0)
+    }
+    pub fn clear_discount(&mut self) {
+        self.discount = ::std::option::Option::None;
+    }
+
+    pub fn has_discount(&self) -> bool {
+        self.discount.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_discount(&mut self, v: u64) {
+        self.discount = ::std::option::Option::Some(v);
+    }
+
+    // optional string closure_reason = 21;
+
+
+    pub fn get_closure_reason(&self) -> &str {
+        match self.closure_reason.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_closure_reason(&mut self) {
+        self.closure_reason.clear();
+    }
+
+    pub fn has_closure_reason(&self) -> bool {
+        self.closure_reason.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_closure_reason(&mut self, v: ::std::string::String) {
+        self.closure_reason = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_closure_reason(&mut self) -> &mut ::std::string::String {
+        if self.closure_reason.is_none() {
+            self.closure_reason.set_default();
+        }
+        self.closure_reason.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_closure_reason(&mut self) -> ::std::string::String {
+        self.closure_reason.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional string currency = 22;
+
+
+    pub fn get_currency(&self) -> &str {
+        match self.currency.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_currency(&mut self) {
+        self.currency.clear();
+    }
+
+    pub fn has_currency(&self) -> bool {
+        self.currency.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_currency(&mut self, v: ::std::string::String) {
+        self.currency = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_currency(&mut self) -> &mut ::std::string::String {
+        if self.currency.is_none() {
+            self.currency.set_default();
+        }
+        self.currency.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_currency(&mut self) -> ::std::string::String {
+        self.currency.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional uint64 amount = 23;
+
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount.unwrap_or(0)
+    }
+    pub fn clear_amount(&mut self) {
+        self.amount = ::std::option::Option::None;
+    }
+
+    pub fn has_amount(&self) -> bool {
+        self.amount.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_amount(&mut self, v: u64) {
+        self.amount = ::std::option::Option::Some(v);
+    }
 }
 
 impl ::protobuf::Message for Payload {
@@ -225,6 +672,16 @@ impl ::protobuf::Message for Payload {
         if self.name.is_none() {
             return false;
         }
+        for v in &self.earn_rule {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        for v in &self.catalog_item {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -250,7 +707,7 @@ impl ::protobuf::Message for Payload {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_sint32()?;
-                    self.amount = ::std::option::Option::Some(tmp);
+                    self.legacy_amount = ::std::option::Option::Some(tmp);
                 },
                 6 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
@@ -269,6 +726,75 @@ impl ::protobuf::Message for Payload {
                 8 => {
                     ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.public_key)?;
                 },
+                9 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.recipient_name)?;
+                },
+                10 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.recipient_number = ::std::option::Option::Some(tmp);
+                },
+                11 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.earn_rule)?;
+                },
+                12 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.purchase_amount = ::std::option::Option::Some(tmp);
+                },
+                13 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.category)?;
+                },
+                14 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.expires_at = ::std::option::Option::Some(tmp);
+                },
+                15 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.owner_signature)?;
+                },
+                16 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.catalog_item)?;
+                },
+                17 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.item_id)?;
+                },
+                18 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.quantity = ::std::option::Option::Some(tmp);
+                },
+                19 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.voucher_id)?;
+                },
+                20 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.discount = ::std::option::Option::Some(tmp);
+                },
+                21 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.closure_reason)?;
+                },
+                22 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.currency)?;
+                },
+                23 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.amount = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -290,7 +816,7 @@ impl ::protobuf::Message for Payload {
         if let Some(v) = self.number {
             my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
         }
-        if let Some(v) = self.amount {
+        if let Some(v) = self.legacy_amount {
             my_size += ::protobuf::rt::value_varint_zigzag_size(5, v);
         }
         if let Some(v) = self.new_number {
@@ -302,6 +828,53 @@ impl ::protobuf::Message for Payload {
         if let Some(ref v) = self.public_key.as_ref() {
             my_size += ::protobuf::rt::string_size(8, &v);
         }
+        if let Some(ref v) = self.recipient_name.as_ref() {
+            my_size += ::protobuf::rt::string_size(9, &v);
+        }
+        if let Some(v) = self.recipient_number {
+            my_size += ::protobuf::rt::value_size(10, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.earn_rule.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(v) = self.purchase_amount {
+            my_size += ::protobuf::rt::value_size(12, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.category.as_ref() {
+            my_size += ::protobuf::rt::string_size(13, &v);
+        }
+        if let Some(v) = self.expires_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(14, v);
+        }
+        if let Some(ref v) = self.owner_signature.as_ref() {
+            my_size += ::protobuf::rt::string_size(15, &v);
+        }
+        if let Some(ref v) = self.catalog_item.as_ref() {
+            let len = v.compute_size();
+            my_size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if let Some(ref v) = self.item_id.as_ref() {
+            my_size += ::protobuf::rt::string_size(17, &v);
+        }
+        if let Some(v) = self.quantity {
+            my_size += ::protobuf::rt::value_size(18, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.voucher_id.as_ref() {
+            my_size += ::protobuf::rt::string_size(19, &v);
+        }
+        if let Some(v) = self.discount {
+            my_size += ::protobuf::rt::value_size(20, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.closure_reason.as_ref() {
+            my_size += ::protobuf::rt::string_size(21, &v);
+        }
+        if let Some(ref v) = self.currency.as_ref() {
+            my_size += ::protobuf::rt::string_size(22, &v);
+        }
+        if let Some(v) = self.amount {
+            my_size += ::protobuf::rt::value_size(23, v, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -317,7 +890,7 @@ impl ::protobuf::Message for Payload {
         if let Some(v) = self.number {
             os.write_uint32(3, v)?;
         }
-        if let Some(v) = self.amount {
+        if let Some(v) = self.legacy_amount {
             os.write_sint32(5, v)?;
         }
         if let Some(v) = self.new_number {
@@ -329,6 +902,55 @@ impl ::protobuf::Message for Payload {
         if let Some(ref v) = self.public_key.as_ref() {
             os.write_string(8, &v)?;
         }
+        if let Some(ref v) = self.recipient_name.as_ref() {
+            os.write_string(9, &v)?;
+        }
+        if let Some(v) = self.recipient_number {
+            os.write_uint32(10, v)?;
+        }
+        if let Some(ref v) = self.earn_rule.as_ref() {
+            os.write_tag(11, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(v) = self.purchase_amount {
+            os.write_uint64(12, v)?;
+        }
+        if let Some(ref v) = self.category.as_ref() {
+            os.write_string(13, &v)?;
+        }
+        if let Some(v) = self.expires_at {
+            os.write_sint64(14, v)?;
+        }
+        if let Some(ref v) = self.owner_signature.as_ref() {
+            os.write_string(15, &v)?;
+        }
+        if let Some(ref v) = self.catalog_item.as_ref() {
+            os.write_tag(16, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if let Some(ref v) = self.item_id.as_ref() {
+            os.write_string(17, &v)?;
+        }
+        if let Some(v) = self.quantity {
+            os.write_uint64(18, v)?;
+        }
+        if let Some(ref v) = self.voucher_id.as_ref() {
+            os.write_string(19, &v)?;
+        }
+        if let Some(v) = self.discount {
+            os.write_uint64(20, v)?;
+        }
+        if let Some(ref v) = self.closure_reason.as_ref() {
+            os.write_string(21, &v)?;
+        }
+        if let Some(ref v) = self.currency.as_ref() {
+            os.write_string(22, &v)?;
+        }
+        if let Some(v) = self.amount {
+            os.write_uint64(23, v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -383,9 +1005,9 @@ impl ::protobuf::Message for Payload {
                 |m: &mut Payload| { &mut m.number },
             ));
             fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint32>(
-                "amount",
-                |m: &Payload| { &m.amount },
-                |m: &mut Payload| { &mut m.amount },
+                "legacy_amount",
+                |m: &Payload| { &m.legacy_amount },
+                |m: &mut Payload| { &mut m.legacy_amount },
             ));
             fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
                 "new_number",
@@ -402,6 +1024,81 @@ impl ::protobuf::Message for Payload {
                 |m: &Payload| { &m.public_key },
                 |m: &mut Payload| { &mut m.public_key },
             ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "recipient_name",
+                |m: &Payload| { &m.recipient_name },
+                |m: &mut Payload| { &mut m.recipient_name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "recipient_number",
+                |m: &Payload| { &m.recipient_number },
+                |m: &mut Payload| { &mut m.recipient_number },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<super::earn_rule::EarnRule>>(
+                "earn_rule",
+                |m: &Payload| { &m.earn_rule },
+                |m: &mut Payload| { &mut m.earn_rule },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "purchase_amount",
+                |m: &Payload| { &m.purchase_amount },
+                |m: &mut Payload| { &mut m.purchase_amount },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "category",
+                |m: &Payload| { &m.category },
+                |m: &mut Payload| { &mut m.category },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "expires_at",
+                |m: &Payload| { &m.expires_at },
+                |m: &mut Payload| { &mut m.expires_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "owner_signature",
+                |m: &Payload| { &m.owner_signature },
+                |m: &mut Payload| { &mut m.owner_signature },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<super::catalog::CatalogItem>>(
+                "catalog_item",
+                |m: &Payload| { &m.catalog_item },
+                |m: &mut Payload| { &mut m.catalog_item },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "item_id",
+                |m: &Payload| { &m.item_id },
+                |m: &mut Payload| { &mut m.item_id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "quantity",
+                |m: &Payload| { &m.quantity },
+                |m: &mut Payload| { &mut m.quantity },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "voucher_id",
+                |m: &Payload| { &m.voucher_id },
+                |m: &mut Payload| { &mut m.voucher_id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "discount",
+                |m: &Payload| { &m.discount },
+                |m: &mut Payload| { &mut m.discount },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "closure_reason",
+                |m: &Payload| { &m.closure_reason },
+                |m: &mut Payload| { &mut m.closure_reason },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "currency",
+                |m: &Payload| { &m.currency },
+                |m: &mut Payload| { &mut m.currency },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "amount",
+                |m: &Payload| { &m.amount },
+                |m: &mut Payload| { &mut m.amount },
+            ));
             ::protobuf::reflect::MessageDescriptor::new_pb_name::<Payload>(
                 "Payload",
                 fields,
@@ -421,10 +1118,25 @@ impl ::protobuf::Clear for Payload {
         self.action = ::std::option::Option::None;
         self.name.clear();
         self.number = ::std::option::Option::None;
-        self.amount = ::std::option::Option::None;
+        self.legacy_amount = ::std::option::Option::None;
         self.new_number = ::std::option::Option::None;
         self.timestamp = ::std::option::Option::None;
         self.public_key.clear();
+        self.recipient_name.clear();
+        self.recipient_number = ::std::option::Option::None;
+        self.earn_rule.clear();
+        self.purchase_amount = ::std::option::Option::None;
+        self.category.clear();
+        self.expires_at = ::std::option::Option::None;
+        self.owner_signature.clear();
+        self.catalog_item.clear();
+        self.item_id.clear();
+        self.quantity = ::std::option::Option::None;
+        self.voucher_id.clear();
+        self.discount = ::std::option::Option::None;
+        self.closure_reason.clear();
+        self.currency.clear();
+        self.amount = ::std::option::Option::None;
         self.unknown_fields.clear();
     }
 }
@@ -448,6 +1160,18 @@ pub enum Payload_Action {
     UPDATE_NUMBER = 2,
     ADD_ACCOUNT = 3,
     ADD_MERCHANT = 4,
+    TRANSFER = 5,
+    SET_EARN_RULE = 6,
+    EARN = 7,
+    EXPIRE = 8,
+    SET_CATALOG_ITEM = 9,
+    REDEEM = 10,
+    ISSUE_VOUCHER = 11,
+    CONSUME_VOUCHER = 12,
+    FREEZE = 13,
+    UNFREEZE = 14,
+    CLOSE = 15,
+    ROTATE_MERCHANT_KEY = 16,
 }
 
 impl ::protobuf::ProtobufEnum for Payload_Action {
@@ -462,6 +1186,18 @@ impl ::protobuf::ProtobufEnum for Payload_Action {
             2 => ::std::option::Option::Some(Payload_Action::UPDATE_NUMBER),
             3 => ::std::option::Option::Some(Payload_Action::ADD_ACCOUNT),
             4 => ::std::option::Option::Some(Payload_Action::ADD_MERCHANT),
+            5 => ::std::option::Option::Some(Payload_Action::TRANSFER),
+            6 => ::std::option::Option::Some(Payload_Action::SET_EARN_RULE),
+            7 => ::std::option::Option::Some(Payload_Action::EARN),
+            8 => ::std::option::Option::Some(Payload_Action::EXPIRE),
+            9 => ::std::option::Option::Some(Payload_Action::SET_CATALOG_ITEM),
+            10 => ::std::option::Option::Some(Payload_Action::REDEEM),
+            11 => ::std::option::Option::Some(Payload_Action::ISSUE_VOUCHER),
+            12 => ::std::option::Option::Some(Payload_Action::CONSUME_VOUCHER),
+            13 => ::std::option::Option::Some(Payload_Action::FREEZE),
+            14 => ::std::option::Option::Some(Payload_Action::UNFREEZE),
+            15 => ::std::option::Option::Some(Payload_Action::CLOSE),
+            16 => ::std::option::Option::Some(Payload_Action::ROTATE_MERCHANT_KEY),
             _ => ::std::option::Option::None
         }
     }
@@ -473,6 +1209,18 @@ impl ::protobuf::ProtobufEnum for Payload_Action {
             Payload_Action::UPDATE_NUMBER,
             Payload_Action::ADD_ACCOUNT,
             Payload_Action::ADD_MERCHANT,
+            Payload_Action::TRANSFER,
+            Payload_Action::SET_EARN_RULE,
+            Payload_Action::EARN,
+            Payload_Action::EXPIRE,
+            Payload_Action::SET_CATALOG_ITEM,
+            Payload_Action::REDEEM,
+            Payload_Action::ISSUE_VOUCHER,
+            Payload_Action::CONSUME_VOUCHER,
+            Payload_Action::FREEZE,
+            Payload_Action::UNFREEZE,
+            Payload_Action::CLOSE,
+            Payload_Action::ROTATE_MERCHANT_KEY,
         ];
         values
     }
@@ -500,22 +1248,209 @@ impl ::protobuf::reflect::ProtobufValue for Payload_Action {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct PayloadEnvelope {
+    // message fields
+    pub payloads: ::protobuf::RepeatedField<Payload>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PayloadEnvelope {
+    fn default() -> &'a PayloadEnvelope {
+        <PayloadEnvelope as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PayloadEnvelope {
+    pub fn new() -> PayloadEnvelope {
+        ::std::default::Default::default()
+    }
+
+    // repeated .Payload payloads = 1;
+
+
+    pub fn get_payloads(&self) -> &[Payload] {
+        &self.payloads
+    }
+    pub fn clear_payloads(&mut self) {
+        self.payloads.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_payloads(&mut self, v: ::protobuf::RepeatedField<Payload>) {
+        self.payloads = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_payloads(&mut self) -> &mut ::protobuf::RepeatedField<Payload> {
+        &mut self.payloads
+    }
+
+    // Take field
+    pub fn take_payloads(&mut self) -> ::protobuf::RepeatedField<Payload> {
+        ::std::mem::replace(&mut self.payloads, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for PayloadEnvelope {
+    fn is_initialized(&self) -> bool {
+        for v in &self.payloads {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.payloads)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.payloads {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.payloads {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> PayloadEnvelope {
+        PayloadEnvelope::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Payload>>(
+                "payloads",
+                |m: &PayloadEnvelope| { &m.payloads },
+                |m: &mut PayloadEnvelope| { &mut m.payloads },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<PayloadEnvelope>(
+                "PayloadEnvelope",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static PayloadEnvelope {
+        static instance: ::protobuf::rt::LazyV2<PayloadEnvelope> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(PayloadEnvelope::new)
+    }
+}
+
+impl ::protobuf::Clear for PayloadEnvelope {
+    fn clear(&mut self) {
+        self.payloads.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for PayloadEnvelope {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PayloadEnvelope {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\rpayload.proto\"\xbf\x02\n\x07Payload\x12)\n\x06action\x18\x01\x20\
-    \x02(\x0e2\x0f.Payload.ActionR\x06actionB\0\x12\x14\n\x04name\x18\x02\
-    \x20\x02(\tR\x04nameB\0\x12\x18\n\x06number\x18\x03\x20\x01(\rR\x06numbe\
-    rB\0\x12\x18\n\x06amount\x18\x05\x20\x01(\x11R\x06amountB\0\x12\x1f\n\nn\
-    ew_number\x18\x06\x20\x01(\rR\tnewNumberB\0\x12\x1e\n\ttimestamp\x18\x07\
-    \x20\x01(\x12R\ttimestampB\0\x12\x1f\n\npublic_key\x18\x08\x20\x01(\tR\t\
-    publicKeyB\0\"[\n\x06Action\x12\x0b\n\x07DEPOSIT\x10\0\x12\x0c\n\x08WITH\
-    DRAW\x10\x01\x12\x11\n\rUPDATE_NUMBER\x10\x02\x12\x0f\n\x0bADD_ACCOUNT\
-    \x10\x03\x12\x10\n\x0cADD_MERCHANT\x10\x04\x1a\0:\0B\0b\x06proto2\
+    \n\rpayload.proto\x1a\rcatalog.proto\x1a\x0fearn_rule.proto\"\xad\x08\n\
+    \x07Payload\x12)\n\x06action\x18\x01\x20\x02(\x0e2\x0f.Payload.ActionR\
+    \x06actionB\0\x12\x14\n\x04name\x18\x02\x20\x02(\tR\x04nameB\0\x12\x18\n\
+    \x06number\x18\x03\x20\x01(\rR\x06numberB\0\x12%\n\rlegacy_amount\x18\
+    \x05\x20\x01(\x11R\x0clegacyAmountB\0\x12\x1f\n\nnew_number\x18\x06\x20\
+    \x01(\rR\tnewNumberB\0\x12\x1e\n\ttimestamp\x18\x07\x20\x01(\x12R\ttimes\
+    tampB\0\x12\x1f\n\npublic_key\x18\x08\x20\x01(\tR\tpublicKeyB\0\x12'\n\
+    \x0erecipient_name\x18\t\x20\x01(\tR\rrecipientNameB\0\x12+\n\x10recipie\
+    nt_number\x18\n\x20\x01(\rR\x0frecipientNumberB\0\x12(\n\tearn_rule\x18\
+    \x0b\x20\x01(\x0b2\t.EarnRuleR\x08earnRuleB\0\x12)\n\x0fpurchase_amount\
+    \x18\x0c\x20\x01(\x04R\x0epurchaseAmountB\0\x12\x1c\n\x08category\x18\r\
+    \x20\x01(\tR\x08categoryB\0\x12\x1f\n\nexpires_at\x18\x0e\x20\x01(\x12R\
+    \texpiresAtB\0\x12)\n\x0fowner_signature\x18\x0f\x20\x01(\tR\x0eownerSig\
+    natureB\0\x121\n\x0ccatalog_item\x18\x10\x20\x01(\x0b2\x0c.CatalogItemR\
+    \x0bcatalogItemB\0\x12\x19\n\x07item_id\x18\x11\x20\x01(\tR\x06itemIdB\0\
+    \x12\x1c\n\x08quantity\x18\x12\x20\x01(\x04R\x08quantityB\0\x12\x1f\n\nv\
+    oucher_id\x18\x13\x20\x01(\tR\tvoucherIdB\0\x12\x1c\n\x08discount\x18\
+    \x14\x20\x01(\x04R\x08discountB\0\x12'\n\x0eclosure_reason\x18\x15\x20\
+    \x01(\tR\rclosureReasonB\0\x12\x1c\n\x08currency\x18\x16\x20\x01(\tR\x08\
+    currencyB\0\x12\x18\n\x06amount\x18\x17\x20\x01(\x04R\x06amountB\0\"\x9a\
+    \x02\n\x06Action\x12\x0b\n\x07DEPOSIT\x10\0\x12\x0c\n\x08WITHDRAW\x10\
+    \x01\x12\x11\n\rUPDATE_NUMBER\x10\x02\x12\x0f\n\x0bADD_ACCOUNT\x10\x03\
+    \x12\x10\n\x0cADD_MERCHANT\x10\x04\x12\x0c\n\x08TRANSFER\x10\x05\x12\x11\
+    \n\rSET_EARN_RULE\x10\x06\x12\x08\n\x04EARN\x10\x07\x12\n\n\x06EXPIRE\
+    \x10\x08\x12\x14\n\x10SET_CATALOG_ITEM\x10\t\x12\n\n\x06REDEEM\x10\n\x12\
+    \x11\n\rISSUE_VOUCHER\x10\x0b\x12\x13\n\x0fCONSUME_VOUCHER\x10\x0c\x12\n\
+    \n\x06FREEZE\x10\r\x12\x0c\n\x08UNFREEZE\x10\x0e\x12\t\n\x05CLOSE\x10\
+    \x0f\x12\x17\n\x13ROTATE_MERCHANT_KEY\x10\x10\x1a\0:\0\";\n\x0fPayloadEn\
+    velope\x12&\n\x08payloads\x18\x01\x20\x03(\x0b2\x08.PayloadR\x08payloads\
+    B\0:\0B\0b\x06proto2\
 ";
 
 static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
 
 fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
-    ::protobuf::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
 }
 
 pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
@@ -0,0 +1,526 @@
+// This file is generated by rust-protobuf 2.28.0. Do not edit
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+//! Generated file from `receipt.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Receipt {
+    // message fields
+    name: ::protobuf::SingularField<::std::string::String>,
+    number: ::std::option::Option<u32>,
+    balance: ::std::option::Option<u64>,
+    pub lot_ids: ::std::vec::Vec<u64>,
+    points: ::std::option::Option<u64>,
+    currency: ::protobuf::SingularField<::std::string::String>,
+    tier: ::protobuf::SingularField<::std::string::String>,
+    tier_changed: ::std::option::Option<bool>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Receipt {
+    fn default() -> &'a Receipt {
+        <Receipt as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Receipt {
+    pub fn new() -> Receipt {
+        ::std::default::Default::default()
+    }
+
+    // required string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        match self.name.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    pub fn has_name(&self) -> bool {
+        self.name.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        if self.name.is_none() {
+            self.name.set_default();
+        }
+        self.name.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        self.name.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required uint32 number = 2;
+
+
+    pub fn get_number(&self) -> u32 {
+        self.number.unwrap_or(0)
+    }
+    pub fn clear_number(&mut self) {
+        self.number = ::std::option::Option::None;
+    }
+
+    pub fn has_number(&self) -> bool {
+        self.number.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_number(&mut self, v: u32) {
+        self.number = ::std::option::Option::Some(v);
+    }
+
+    // required uint64 balance = 3;
+
+
+    pub fn get_balance(&self) -> u64 {
+        self.balance.unwrap_or(0)
+    }
+    pub fn clear_balance(&mut self) {
+        self.balance = ::std::option::Option::None;
+    }
+
+    pub fn has_balance(&self) -> bool {
+        self.balance.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_balance(&mut self, v: u64) {
+        self.balance = ::std::option::Option::Some(v);
+    }
+
+    // repeated uint64 lot_ids = 4;
+
+
+    pub fn get_lot_ids(&self) -> &[u64] {
+        &self.lot_ids
+    }
+    pub fn clear_lot_ids(&mut self) {
+        self.lot_ids.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_lot_ids(&mut self, v: ::std::vec::Vec<u64>) {
+        self.lot_ids = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_lot_ids(&mut self) -> &mut ::std::vec::Vec<u64> {
+        &mut self.lot_ids
+    }
+
+    // Take field
+    pub fn take_lot_ids(&mut self) -> ::std::vec::Vec<u64> {
+        ::std::mem::replace(&mut self.lot_ids, ::std::vec::Vec::new())
+    }
+
+    // optional uint64 points = 5;
+
+
+    pub fn get_points(&self) -> u64 {
+        self.points.unwrap_or(0)
+    }
+    pub fn clear_points(&mut self) {
+        self.points = ::std::option::Option::None;
+    }
+
+    pub fn has_points(&self) -> bool {
+        self.points.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_points(&mut self, v: u64) {
+        self.points = ::std::option::Option::Some(v);
+    }
+
+    // optional string currency = 6;
+
+
+    pub fn get_currency(&self) -> &str {
+        match self.currency.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_currency(&mut self) {
+        self.currency.clear();
+    }
+
+    pub fn has_currency(&self) -> bool {
+        self.currency.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_currency(&mut self, v: ::std::string::String) {
+        self.currency = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_currency(&mut self) -> &mut ::std::string::String {
+        if self.currency.is_none() {
+            self.currency.set_default();
+        }
+        self.currency.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_currency(&mut self) -> ::std::string::String {
+        self.currency.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional string tier = 7;
+
+
+    pub fn get_tier(&self) -> &str {
+        match self.tier.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_tier(&mut self) {
+        self.tier.clear();
+    }
+
+    pub fn has_tier(&self) -> bool {
+        self.tier.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tier(&mut self, v: ::std::string::String) {
+        self.tier = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_tier(&mut self) -> &mut ::std::string::String {
+        if self.tier.is_none() {
+            self.tier.set_default();
+        }
+        self.tier.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_tier(&mut self) -> ::std::string::String {
+        self.tier.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // optional bool tier_changed = 8;
+
+
+    pub fn get_tier_changed(&self) -> bool {
+        self.tier_changed.unwrap_or(false)
+    }
+    pub fn clear_tier_changed(&mut self) {
+        self.tier_changed = ::std::option::Option::None;
+    }
+
+    pub fn has_tier_changed(&self) -> bool {
+        self.tier_changed.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tier_changed(&mut self, v: bool) {
+        self.tier_changed = ::std::option::Option::Some(v);
+    }
+}
+
+impl ::protobuf::Message for Receipt {
+    fn is_initialized(&self) -> bool {
+        if self.name.is_none() {
+            return false;
+        }
+        if self.number.is_none() {
+            return false;
+        }
+        if self.balance.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.number = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.balance = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    ::protobuf::rt::read_repeated_uint64_into(wire_type, is, &mut self.lot_ids)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.points = ::std::option::Option::Some(tmp);
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.currency)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.tier)?;
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.tier_changed = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.name.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(v) = self.number {
+            my_size += ::protobuf::rt::value_size(2, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.balance {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        for value in &self.lot_ids {
+            my_size += ::protobuf::rt::value_size(4, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if let Some(v) = self.points {
+            my_size += ::protobuf::rt::value_size(5, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.currency.as_ref() {
+            my_size += ::protobuf::rt::string_size(6, &v);
+        }
+        if let Some(ref v) = self.tier.as_ref() {
+            my_size += ::protobuf::rt::string_size(7, &v);
+        }
+        if let Some(v) = self.tier_changed {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.name.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(v) = self.number {
+            os.write_uint32(2, v)?;
+        }
+        if let Some(v) = self.balance {
+            os.write_uint64(3, v)?;
+        }
+        for v in &self.lot_ids {
+            os.write_uint64(4, *v)?;
+        };
+        if let Some(v) = self.points {
+            os.write_uint64(5, v)?;
+        }
+        if let Some(ref v) = self.currency.as_ref() {
+            os.write_string(6, &v)?;
+        }
+        if let Some(ref v) = self.tier.as_ref() {
+            os.write_string(7, &v)?;
+        }
+        if let Some(v) = self.tier_changed {
+            os.write_bool(8, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Receipt {
+        Receipt::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "name",
+                |m: &Receipt| { &m.name },
+                |m: &mut Receipt| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "number",
+                |m: &Receipt| { &m.number },
+                |m: &mut Receipt| { &mut m.number },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "balance",
+                |m: &Receipt| { &m.balance },
+                |m: &mut Receipt| { &mut m.balance },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_vec_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "lot_ids",
+                |m: &Receipt| { &m.lot_ids },
+                |m: &mut Receipt| { &mut m.lot_ids },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "points",
+                |m: &Receipt| { &m.points },
+                |m: &mut Receipt| { &mut m.points },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "currency",
+                |m: &Receipt| { &m.currency },
+                |m: &mut Receipt| { &mut m.currency },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "tier",
+                |m: &Receipt| { &m.tier },
+                |m: &mut Receipt| { &mut m.tier },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "tier_changed",
+                |m: &Receipt| { &m.tier_changed },
+                |m: &mut Receipt| { &mut m.tier_changed },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Receipt>(
+                "Receipt",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Receipt {
+        static instance: ::protobuf::rt::LazyV2<Receipt> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Receipt::new)
+    }
+}
+
+impl ::protobuf::Clear for Receipt {
+    fn clear(&mut self) {
+        self.name.clear();
+        self.number = ::std::option::Option::None;
+        self.balance = ::std::option::Option::None;
+        self.lot_ids.clear();
+        self.points = ::std::option::Option::None;
+        self.currency.clear();
+        self.tier.clear();
+        self.tier_changed = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Receipt {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Receipt {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\rreceipt.proto\"\xe5\x01\n\x07Receipt\x12\x14\n\x04name\x18\x01\x20\
+    \x02(\tR\x04nameB\0\x12\x18\n\x06number\x18\x02\x20\x02(\rR\x06numberB\0\
+    \x12\x1a\n\x07balance\x18\x03\x20\x02(\x04R\x07balanceB\0\x12\x19\n\x07l\
+    ot_ids\x18\x04\x20\x03(\x04R\x06lotIdsB\0\x12\x18\n\x06points\x18\x05\
+    \x20\x01(\x04R\x06pointsB\0\x12\x1c\n\x08currency\x18\x06\x20\x01(\tR\
+    \x08currencyB\0\x12\x14\n\x04tier\x18\x07\x20\x01(\tR\x04tierB\0\x12#\n\
+    \x0ctier_changed\x18\x08\x20\x01(\x08R\x0btierChangedB\0:\0B\0b\x06proto\
+    2\
+";
+
+static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    file_descriptor_proto_lazy.get(|| {
+        parse_descriptor_proto()
+    })
+}
@@ -0,0 +1,522 @@
+// This file is generated by rust-protobuf 2.28.0. Do not edit
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+//! Generated file from `settlement.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Settlement {
+    // message fields
+    issuer: ::protobuf::SingularField<::std::string::String>,
+    redeemer: ::protobuf::SingularField<::std::string::String>,
+    period_start: ::std::option::Option<i64>,
+    amount: ::std::option::Option<u64>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Settlement {
+    fn default() -> &'a Settlement {
+        <Settlement as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Settlement {
+    pub fn new() -> Settlement {
+        ::std::default::Default::default()
+    }
+
+    // required string issuer = 1;
+
+
+    pub fn get_issuer(&self) -> &str {
+        match self.issuer.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_issuer(&mut self) {
+        self.issuer.clear();
+    }
+
+    pub fn has_issuer(&self) -> bool {
+        self.issuer.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_issuer(&mut self, v: ::std::string::String) {
+        self.issuer = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_issuer(&mut self) -> &mut ::std::string::String {
+        if self.issuer.is_none() {
+            self.issuer.set_default();
+        }
+        self.issuer.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_issuer(&mut self) -> ::std::string::String {
+        self.issuer.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required string redeemer = 2;
+
+
+    pub fn get_redeemer(&self) -> &str {
+        match self.redeemer.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_redeemer(&mut self) {
+        self.redeemer.clear();
+    }
+
+    pub fn has_redeemer(&self) -> bool {
+        self.redeemer.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_redeemer(&mut self, v: ::std::string::String) {
+        self.redeemer = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_redeemer(&mut self) -> &mut ::std::string::String {
+        if self.redeemer.is_none() {
+            self.redeemer.set_default();
+        }
+        self.redeemer.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_redeemer(&mut self) -> ::std::string::String {
+        self.redeemer.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required sint64 period_start = 3;
+
+
+    pub fn get_period_start(&self) -> i64 {
+        self.period_start.unwrap_or(0)
+    }
+    pub fn clear_period_start(&mut self) {
+        self.period_start = ::std::option::Option::None;
+    }
+
+    pub fn has_period_start(&self) -> bool {
+        self.period_start.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_period_start(&mut self, v: i64) {
+        self.period_start = ::std::option::Option::Some(v);
+    }
+
+    // required uint64 amount = 4;
+
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount.unwrap_or(0)
+    }
+    pub fn clear_amount(&mut self) {
+        self.amount = ::std::option::Option::None;
+    }
+
+    pub fn has_amount(&self) -> bool {
+        self.amount.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_amount(&mut self, v: u64) {
+        self.amount = ::std::option::Option::Some(v);
+    }
+}
+
+impl ::protobuf::Message for Settlement {
+    fn is_initialized(&self) -> bool {
+        if self.issuer.is_none() {
+            return false;
+        }
+        if self.redeemer.is_none() {
+            return false;
+        }
+        if self.period_start.is_none() {
+            return false;
+        }
+        if self.amount.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.issuer)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.redeemer)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.period_start = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.amount = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.issuer.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(ref v) = self.redeemer.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        }
+        if let Some(v) = self.period_start {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(3, v);
+        }
+        if let Some(v) = self.amount {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.issuer.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(ref v) = self.redeemer.as_ref() {
+            os.write_string(2, &v)?;
+        }
+        if let Some(v) = self.period_start {
+            os.write_sint64(3, v)?;
+        }
+        if let Some(v) = self.amount {
+            os.write_uint64(4, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Settlement {
+        Settlement::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "issuer",
+                |m: &Settlement| { &m.issuer },
+                |m: &mut Settlement| { &mut m.issuer },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "redeemer",
+                |m: &Settlement| { &m.redeemer },
+                |m: &mut Settlement| { &mut m.redeemer },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "period_start",
+                |m: &Settlement| { &m.period_start },
+                |m: &mut Settlement| { &mut m.period_start },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "amount",
+                |m: &Settlement| { &m.amount },
+                |m: &mut Settlement| { &mut m.amount },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Settlement>(
+                "Settlement",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Settlement {
+        static instance: ::protobuf::rt::LazyV2<Settlement> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Settlement::new)
+    }
+}
+
+impl ::protobuf::Clear for Settlement {
+    fn clear(&mut self) {
+        self.issuer.clear();
+        self.redeemer.clear();
+        self.period_start = ::std::option::Option::None;
+        self.amount = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Settlement {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Settlement {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct SettlementContainer {
+    // message fields
+    pub entries: ::protobuf::RepeatedField<Settlement>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a SettlementContainer {
+    fn default() -> &'a SettlementContainer {
+        <SettlementContainer as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SettlementContainer {
+    pub fn new() -> SettlementContainer {
+        ::std::default::Default::default()
+    }
+
+    // repeated .Settlement entries = 1;
+
+
+    pub fn get_entries(&self) -> &[Settlement] {
+        &self.entries
+    }
+    pub fn clear_entries(&mut self) {
+        self.entries.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_entries(&mut self, v: ::protobuf::RepeatedField<Settlement>) {
+        self.entries = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_entries(&mut self) -> &mut ::protobuf::RepeatedField<Settlement> {
+        &mut self.entries
+    }
+
+    // Take field
+    pub fn take_entries(&mut self) -> ::protobuf::RepeatedField<Settlement> {
+        ::std::mem::replace(&mut self.entries, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for SettlementContainer {
+    fn is_initialized(&self) -> bool {
+        for v in &self.entries {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.entries)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.entries {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.entries {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> SettlementContainer {
+        SettlementContainer::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Settlement>>(
+                "entries",
+                |m: &SettlementContainer| { &m.entries },
+                |m: &mut SettlementContainer| { &mut m.entries },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<SettlementContainer>(
+                "SettlementContainer",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static SettlementContainer {
+        static instance: ::protobuf::rt::LazyV2<SettlementContainer> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(SettlementContainer::new)
+    }
+}
+
+impl ::protobuf::Clear for SettlementContainer {
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for SettlementContainer {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SettlementContainer {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\x10settlement.proto\"\x85\x01\n\nSettlement\x12\x18\n\x06issuer\x18\
+    \x01\x20\x02(\tR\x06issuerB\0\x12\x1c\n\x08redeemer\x18\x02\x20\x02(\tR\
+    \x08redeemerB\0\x12#\n\x0cperiod_start\x18\x03\x20\x02(\x12R\x0bperiodSt\
+    artB\0\x12\x18\n\x06amount\x18\x04\x20\x02(\x04R\x06amountB\0:\0\"@\n\
+    \x13SettlementContainer\x12'\n\x07entries\x18\x01\x20\x03(\x0b2\x0b.Sett\
+    lementR\x07entriesB\0:\0B\0b\x06proto2\
+";
+
+static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    file_descriptor_proto_lazy.get(|| {
+        parse_descriptor_proto()
+    })
+}
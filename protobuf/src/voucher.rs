@@ -0,0 +1,703 @@
+// This file is generated by rust-protobuf 2.28.0. Do not edit
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+//! Generated file from `voucher.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+// const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_2_28_0;
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Voucher {
+    // message fields
+    public_key: ::protobuf::SingularField<::std::string::String>,
+    id: ::protobuf::SingularField<::std::string::String>,
+    name: ::protobuf::SingularField<::std::string::String>,
+    number: ::std::option::Option<u32>,
+    discount: ::std::option::Option<u64>,
+    issued_at: ::std::option::Option<i64>,
+    expires_at: ::std::option::Option<i64>,
+    consumed_at: ::std::option::Option<i64>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Voucher {
+    fn default() -> &'a Voucher {
+        <Voucher as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Voucher {
+    pub fn new() -> Voucher {
+        ::std::default::Default::default()
+    }
+
+    // required string public_key = 1;
+
+
+    pub fn get_public_key(&self) -> &str {
+        match self.public_key.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_public_key(&mut self) {
+        self.public_key.clear();
+    }
+
+    pub fn has_public_key(&self) -> bool {
+        self.public_key.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_public_key(&mut self, v: ::std::string::String) {
+        self.public_key = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_public_key(&mut self) -> &mut ::std::string::String {
+        if self.public_key.is_none() {
+            self.public_key.set_default();
+        }
+        self.public_key.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_public_key(&mut self) -> ::std::string::String {
+        self.public_key.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required string id = 2;
+
+
+    pub fn get_id(&self) -> &str {
+        match self.id.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_id(&mut self) {
+        self.id.clear();
+    }
+
+    pub fn has_id(&self) -> bool {
+        self.id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_id(&mut self, v: ::std::string::String) {
+        self.id = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_id(&mut self) -> &mut ::std::string::String {
+        if self.id.is_none() {
+            self.id.set_default();
+        }
+        self.id.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_id(&mut self) -> ::std::string::String {
+        self.id.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required string name = 3;
+
+
+    pub fn get_name(&self) -> &str {
+        match self.name.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    pub fn has_name(&self) -> bool {
+        self.name.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        if self.name.is_none() {
+            self.name.set_default();
+        }
+        self.name.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        self.name.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    // required uint32 number = 4;
+
+
+    pub fn get_number(&self) -> u32 {
+        self.number.unwrap_or(0)
+    }
+    pub fn clear_number(&mut self) {
+        self.number = ::std::option::Option::None;
+    }
+
+    pub fn has_number(&self) -> bool {
+        self.number.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_number(&mut self, v: u32) {
+        self.number = ::std::option::Option::Some(v);
+    }
+
+    // required uint64 discount = 5;
+
+
+    pub fn get_discount(&self) -> u64 {
+        self.discount.unwrap_or(0)
+    }
+    pub fn clear_discount(&mut self) {
+        self.discount = ::std::option::Option::None;
+    }
+
+    pub fn has_discount(&self) -> bool {
+        self.discount.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_discount(&mut self, v: u64) {
+        self.discount = ::std::option::Option::Some(v);
+    }
+
+    // required sint64 issued_at = 6;
+
+
+    pub fn get_issued_at(&self) -> i64 {
+        self.issued_at.unwrap_or(0)
+    }
+    pub fn clear_issued_at(&mut self) {
+        self.issued_at = ::std::option::Option::None;
+    }
+
+    pub fn has_issued_at(&self) -> bool {
+        self.issued_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_issued_at(&mut self, v: i64) {
+        self.issued_at = ::std::option::Option::Some(v);
+    }
+
+    // required sint64 expires_at = 7;
+
+
+    pub fn get_expires_at(&self) -> i64 {
+        self.expires_at.unwrap_or(0)
+    }
+    pub fn clear_expires_at(&mut self) {
+        self.expires_at = ::std::option::Option::None;
+    }
+
+    pub fn has_expires_at(&self) -> bool {
+        self.expires_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expires_at(&mut self, v: i64) {
+        self.expires_at = ::std::option::Option::Some(v);
+    }
+
+    // optional sint64 consumed_at = 8;
+
+
+    pub fn get_consumed_at(&self) -> i64 {
+        self.consumed_at.unwrap_or(0)
+    }
+    pub fn clear_consumed_at(&mut self) {
+        self.consumed_at = ::std::option::Option::None;
+    }
+
+    pub fn has_consumed_at(&self) -> bool {
+        self.consumed_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_consumed_at(&mut self, v: i64) {
+        self.consumed_at = ::std::option::Option::Some(v);
+    }
+}
+
+impl ::protobuf::Message for Voucher {
+    fn is_initialized(&self) -> bool {
+        if self.public_key.is_none() {
+            return false;
+        }
+        if self.id.is_none() {
+            return false;
+        }
+        if self.name.is_none() {
+            return false;
+        }
+        if self.number.is_none() {
+            return false;
+        }
+        if self.discount.is_none() {
+            return false;
+        }
+        if self.issued_at.is_none() {
+            return false;
+        }
+        if self.expires_at.is_none() {
+            return false;
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.public_key)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.id)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.name)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.number = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.discount = ::std::option::Option::Some(tmp);
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.issued_at = ::std::option::Option::Some(tmp);
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.expires_at = ::std::option::Option::Some(tmp);
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_sint64()?;
+                    self.consumed_at = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.public_key.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(ref v) = self.id.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        }
+        if let Some(ref v) = self.name.as_ref() {
+            my_size += ::protobuf::rt::string_size(3, &v);
+        }
+        if let Some(v) = self.number {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.discount {
+            my_size += ::protobuf::rt::value_size(5, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.issued_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(6, v);
+        }
+        if let Some(v) = self.expires_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(7, v);
+        }
+        if let Some(v) = self.consumed_at {
+            my_size += ::protobuf::rt::value_varint_zigzag_size(8, v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.public_key.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(ref v) = self.id.as_ref() {
+            os.write_string(2, &v)?;
+        }
+        if let Some(ref v) = self.name.as_ref() {
+            os.write_string(3, &v)?;
+        }
+        if let Some(v) = self.number {
+            os.write_uint32(4, v)?;
+        }
+        if let Some(v) = self.discount {
+            os.write_uint64(5, v)?;
+        }
+        if let Some(v) = self.issued_at {
+            os.write_sint64(6, v)?;
+        }
+        if let Some(v) = self.expires_at {
+            os.write_sint64(7, v)?;
+        }
+        if let Some(v) = self.consumed_at {
+            os.write_sint64(8, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Voucher {
+        Voucher::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "public_key",
+                |m: &Voucher| { &m.public_key },
+                |m: &mut Voucher| { &mut m.public_key },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "id",
+                |m: &Voucher| { &m.id },
+                |m: &mut Voucher| { &mut m.id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "name",
+                |m: &Voucher| { &m.name },
+                |m: &mut Voucher| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "number",
+                |m: &Voucher| { &m.number },
+                |m: &mut Voucher| { &mut m.number },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "discount",
+                |m: &Voucher| { &m.discount },
+                |m: &mut Voucher| { &mut m.discount },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "issued_at",
+                |m: &Voucher| { &m.issued_at },
+                |m: &mut Voucher| { &mut m.issued_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "expires_at",
+                |m: &Voucher| { &m.expires_at },
+                |m: &mut Voucher| { &mut m.expires_at },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeSint64>(
+                "consumed_at",
+                |m: &Voucher| { &m.consumed_at },
+                |m: &mut Voucher| { &mut m.consumed_at },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Voucher>(
+                "Voucher",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Voucher {
+        static instance: ::protobuf::rt::LazyV2<Voucher> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Voucher::new)
+    }
+}
+
+impl ::protobuf::Clear for Voucher {
+    fn clear(&mut self) {
+        self.public_key.clear();
+        self.id.clear();
+        self.name.clear();
+        self.number = ::std::option::Option::None;
+        self.discount = ::std::option::Option::None;
+        self.issued_at = ::std::option::Option::None;
+        self.expires_at = ::std::option::Option::None;
+        self.consumed_at = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Voucher {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Voucher {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct VoucherContainer {
+    // message fields
+    pub entries: ::protobuf::RepeatedField<Voucher>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a VoucherContainer {
+    fn default() -> &'a VoucherContainer {
+        <VoucherContainer as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl VoucherContainer {
+    pub fn new() -> VoucherContainer {
+        ::std::default::Default::default()
+    }
+
+    // repeated .Voucher entries = 1;
+
+
+    pub fn get_entries(&self) -> &[Voucher] {
+        &self.entries
+    }
+    pub fn clear_entries(&mut self) {
+        self.entries.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_entries(&mut self, v: ::protobuf::RepeatedField<Voucher>) {
+        self.entries = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_entries(&mut self) -> &mut ::protobuf::RepeatedField<Voucher> {
+        &mut self.entries
+    }
+
+    // Take field
+    pub fn take_entries(&mut self) -> ::protobuf::RepeatedField<Voucher> {
+        ::std::mem::replace(&mut self.entries, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for VoucherContainer {
+    fn is_initialized(&self) -> bool {
+        for v in &self.entries {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.entries)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.entries {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.entries {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> VoucherContainer {
+        VoucherContainer::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Voucher>>(
+                "entries",
+                |m: &VoucherContainer| { &m.entries },
+                |m: &mut VoucherContainer| { &mut m.entries },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<VoucherContainer>(
+                "VoucherContainer",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static VoucherContainer {
+        static instance: ::protobuf::rt::LazyV2<VoucherContainer> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(VoucherContainer::new)
+    }
+}
+
+impl ::protobuf::Clear for VoucherContainer {
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for VoucherContainer {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for VoucherContainer {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\rvoucher.proto\"\xef\x01\n\x07Voucher\x12\x1f\n\npublic_key\x18\x01\
+    \x20\x02(\tR\tpublicKeyB\0\x12\x10\n\x02id\x18\x02\x20\x02(\tR\x02idB\0\
+    \x12\x14\n\x04name\x18\x03\x20\x02(\tR\x04nameB\0\x12\x18\n\x06number\
+    \x18\x04\x20\x02(\rR\x06numberB\0\x12\x1c\n\x08discount\x18\x05\x20\x02(\
+    \x04R\x08discountB\0\x12\x1d\n\tissued_at\x18\x06\x20\x02(\x12R\x08issue\
+    dAtB\0\x12\x1f\n\nexpires_at\x18\x07\x20\x02(\x12R\texpiresAtB\0\x12!\n\
+    \x0bconsumed_at\x18\x08\x20\x01(\x12R\nconsumedAtB\0:\0\":\n\x10VoucherC\
+    ontainer\x12$\n\x07entries\x18\x01\x20\x03(\x0b2\x08.VoucherR\x07entries\
+    B\0:\0B\0b\x06proto2\
+";
+
+static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    file_descriptor_proto_lazy.get(|| {
+        parse_descriptor_proto()
+    })
+}
@@ -6,11 +6,19 @@ use regex::Regex;
 use sawtooth_sdk::messages::events::Event;
 use sawtooth_sdk::messages::transaction_receipt::{StateChange, StateChangeList};
 use std::cell::RefCell;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::rc::Rc;
 
-use archer::{Account, ArcherStructs, ArcherTypes, Merchant, NAME as NAMESPACE};
+use archer::{
+    Account, ArcherEvent, ArcherStructs, ArcherTypes, CatalogItem, Merchant, Settlement, Voucher,
+    NAME as NAMESPACE,
+};
 use archer_protobuf::deserialize_data;
-use database::models::{Block, NewAccount, NewMerchant};
+use database::models::{
+    Block, NewAccount, NewAccountTier, NewCatalogItem, NewLot, NewMerchant, NewSettlement,
+    NewVoucher,
+};
 use database::PgPool;
 use database::*;
 
@@ -34,6 +42,9 @@ pub fn handle_events(events: Vec<Event>, connection: &PgConnection) {
     if !resolve_if_forked(block_num, &block_id, connection) {
         apply_state_changes(events.as_slice(), block_num, &block_id, connection);
     }
+    for (event, attributes) in parse_archer_events(&events) {
+        info!("{:?} in block {}: {:?}", event, block_num, attributes);
+    }
     let transaction_result = commit(connection);
     match transaction_result {
         Ok(_) => {}
@@ -53,7 +64,13 @@ pub fn apply_state_changes(
     let changes = parse_state_changes(&events);
     for change in changes.iter() {
         let (data_type, mut resources): (ArcherTypes, Vec<ArcherStructs>) =
-            deserialize_data(change.get_address(), change.get_value().to_vec());
+            match deserialize_data(change.get_address(), change.get_value().to_vec()) {
+                Ok(deserialized) => deserialized,
+                Err(err) => {
+                    error!("Skipping state change: {}", err);
+                    continue;
+                }
+            };
         insert_block(block_num, block_id, connection).expect("Error inserting block");
         match data_type {
             ArcherTypes::Account => {
@@ -78,6 +95,41 @@ pub fn apply_state_changes(
                     .collect();
                 apply_merchant_change(block_num, merchants, connection);
             }
+            ArcherTypes::CatalogItem => {
+                let items = resources
+                    .drain(..)
+                    .map(|resource| {
+                        resource
+                            .catalog_item()
+                            .expect("Error converting resource to catalog item")
+                    })
+                    .collect();
+                apply_catalog_item_change(block_num, items, connection);
+            }
+            ArcherTypes::Voucher => {
+                let vouchers = resources
+                    .drain(..)
+                    .map(|resource| {
+                        resource
+                            .voucher()
+                            .expect("Error converting resource to voucher")
+                    })
+                    .collect();
+                apply_voucher_change(block_num, vouchers, connection);
+            }
+            ArcherTypes::Settlement => {
+                let settlements = resources
+                    .drain(..)
+                    .map(|resource| {
+                        resource
+                            .settlement()
+                            .expect("Error converting resource to settlement")
+                    })
+                    .collect();
+                apply_settlement_change(block_num, settlements, connection);
+            }
+            // Earn rules are only read by the processor and are not kept in the read model
+            ArcherTypes::EarnRule => {}
         }
     }
 }
@@ -105,18 +157,98 @@ pub fn parse_state_changes(events: &[Event]) -> Vec<StateChange> {
 }
 
 pub fn apply_account_change(block_num: i64, accounts: Vec<Account>, connection: &PgConnection) {
-    for account in accounts {
-        let new_account = NewAccount {
-            name: &account.name,
-            number: account.number as i32,
-            balance: account.balance,
-            start_block_num: Some(block_num),
-            end_block_num: Some(MAX_BLOCK_NUMBER),
-        };
-        insert_account(new_account, connection).expect("Error inserting new account");
+    for account in unique_accounts(accounts) {
+        // Numbers are never reused, so closing them again on later changes is a no-op
+        for previous_number in account.previous_numbers.iter() {
+            close_account(
+                &account.name,
+                *previous_number as i32,
+                block_num,
+                connection,
+            )
+            .expect("Error closing renumbered account");
+        }
+
+        // One row for the shared pool, under the empty currency, then one per merchant currency
+        let shared_pool = (String::new(), account.balance);
+        for (currency, balance) in
+            std::iter::once(&shared_pool).chain(account.currency_balances.iter())
+        {
+            let new_account = NewAccount {
+                name: &account.name,
+                number: account.number as i32,
+                balance: i64::try_from(*balance).expect("Balance exceeds the read model range"),
+                start_block_num: Some(block_num),
+                end_block_num: Some(MAX_BLOCK_NUMBER),
+                previous_number: account
+                    .previous_numbers
+                    .last()
+                    .map(|previous_number| *previous_number as i32),
+                status: account.status.as_str(),
+                closure_reason: account.closure_reason.as_deref(),
+                currency,
+                tier: account.tier.as_deref(),
+                lifetime_earned: i64::try_from(account.lifetime_earned)
+                    .expect("Lifetime earnings exceed the read model range"),
+            };
+            insert_account(new_account, connection).expect("Error inserting new account");
+        }
+
+        if let Some(tier_changed_at) = account.tier_changed_at {
+            let new_account_tier = NewAccountTier {
+                name: &account.name,
+                number: account.number as i32,
+                tier: account.tier.as_deref(),
+                changed_at: NaiveDateTime::from_timestamp(tier_changed_at, 0),
+                start_block_num: Some(block_num),
+                end_block_num: Some(MAX_BLOCK_NUMBER),
+            };
+            insert_account_tier(new_account_tier, connection)
+                .expect("Error inserting account tier");
+        }
+
+        let new_lots: Vec<NewLot> = account
+            .lots
+            .iter()
+            .map(|lot| NewLot {
+                name: &account.name,
+                number: account.number as i32,
+                lot_id: lot.id as i64,
+                amount: i64::try_from(lot.amount).expect("Lot exceeds the read model range"),
+                earned_at: NaiveDateTime::from_timestamp(lot.earned_at, 0),
+                expires_at: lot
+                    .expires_at
+                    .map(|expires_at| NaiveDateTime::from_timestamp(expires_at, 0)),
+                start_block_num: Some(block_num),
+                end_block_num: Some(MAX_BLOCK_NUMBER),
+                issuer: lot.issuer.as_deref(),
+                currency: lot.currency.as_deref().unwrap_or(""),
+            })
+            .collect();
+        insert_lots(
+            &account.name,
+            account.number as i32,
+            new_lots,
+            block_num,
+            connection,
+        )
+        .expect("Error inserting account lots");
     }
 }
 
+/// Keeps the last entry for each name and number, containers written before the
+/// processor rejected duplicates may still hold several
+fn unique_accounts(accounts: Vec<Account>) -> Vec<Account> {
+    let mut seen: HashSet<(String, u32)> = HashSet::new();
+    let mut unique: Vec<Account> = accounts
+        .into_iter()
+        .rev()
+        .filter(|account| seen.insert((account.name.clone(), account.number)))
+        .collect();
+    unique.reverse();
+    unique
+}
+
 pub fn apply_merchant_change(block_num: i64, merchants: Vec<Merchant>, connection: &PgConnection) {
     for merchant in merchants {
         let new_merchant = NewMerchant {
@@ -125,11 +257,97 @@ pub fn apply_merchant_change(block_num: i64, merchants: Vec<Merchant>, connectio
             created: &NaiveDateTime::from_timestamp(merchant.timestamp, 0),
             start_block_num: Some(block_num),
             end_block_num: Some(MAX_BLOCK_NUMBER),
+            successor_public_key: merchant.successor_public_key.as_deref(),
         };
         insert_merchant(new_merchant, connection).expect("Error inserting new merchant");
     }
 }
 
+pub fn apply_catalog_item_change(
+    block_num: i64,
+    items: Vec<CatalogItem>,
+    connection: &PgConnection,
+) {
+    for item in items {
+        let new_item = NewCatalogItem {
+            public_key: &item.public_key,
+            item_id: &item.id,
+            description: &item.description,
+            point_cost: i64::try_from(item.point_cost)
+                .expect("Point cost exceeds the read model range"),
+            stock: i64::try_from(item.stock).expect("Stock exceeds the read model range"),
+            start_block_num: Some(block_num),
+            end_block_num: Some(MAX_BLOCK_NUMBER),
+        };
+        insert_catalog_item(new_item, connection).expect("Error inserting catalog item");
+    }
+}
+
+pub fn apply_voucher_change(block_num: i64, vouchers: Vec<Voucher>, connection: &PgConnection) {
+    for voucher in vouchers {
+        let new_voucher = NewVoucher {
+            public_key: &voucher.public_key,
+            voucher_id: &voucher.id,
+            name: &voucher.name,
+            number: voucher.number as i32,
+            discount: i64::try_from(voucher.discount)
+                .expect("Discount exceeds the read model range"),
+            issued_at: NaiveDateTime::from_timestamp(voucher.issued_at, 0),
+            expires_at: voucher
+                .expires_at
+                .map(|expires_at| NaiveDateTime::from_timestamp(expires_at, 0)),
+            consumed_at: voucher
+                .consumed_at
+                .map(|consumed_at| NaiveDateTime::from_timestamp(consumed_at, 0)),
+            start_block_num: Some(block_num),
+            end_block_num: Some(MAX_BLOCK_NUMBER),
+        };
+        insert_voucher(new_voucher, connection).expect("Error inserting voucher");
+    }
+}
+
+pub fn apply_settlement_change(
+    block_num: i64,
+    settlements: Vec<Settlement>,
+    connection: &PgConnection,
+) {
+    for settlement in settlements {
+        let new_settlement = NewSettlement {
+            issuer: &settlement.issuer,
+            redeemer: &settlement.redeemer,
+            period_start: NaiveDateTime::from_timestamp(settlement.period_start, 0),
+            amount: i64::try_from(settlement.amount)
+                .expect("Settlement exceeds the read model range"),
+            start_block_num: Some(block_num),
+            end_block_num: Some(MAX_BLOCK_NUMBER),
+        };
+        insert_settlement(new_settlement, connection).expect("Error inserting settlement");
+    }
+}
+
+pub fn parse_archer_events(events: &[Event]) -> Vec<(ArcherEvent, Vec<(String, String)>)> {
+    events
+        .iter()
+        .filter_map(|event| {
+            ArcherEvent::try_from(event.get_event_type())
+                .ok()
+                .map(|archer_event| {
+                    let attributes = event
+                        .get_attributes()
+                        .iter()
+                        .map(|attribute| {
+                            (
+                                String::from(attribute.get_key()),
+                                String::from(attribute.get_value()),
+                            )
+                        })
+                        .collect();
+                    (archer_event, attributes)
+                })
+        })
+        .collect()
+}
+
 pub fn parse_new_block(events: &[Event]) -> Option<(i64, String)> {
     let block_event: Option<&Event> = events
         .iter()
@@ -181,6 +399,28 @@ pub fn resolve_if_forked(block_num: i64, block_id: &str, connection: &PgConnecti
 
 #[cfg(test)]
 mod test {
+    use super::*;
+    use archer::AccountStatus;
+    use sawtooth_sdk::messages::events::Event_Attribute;
+
+    fn make_account(name: &str, number: u32, balance: u64) -> Account {
+        Account {
+            name: String::from(name),
+            number,
+            balance,
+            currency_balances: Vec::new(),
+            lots: Vec::new(),
+            owner_public_key: None,
+            previous_numbers: Vec::new(),
+            status: AccountStatus::Active,
+            closure_reason: None,
+            lifetime_earned: 0,
+            tier: None,
+            tier_changed_at: None,
+            start_block_num: None,
+            end_block_num: None,
+        }
+    }
 
     #[test]
     fn test_apply_state_changes() {}
@@ -191,6 +431,39 @@ mod test {
     #[test]
     fn test_apply_account_change() {}
 
+    #[test]
+    fn test_parse_archer_events() {
+        let mut block_event = Event::new();
+        block_event.set_event_type(String::from("sawtooth/block-commit"));
+        let mut earned_event = Event::new();
+        earned_event.set_event_type(String::from(ArcherEvent::PointsEarned.as_str()));
+        let mut attribute = Event_Attribute::new();
+        attribute.set_key(String::from("balance"));
+        attribute.set_value(String::from("50"));
+        earned_event.mut_attributes().push(attribute);
+
+        let events = parse_archer_events(&[block_event, earned_event]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, ArcherEvent::PointsEarned);
+        assert_eq!(
+            events[0].1,
+            vec![(String::from("balance"), String::from("50"))]
+        );
+    }
+
+    #[test]
+    fn test_unique_accounts() {
+        let accounts = vec![
+            make_account("John Doe", 12345, 0),
+            make_account("John Doe", 54321, 20),
+            make_account("John Doe", 12345, 50),
+        ];
+        let unique = unique_accounts(accounts);
+        assert_eq!(unique.len(), 2);
+        assert_eq!((unique[0].number, unique[0].balance), (54321, 20));
+        assert_eq!((unique[1].number, unique[1].balance), (12345, 50));
+    }
+
     #[test]
     fn test_parse_new_block() {}
 
@@ -1,4 +1,4 @@
-use archer::{any_as_u8_slice, NAME as NAMESPACE};
+use archer::{any_as_u8_slice, ArcherEvent, NAME as NAMESPACE};
 use log::{error, info, warn};
 use protobuf::{parse_from_bytes, RepeatedField};
 use sawtooth_sdk::messages::client_event::{
@@ -196,6 +196,13 @@ impl Subscriber {
         delta_sub.set_filters(RepeatedField::from_vec(vec![delta_sub_event_filter]));
 
         self.subscriptions.append(&mut vec![block_sub, delta_sub]);
+
+        // Events the processor adds itself, they describe the operations behind the deltas
+        for event in ArcherEvent::ALL.iter() {
+            let mut archer_sub = EventSubscription::new();
+            archer_sub.set_event_type(String::from(event.as_str()));
+            self.subscriptions.push(archer_sub);
+        }
     }
 
     pub fn add_handler(&mut self, handler: Box<dyn Fn(Vec<Event>)>) {